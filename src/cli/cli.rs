@@ -1,4 +1,4 @@
-use crate::cli::validator::{isValidDirectory, isValidDriverClass, isValidPath, isValidPathIncludeWildcard, isValidSystemPath};
+use crate::cli::validator::{isValidDirectory, isValidDriverClass, isValidIndexFormat, isValidPath, isValidProblemCodes, isValidReportFormat, isValidPathIncludeWildcard, isValidSystemPath, isValidCacheSize, isValidSignaturePolicy};
 use crate::i18n::getLocaleText;
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
@@ -18,6 +18,17 @@ pub const RENAME_DRIVER: &str = "RenameDriver";
 pub const EJECTDRIVERCD: &str = "EjectDriverCD";
 pub const PROGRAM_PATH: &str = "ProgramPath";
 pub const SYSTEM_ROOT: &str = "SystemRoot";
+pub const INDEX_FORMAT: &str = "Format";
+pub const UPDATE_INDEX: &str = "Update";
+pub const INVENTORY: &str = "Inventory";
+pub const PROBLEM: &str = "Problem";
+pub const LIST: &str = "List";
+pub const IN_USE: &str = "InUse";
+pub const UNUSED: &str = "Unused";
+pub const CACHE_PATH: &str = "Cache";
+pub const CACHE_SIZE: &str = "CacheSize";
+pub const PURGE: &str = "Purge";
+pub const SIGNATURE_POLICY: &str = "SignaturePolicy";
 
 pub fn cli() -> ArgMatches {
     Command::new(env!("CARGO_PKG_NAME"))
@@ -78,6 +89,83 @@ pub fn cli() -> ArgMatches {
                         .long(PASSWORD)
                         .value_name(PASSWORD)
                         .help(getLocaleText("package-password", None)),
+                )
+                // 选项-索引格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidIndexFormat)
+                        .help(getLocaleText("index-format", None)),
+                )
+                // 选项-增量更新索引
+                .arg(
+                    Arg::new(UPDATE_INDEX)
+                        .short('u')
+                        .long(UPDATE_INDEX)
+                        .action(ArgAction::SetTrue)
+                        .help(getLocaleText("update-index", None)),
+                ),
+        )
+        // 合并索引
+        .subcommand(
+            Command::new("merge-index")
+                .about(getLocaleText("merge-index", None))
+                // 参数-索引文件
+                .arg(
+                    Arg::new(INDEX_PATH)
+                        .value_name(INDEX_PATH)
+                        .value_parser(isValidPathIncludeWildcard)
+                        .required(true)
+                        .index(1)
+                        .help(getLocaleText("merge-index-path", None)),
+                )
+                // 参数-输出位置
+                .arg(
+                    Arg::new(EXPORT_PATH)
+                        .value_name(EXPORT_PATH)
+                        .required(true)
+                        .index(2)
+                        .help(getLocaleText("merge-save-path", None)),
+                )
+                // 选项-索引格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidIndexFormat)
+                        .help(getLocaleText("index-format", None)),
+                ),
+        )
+        // 拆分索引
+        .subcommand(
+            Command::new("split-index")
+                .about(getLocaleText("split-index", None))
+                // 参数-索引文件
+                .arg(
+                    Arg::new(INDEX_PATH)
+                        .value_name(INDEX_PATH)
+                        .value_parser(isValidPath)
+                        .required(true)
+                        .index(1),
+                )
+                // 参数-输出位置
+                .arg(
+                    Arg::new(EXPORT_PATH)
+                        .value_name(EXPORT_PATH)
+                        .index(2)
+                        .help(getLocaleText("split-save-path", None)),
+                )
+                // 选项-索引格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidIndexFormat)
+                        .help(getLocaleText("index-format", None)),
                 ),
         )
         // 加载驱动
@@ -115,6 +203,15 @@ pub fn cli() -> ArgMatches {
                         .long(ALL_DEVICE)
                         .help(getLocaleText("match-all-device", None)),
                 )
+                // 选项-问题代码
+                .arg(
+                    Arg::new(PROBLEM)
+                        .long(PROBLEM)
+                        .value_name(PROBLEM)
+                        .value_parser(isValidProblemCodes)
+                        .conflicts_with(ALL_DEVICE)
+                        .help(getLocaleText("problem-code", None)),
+                )
                 // 选项-驱动类别
                 .arg(
                     Arg::new(DRIVE_CLASS)
@@ -139,6 +236,113 @@ pub fn cli() -> ArgMatches {
                         .long(EJECTDRIVERCD)
                         .help(getLocaleText("eject-driver-cd", None)),
                 )
+                // 选项-硬件清单
+                .arg(
+                    Arg::new(INVENTORY)
+                        .short('i')
+                        .long(INVENTORY)
+                        .value_name(INVENTORY)
+                        .value_parser(isValidPath)
+                        .help(getLocaleText("inventory-path", None)),
+                )
+                // 选项-解压缓存
+                .arg(
+                    Arg::new(CACHE_PATH)
+                        .long(CACHE_PATH)
+                        .value_name(CACHE_PATH)
+                        .conflicts_with(EXTRACT_PATH)
+                        .help(getLocaleText("cache-path", None)),
+                )
+                // 选项-解压缓存大小上限
+                .arg(
+                    Arg::new(CACHE_SIZE)
+                        .long(CACHE_SIZE)
+                        .value_name(CACHE_SIZE)
+                        .value_parser(isValidCacheSize)
+                        .requires(CACHE_PATH)
+                        .help(getLocaleText("cache-size", None)),
+                )
+                // 选项-签名策略
+                .arg(
+                    Arg::new(SIGNATURE_POLICY)
+                        .long(SIGNATURE_POLICY)
+                        .value_name(SIGNATURE_POLICY)
+                        .value_parser(isValidSignaturePolicy)
+                        .help(getLocaleText("signature-policy", None)),
+                )
+        )
+        // 预览匹配结果
+        .subcommand(
+            Command::new("match-driver")
+                .about(getLocaleText("match-driver", None))
+                // 参数-驱动
+                .arg(
+                    Arg::new(DRIVE_PATH)
+                        .value_name(DRIVE_PATH)
+                        .value_parser(isValidPathIncludeWildcard)
+                        .required(true)
+                        .index(1)
+                        .help(getLocaleText("package-path", None)),
+                )
+                // 参数-索引文件
+                .arg(
+                    Arg::new(INDEX_PATH)
+                        .value_name(INDEX_PATH)
+                        .index(2)
+                        .help(getLocaleText("index-path", None)),
+                )
+                // 选项-指定压缩包密码
+                .arg(
+                    Arg::new(PASSWORD)
+                        .short('p')
+                        .long(PASSWORD)
+                        .value_name(PASSWORD)
+                        .help(getLocaleText("package-password", None)),
+                )
+                // 选项-匹配所有设备（包括已安装驱动设备）
+                .arg(
+                    Arg::new(ALL_DEVICE)
+                        .short('a')
+                        .long(ALL_DEVICE)
+                        .action(ArgAction::SetTrue)
+                        .help(getLocaleText("match-all-device", None)),
+                )
+                // 选项-问题代码
+                .arg(
+                    Arg::new(PROBLEM)
+                        .long(PROBLEM)
+                        .value_name(PROBLEM)
+                        .value_parser(isValidProblemCodes)
+                        .conflicts_with(ALL_DEVICE)
+                        .help(getLocaleText("problem-code", None)),
+                )
+                // 选项-驱动类别
+                .arg(
+                    Arg::new(DRIVE_CLASS)
+                        .short('c')
+                        .long(DRIVE_CLASS)
+                        .value_name(DRIVE_CLASS)
+                        .value_parser(isValidDriverClass)
+                        .help(getLocaleText("driver-category", None)),
+                )
+                // 选项-报告格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidReportFormat)
+                        .help(getLocaleText("report-format", None)),
+                )
+                // 选项-硬件清单
+                .arg(
+                    Arg::new(INVENTORY)
+                        .short('i')
+                        .long(INVENTORY)
+                        .value_name(INVENTORY)
+                        .value_parser(isValidPath)
+                        .help(getLocaleText("inventory-path", None)),
+                )
         )
         // 加载离线驱动
         .subcommand(
@@ -205,6 +409,23 @@ pub fn cli() -> ArgMatches {
                         .long(MATCH_DEVICE)
                         .help(getLocaleText("match-device", None)),
                 )
+                // 选项-硬件清单
+                .arg(
+                    Arg::new(INVENTORY)
+                        .short('i')
+                        .long(INVENTORY)
+                        .value_name(INVENTORY)
+                        .value_parser(isValidPath)
+                        .help(getLocaleText("inventory-path", None)),
+                )
+                // 选项-签名策略
+                .arg(
+                    Arg::new(SIGNATURE_POLICY)
+                        .long(SIGNATURE_POLICY)
+                        .value_name(SIGNATURE_POLICY)
+                        .value_parser(isValidSignaturePolicy)
+                        .help(getLocaleText("signature-policy", None)),
+                )
         )
         // 导出驱动
         .subcommand(
@@ -222,7 +443,7 @@ pub fn cli() -> ArgMatches {
                 .arg(
                     Arg::new(EXPORT_PATH)
                         .value_name(EXPORT_PATH)
-                        .required(true)
+                        .required_unless_present(LIST)
                         .index(2),
                 )
                 // 选项-驱动类别
@@ -242,6 +463,32 @@ pub fn cli() -> ArgMatches {
                         .value_name(DRIVER_NAME)
                         .help(getLocaleText("driver-name", None)),
                 )
+                // 选项-列出驱动（不导出）
+                .arg(
+                    Arg::new(LIST)
+                        .short('l')
+                        .long(LIST)
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with(EXPORT_PATH)
+                        .help(getLocaleText("driver-list", None)),
+                )
+                // 选项-报告格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidReportFormat)
+                        .requires(LIST)
+                        .help(getLocaleText("report-format", None)),
+                )
+                // 选项-仅正在使用的驱动
+                .arg(
+                    Arg::new(IN_USE)
+                        .long(IN_USE)
+                        .action(ArgAction::SetTrue)
+                        .help(getLocaleText("driver-in-use", None)),
+                )
         )
         // 删除驱动
         .subcommand(
@@ -271,6 +518,13 @@ pub fn cli() -> ArgMatches {
                         .value_parser(isValidDriverClass)
                         .help(getLocaleText("driver-category", None)),
                 )
+                // 选项-仅未使用的驱动
+                .arg(
+                    Arg::new(UNUSED)
+                        .long(UNUSED)
+                        .action(ArgAction::SetTrue)
+                        .help(getLocaleText("driver-unused", None)),
+                )
         )
         // 整理驱动
         .subcommand(
@@ -319,6 +573,50 @@ pub fn cli() -> ArgMatches {
                         .help(getLocaleText("driver-package-program-path", None)),
                 )
         )
+        // 保存硬件清单
+        .subcommand(
+            Command::new("inventory")
+                .about(getLocaleText("inventory", None))
+                // 参数-保存位置
+                .arg(
+                    Arg::new(EXPORT_PATH)
+                        .value_name(EXPORT_PATH)
+                        .required(true)
+                        .index(1)
+                        .help(getLocaleText("inventory-save-path", None)),
+                )
+        )
+        // 解压缓存
+        .subcommand(
+            Command::new("cache")
+                .about(getLocaleText("cache", None))
+                // 参数-缓存目录
+                .arg(
+                    Arg::new(CACHE_PATH)
+                        .value_name(CACHE_PATH)
+                        .value_parser(isValidDirectory)
+                        .required(true)
+                        .index(1)
+                        .help(getLocaleText("cache-path", None)),
+                )
+                // 选项-清空缓存
+                .arg(
+                    Arg::new(PURGE)
+                        .long(PURGE)
+                        .action(ArgAction::SetTrue)
+                        .help(getLocaleText("cache-purge", None)),
+                )
+                // 选项-报告格式
+                .arg(
+                    Arg::new(INDEX_FORMAT)
+                        .short('f')
+                        .long(INDEX_FORMAT)
+                        .value_name(INDEX_FORMAT)
+                        .value_parser(isValidReportFormat)
+                        .conflicts_with(PURGE)
+                        .help(getLocaleText("report-format", None)),
+                )
+        )
         // 扫描设备硬件更改
         .subcommand(
             Command::new("scan-devices")
@@ -1,8 +1,13 @@
-use crate::cli::cli::{cli, ALL_DEVICE, DRIVER_NAME, DRIVE_CLASS, DRIVE_PATH, EJECTDRIVERCD, EXPORT_PATH, EXTRACT_PATH, INDEX_PATH, MATCH_DEVICE, PASSWORD, PROGRAM_PATH, RENAME_DRIVER, SYSTEM_DRIVE};
+use crate::cli::cli::{cli, ALL_DEVICE, DRIVER_NAME, DRIVE_CLASS, DRIVE_PATH, EJECTDRIVERCD, EXPORT_PATH, EXTRACT_PATH, INDEX_FORMAT, INDEX_PATH, INVENTORY, IN_USE, LIST, CACHE_PATH, CACHE_SIZE, PURGE, MATCH_DEVICE, SIGNATURE_POLICY, PASSWORD, PROBLEM, PROGRAM_PATH, RENAME_DRIVER, SYSTEM_DRIVE, UNUSED, UPDATE_INDEX};
 use crate::command;
+use crate::command::create_index::IndexFormat;
+use crate::command::match_driver::ReportFormat;
 use crate::i18n::getLocaleText;
-use crate::utils::console::{writeConsole, ConsoleType};
+use crate::utils::console::{setQuiet, writeConsole, ConsoleType};
+use crate::utils::deviceBackend::{parseProblemCodes, DeviceFilter};
+use crate::utils::extractCache::{ExtractCache, DEFAULT_CACHE_SIZE};
 use crate::utils::setupAPI;
+use crate::utils::signaturePolicy::SignaturePolicy;
 use crate::utils::util::{ejectDrive, getFileList, isDriverCD};
 use crate::LOG_PATH;
 use clap::ArgMatches;
@@ -35,8 +40,13 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
             driverPath.parent().unwrap().join(indexName)
         };
 
+        let format = matches
+            .get_one::<String>(INDEX_FORMAT)
+            .and_then(|format| IndexFormat::fromName(format));
+        let update = matches.get_flag(UPDATE_INDEX);
+
         writeConsole(ConsoleType::Info, &getLocaleText("processing", None));
-        return match command::create_index::createIndex(&driverPath, password, &indexPath) {
+        return match command::create_index::createIndex(&driverPath, password, &indexPath, format, update) {
             Ok(_) => { Ok(()) }
             Err(e) => {
                 writeConsole(ConsoleType::Err, &e.to_string());
@@ -45,11 +55,69 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
         };
     }
 
+    // 合并索引
+    if let Some(matches) = matches.subcommand_matches("merge-index") {
+        let indexPath = PathBuf::from(matches.get_one::<String>(INDEX_PATH).unwrap());
+        let savePath = PathBuf::from(matches.get_one::<String>(EXPORT_PATH).unwrap());
+        let format = matches
+            .get_one::<String>(INDEX_FORMAT)
+            .and_then(|format| IndexFormat::fromName(format))
+            .unwrap_or(IndexFormat::Json);
+
+        // 处理通配符（合并索引自身不参与合并）
+        let indexName = indexPath.file_name().unwrap().to_str().unwrap();
+        let indexList: Vec<PathBuf> = if indexName.contains('*') || indexName.contains('?') {
+            getFileList(&PathBuf::from(&indexPath.parent().unwrap()), indexName)?
+                .into_iter()
+                .filter(|item| item.canonicalize().ok() != savePath.canonicalize().ok())
+                .collect()
+        } else {
+            vec![indexPath]
+        };
+
+        return match command::merge_index::mergeIndex(&indexList, &savePath, format) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                writeConsole(ConsoleType::Err, &e.to_string());
+                Err(e)
+            }
+        };
+    }
+
+    // 拆分索引
+    if let Some(matches) = matches.subcommand_matches("split-index") {
+        let indexPath = PathBuf::from(matches.get_one::<String>(INDEX_PATH).unwrap());
+        let saveDir = match matches.get_one::<String>(EXPORT_PATH) {
+            Some(path) => PathBuf::from(path),
+            None => indexPath.parent().unwrap().to_path_buf(),
+        };
+        let format = matches.get_one::<String>(INDEX_FORMAT).and_then(|format| IndexFormat::fromName(format));
+
+        return match command::split_index::splitIndex(&indexPath, &saveDir, format) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                writeConsole(ConsoleType::Err, &e.to_string());
+                Err(e)
+            }
+        };
+    }
+
     // 加载驱动
     if let Some(matches) = matches.subcommand_matches("load-driver") {
         let drivePath = PathBuf::from(matches.get_one::<String>(DRIVE_PATH).unwrap());
         let password = matches.get_one::<String>(PASSWORD).map(String::as_str);
         let extractPath = matches.get_one::<String>(EXTRACT_PATH).map(String::as_str);
+        let inventoryPath = matches.get_one::<String>(INVENTORY).map(Path::new);
+        let problemCodes = matches.get_one::<String>(PROBLEM).and_then(|codes| parseProblemCodes(codes)).unwrap_or_default();
+        let filter = DeviceFilter::new(matches.contains_id(ALL_DEVICE), problemCodes);
+        let policy = matches.get_one::<String>(SIGNATURE_POLICY).and_then(|policy| SignaturePolicy::fromName(policy)).unwrap_or_default();
+
+        // 解压缓存（大小上限以 MB 为单位）
+        let cacheSize = matches.get_one::<String>(CACHE_SIZE).and_then(|size| size.trim().parse::<u64>().ok()).unwrap_or(DEFAULT_CACHE_SIZE);
+        let mut cache = match matches.get_one::<String>(CACHE_PATH) {
+            Some(cachePath) => Some(ExtractCache::open(Path::new(cachePath), cacheSize * 1024 * 1024)?),
+            None => None,
+        };
 
         // 弹出免驱设备虚拟光驱
         if matches.contains_id(EJECTDRIVERCD) {
@@ -74,41 +142,19 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
                 return Err(String::from("No driver package was found in this directory").into());
             }
 
-            // 创建索引列表（无索引则使用None）
-            let mut indexList: Vec<Option<PathBuf>> = Vec::new();
-            if matches.contains_id(INDEX_PATH) {
-                let inedxPath = PathBuf::from(matches.get_one::<String>(INDEX_PATH).unwrap());
-                let indexName = inedxPath.file_name().unwrap().to_str().unwrap();
+            // 索引文件列表（支持通配符，多个索引合并使用；未指定时各驱动包自动检测同名索引）
+            let mut indexList: Vec<PathBuf> = Vec::new();
+            if let Some(indexPath) = matches.get_one::<String>(INDEX_PATH).map(PathBuf::from) {
+                let indexName = indexPath.file_name().unwrap().to_str().unwrap();
                 if indexName.contains('*') || indexName.contains('?') {
-                    for item in getFileList(&PathBuf::from(&inedxPath.parent().unwrap()), indexName)
-                        .unwrap()
-                    {
-                        indexList.push(Some(item));
-                    }
+                    indexList = getFileList(&PathBuf::from(&indexPath.parent().unwrap()), indexName).unwrap();
                 } else {
-                    indexList.push(Some(PathBuf::from(matches.get_one::<String>(INDEX_PATH).unwrap())));
+                    indexList.push(indexPath);
                 }
-            } else {
-                indexList.append(
-                    &mut driveList
-                        .iter()
-                        .map(|_item| None)
-                        .collect::<Vec<Option<PathBuf>>>(),
-                );
             }
+            let class: Option<String> = matches.get_one::<String>(DRIVE_CLASS).cloned();
 
-            let mut indexIter = indexList.iter();
-
-            // 遍历驱动包
-            for drivePathItem in driveList.iter() {
-                let index = indexIter.next().unwrap().clone();
-                let class: Option<String> = matches.get_one::<String>(DRIVE_CLASS).cloned();
-
-                let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => drivePathItem.to_str().unwrap().into());
-                writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
-
-                command::load_driver::loadDriver(drivePathItem, password, index, matches.contains_id(ALL_DEVICE), class, extractPath)?;
-            }
+            command::load_driver::loadDriverPackages(&driveList, password, &indexList, &filter, class, policy, extractPath, inventoryPath, cache.as_mut())?;
         } else {
             // 无通配符
             let index = match matches.contains_id(INDEX_PATH) {
@@ -120,10 +166,57 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
             let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => drivePath.to_str().unwrap().into());
             writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
 
-            command::load_driver::loadDriver(&drivePath, password, index, matches.contains_id(ALL_DEVICE), class, extractPath)?;
+            command::load_driver::loadDriver(&drivePath, password, index, &filter, class, policy, extractPath, inventoryPath, cache.as_mut())?;
         }
     }
 
+    // 预览匹配结果
+    if let Some(matches) = matches.subcommand_matches("match-driver") {
+        let drivePath = PathBuf::from(matches.get_one::<String>(DRIVE_PATH).unwrap());
+        let password = matches.get_one::<String>(PASSWORD).map(String::as_str);
+        let class = matches.get_one::<String>(DRIVE_CLASS).cloned();
+        let inventoryPath = matches.get_one::<String>(INVENTORY).map(Path::new);
+        let problemCodes = matches.get_one::<String>(PROBLEM).and_then(|codes| parseProblemCodes(codes)).unwrap_or_default();
+        let filter = DeviceFilter::new(matches.get_flag(ALL_DEVICE), problemCodes);
+        let format = matches
+            .get_one::<String>(INDEX_FORMAT)
+            .and_then(|format| ReportFormat::fromName(format))
+            .unwrap_or(ReportFormat::Table);
+
+        // JSON 报告仅输出到标准输出，其余信息输出到标准错误
+        setQuiet(format == ReportFormat::Json);
+
+        // 处理通配符
+        let driveName = drivePath.file_name().unwrap().to_str().unwrap();
+        let groups = if driveName.contains('*') || driveName.contains('?') {
+            let driveList = getFileList(&PathBuf::from(&drivePath.parent().unwrap()), driveName).unwrap();
+            if driveList.is_empty() {
+                writeConsole(ConsoleType::Err, "No driver package was found in this directory");
+                return Err(String::from("No driver package was found in this directory").into());
+            }
+
+            // 索引文件列表（支持通配符，多个索引合并使用）
+            let mut indexList: Vec<PathBuf> = Vec::new();
+            if let Some(indexPath) = matches.get_one::<String>(INDEX_PATH).map(PathBuf::from) {
+                let indexName = indexPath.file_name().unwrap().to_str().unwrap();
+                if indexName.contains('*') || indexName.contains('?') {
+                    indexList = getFileList(&PathBuf::from(&indexPath.parent().unwrap()), indexName).unwrap();
+                } else {
+                    indexList.push(indexPath);
+                }
+            }
+            command::load_driver::packagesMatchGroups(&driveList, password, &indexList, None)?
+        } else {
+            let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => drivePath.to_str().unwrap().into());
+            writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
+
+            let index = matches.get_one::<String>(INDEX_PATH).map(PathBuf::from);
+            vec![command::load_driver::packageMatchGroup(&drivePath, password, index, None)?]
+        };
+
+        return command::match_driver::matchDriver(&groups, &filter, class, format, inventoryPath);
+    }
+
     // 加载离线驱动
     if let Some(matches) = matches.subcommand_matches("load-offline-driver") {
         let systemDrive = matches.get_one::<String>(SYSTEM_DRIVE).map(Path::new);
@@ -145,6 +238,8 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
         let systemDrive = PathBuf::from(matches.get_one::<String>(SYSTEM_DRIVE).unwrap());
         let drivePath = PathBuf::from(matches.get_one::<String>(DRIVE_PATH).unwrap());
         let password = matches.get_one::<&str>(PASSWORD).cloned();
+        let inventoryPath = matches.get_one::<String>(INVENTORY).map(Path::new);
+        let policy = matches.get_one::<String>(SIGNATURE_POLICY).and_then(|policy| SignaturePolicy::fromName(policy)).unwrap_or_default();
 
         // 处理通配符
         let driveName = drivePath.file_name().unwrap().to_str().unwrap();
@@ -158,7 +253,7 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
                 let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => item.to_str().unwrap().into());
                 writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
 
-                match command::import_driver::import_driver(&systemDrive, &item, password, matches.contains_id(MATCH_DEVICE)) {
+                match command::import_driver::import_driver(&systemDrive, &item, password, matches.contains_id(MATCH_DEVICE), inventoryPath, policy) {
                     Ok(_) => {}
                     Err(e) => {
                         writeConsole(ConsoleType::Err, &e.to_string());
@@ -170,7 +265,7 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
             let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => drivePath.to_str().unwrap().into());
             writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
 
-            return match command::import_driver::import_driver(&systemDrive, &drivePath, password, matches.contains_id(MATCH_DEVICE)) {
+            return match command::import_driver::import_driver(&systemDrive, &drivePath, password, matches.contains_id(MATCH_DEVICE), inventoryPath, policy) {
                 Ok(_) => {
                     Ok(())
                 }
@@ -185,11 +280,28 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
     // 导出驱动
     if let Some(matches) = matches.subcommand_matches("export-driver") {
         let systemDrive = PathBuf::from(matches.get_one::<String>(SYSTEM_DRIVE).unwrap());
+        let name = matches.get_one::<String>(DRIVER_NAME).map(String::as_str);
+        let class = matches.get_one::<String>(DRIVE_CLASS).map(String::as_str);
+
+        // 列出驱动
+        if matches.get_flag(LIST) {
+            let format = matches
+                .get_one::<String>(INDEX_FORMAT)
+                .and_then(|format| ReportFormat::fromName(format))
+                .unwrap_or(ReportFormat::Table);
+            setQuiet(format == ReportFormat::Json);
+            return match command::export_driver::listDrivers(&systemDrive, name, class, format, matches.get_flag(IN_USE)) {
+                Ok(_) => Ok(()),
+                Err(e) => {
+                    writeConsole(ConsoleType::Err, &e.to_string());
+                    Err(e)
+                }
+            };
+        }
+
         let exportPath = PathBuf::from(matches.get_one::<String>(EXPORT_PATH).unwrap());
-        let name = matches.get_one(DRIVER_NAME).cloned();
-        let class = matches.get_one(DRIVE_CLASS).cloned();
 
-        return match command::export_driver::export_driver(&systemDrive, &exportPath, name, class) {
+        return match command::export_driver::export_driver(&systemDrive, &exportPath, name, class, matches.get_flag(IN_USE)) {
             Ok(_) => {
                 writeConsole(ConsoleType::Success, &getLocaleText("driver-export-success", None));
                 Ok(())
@@ -204,10 +316,10 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
     // 删除驱动
     if let Some(matches) = matches.subcommand_matches("remove-driver") {
         let systemDrive = PathBuf::from(matches.get_one::<String>(SYSTEM_DRIVE).unwrap());
-        let driveName = matches.get_one(DRIVER_NAME).cloned();
-        let class = matches.get_one(DRIVE_CLASS).cloned();
+        let driveName = matches.get_one::<String>(DRIVER_NAME).map(String::as_str);
+        let class = matches.get_one::<String>(DRIVE_CLASS).map(String::as_str);
 
-        return match command::remove_driver::remove_driver(&systemDrive, driveName, class) {
+        return match command::remove_driver::remove_driver(&systemDrive, driveName, class, matches.get_flag(UNUSED)) {
             Ok(_) => {
                 writeConsole(ConsoleType::Success, &getLocaleText("driver-remove-success", None));
                 Ok(())
@@ -254,6 +366,42 @@ pub fn matches(matches: ArgMatches) -> Result<(), Box<dyn Error>> {
         };
     }
 
+    // 保存硬件清单
+    if let Some(matches) = matches.subcommand_matches("inventory") {
+        let savePath = PathBuf::from(matches.get_one::<String>(EXPORT_PATH).unwrap());
+
+        return match command::inventory::saveInventory(&savePath) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                writeConsole(ConsoleType::Err, &e.to_string());
+                Err(e)
+            }
+        };
+    }
+
+    // 解压缓存
+    if let Some(matches) = matches.subcommand_matches("cache") {
+        let cachePath = PathBuf::from(matches.get_one::<String>(CACHE_PATH).unwrap());
+
+        let result = if matches.get_flag(PURGE) {
+            command::cache::purgeCache(&cachePath)
+        } else {
+            let format = matches
+                .get_one::<String>(INDEX_FORMAT)
+                .and_then(|format| ReportFormat::fromName(format))
+                .unwrap_or(ReportFormat::Table);
+            setQuiet(format == ReportFormat::Json);
+            command::cache::listCache(&cachePath, format)
+        };
+        return match result {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                writeConsole(ConsoleType::Err, &e.to_string());
+                Err(e)
+            }
+        };
+    }
+
     // 扫描硬件设备更改
     if let Some(_matches) = matches.subcommand_matches("scan-devices") {
         unsafe {
@@ -1,3 +1,7 @@
+use crate::command::create_index::IndexFormat;
+use crate::command::match_driver::ReportFormat;
+use crate::utils::deviceBackend::parseProblemCodes;
+use crate::utils::signaturePolicy::SignaturePolicy;
 use crate::i18n::getLocaleText;
 use std::path::{Path, PathBuf};
 
@@ -184,3 +188,43 @@ pub fn isValidDriverClass(class: &str) -> Result<(), String> {
     }
     Err(getLocaleText("not-driver-category", None))
 }
+
+/// 是否为有效的索引格式
+pub fn isValidIndexFormat(format: &str) -> Result<(), String> {
+    match IndexFormat::fromName(format) {
+        Some(_) => Ok(()),
+        None => Err(getLocaleText("not-index-format", None)),
+    }
+}
+
+/// 是否为有效的报告格式
+pub fn isValidReportFormat(format: &str) -> Result<(), String> {
+    match ReportFormat::fromName(format) {
+        Some(_) => Ok(()),
+        None => Err(getLocaleText("not-report-format", None)),
+    }
+}
+
+/// 是否为有效的问题代码列表
+pub fn isValidProblemCodes(codes: &str) -> Result<(), String> {
+    match parseProblemCodes(codes) {
+        Some(_) => Ok(()),
+        None => Err(getLocaleText("not-problem-code", None)),
+    }
+}
+
+/// 是否为有效的缓存大小（MB，正整数）
+pub fn isValidCacheSize(size: &str) -> Result<(), String> {
+    match size.trim().parse::<u64>() {
+        Ok(size) if size > 0 => Ok(()),
+        _ => Err(getLocaleText("not-cache-size", None)),
+    }
+}
+
+/// 是否为有效的签名策略
+pub fn isValidSignaturePolicy(policy: &str) -> Result<(), String> {
+    match SignaturePolicy::fromName(policy) {
+        Some(_) => Ok(()),
+        None => Err(getLocaleText("not-signature-policy", None)),
+    }
+}
@@ -0,0 +1,73 @@
+use crate::command::export_driver::formatSize;
+use crate::command::match_driver::ReportFormat;
+use crate::i18n::getLocaleText;
+use crate::utils::console::{formatTable, writeConsole, ConsoleType};
+use crate::utils::extractCache::{CacheEntry, ExtractCache, DEFAULT_CACHE_SIZE};
+use chrono::{DateTime, Local};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// 列出解压缓存
+/// # 参数
+/// 1. 缓存目录
+/// 2. 报告格式
+pub fn listCache(cachePath: &Path, format: ReportFormat) -> Result<(), Box<dyn Error>> {
+    let cache = ExtractCache::open(cachePath, DEFAULT_CACHE_SIZE * 1024 * 1024)?;
+    let mut entryList: Vec<&CacheEntry> = cache.Entries.iter().collect();
+    // 最近使用的在前
+    entryList.sort_by_key(|entry| Reverse(entry.LastUsed));
+
+    match format {
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&entryList)?),
+        ReportFormat::Table if entryList.is_empty() => writeConsole(ConsoleType::Info, &getLocaleText("cache-empty", None)),
+        ReportFormat::Table => {
+            for line in cacheTable(&entryList) {
+                println!("    {}", line);
+            }
+            println!();
+            let args: HashMap<String, FluentValue> = hash_map!(
+                "count".to_string() => entryList.len().into(),
+                "size".to_string() => formatSize(cache.totalSize()).into(),
+            );
+            writeConsole(ConsoleType::Info, &getLocaleText("cache-list-summary", Some(&args)));
+        }
+    }
+    Ok(())
+}
+
+/// 清空解压缓存
+/// # 参数
+/// 1. 缓存目录
+pub fn purgeCache(cachePath: &Path) -> Result<(), Box<dyn Error>> {
+    let mut cache = ExtractCache::open(cachePath, DEFAULT_CACHE_SIZE * 1024 * 1024)?;
+    cache.purge()?;
+    let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => cachePath.display().to_string().into());
+    writeConsole(ConsoleType::Success, &getLocaleText("cache-purged", Some(&args)));
+    Ok(())
+}
+
+/// 以表格形式显示缓存的驱动目录
+/// # 参数
+/// 1. 缓存的驱动目录列表
+/// # 返回
+/// 表格各行（含表头）
+fn cacheTable(entryList: &[&CacheEntry]) -> Vec<String> {
+    let mut rows: Vec<Vec<String>> = vec![
+        ["report-package", "cache-list-path", "driver-list-size", "cache-list-last-used"]
+            .iter()
+            .map(|id| getLocaleText(id, None))
+            .collect(),
+    ];
+    for entry in entryList.iter() {
+        rows.push(vec![
+            entry.Package.clone(),
+            entry.Path.clone(),
+            formatSize(entry.Size),
+            DateTime::from_timestamp(entry.LastUsed as i64, 0).map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+        ]);
+    }
+    formatTable(&rows)
+}
@@ -1,5 +1,8 @@
-use crate::command::create_index::createIndex;
+use crate::command::create_index::{createIndex, IndexFormat};
 use crate::i18n::getLocaleText;
+use crate::utils::archiveBackend::{openArchive, ArchiveBackend};
+use crate::utils::deviceBackend::DeviceFilter;
+use crate::utils::signaturePolicy::SignaturePolicy;
 use crate::{command, sevenZip, TEMP_PATH};
 use std::error::Error;
 use std::fs::OpenOptions;
@@ -16,17 +19,17 @@ pub fn createDriver(driverPath: &Path, outPath: &Path) -> Result<(), Box<dyn Err
     let zip = sevenZip::new().unwrap();
     let mut driverPath = driverPath.to_path_buf();
 
-    if driverPath.is_file() && !zip.isDriverPackage(&driverPath).unwrap_or(false) {
+    if driverPath.is_file() && !openArchive(&driverPath)?.isDriverPackage(&driverPath).unwrap_or(false) {
         return Err(String::from(&getLocaleText("no-driver-package", None)).into());
     }
     if driverPath.is_dir() {
         // 创建驱动索引
         let indexPath = driverPath.join(format!("{}.index", driverPath.file_stem().unwrap().to_str().unwrap()));
-        createIndex(&driverPath, None, &indexPath);
+        createIndex(&driverPath, None, &indexPath, Some(IndexFormat::Json), false);
 
         // 打包驱动
         let tempArchivePath = TEMP_PATH.join(format!("{}.7z", driverPath.file_stem().unwrap().to_str().unwrap()));
-        if zip.createArchivePage(&driverPath, &tempArchivePath).unwrap_or(false) && !tempArchivePath.exists() {
+        if zip.create(&driverPath, &tempArchivePath).unwrap_or(false) && !tempArchivePath.exists() {
             return Err(String::from(&getLocaleText("Pack-Driver-failed", None)).into());
         }
         driverPath = tempArchivePath;
@@ -68,6 +71,6 @@ pub fn selfDriver() -> Result<bool, Box<dyn Error>> {
             index = Option::from(indexList[0].clone());
         }
     };
-    command::load_driver::loadDriver(&env::current_exe().unwrap(), None, index, false, None, None)?;
+    command::load_driver::loadDriver(&env::current_exe().unwrap(), None, index, &DeviceFilter::Problem, None, SignaturePolicy::Any, None, None, None)?;
     Ok(true)
 }
\ No newline at end of file
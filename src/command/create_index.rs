@@ -1,20 +1,25 @@
 use crate::i18n::getLocaleText;
+use crate::utils::binaryIndex::BinaryIndex;
 use crate::utils::console::{writeConsole, ConsoleType};
-use crate::utils::sevenZIP::sevenZip;
-use crate::utils::util::{extract_vars, getFileList, String_utils};
-use crate::TEMP_PATH;
-use chardet::{charset2encoding, detect};
-use encoding::label::encoding_from_whatwg_label;
-use encoding::DecoderTrap;
+use crate::utils::catalog::SignatureStatus;
+use crate::utils::driverFiles::{archiveFiles, archiveKey, catalogFiles, missingEntries, missingFiles, requiredFiles, unionFiles, verifySignature, SectionFiles};
+use crate::utils::driverRank::DEFAULT_FEATURE_SCORE;
+use crate::utils::archiveBackend::{openArchive, ArchiveBackend, ArchiveEntry, ArchiveFile};
+use crate::utils::infParser::InfFile;
+use crate::utils::targetOS::TargetOSVersion;
+use crate::utils::util::getFileList;
+use chrono::Local;
 use fluent_templates::fluent_bundle::FluentValue;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::fs::File;
 use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::path::{Path, PathBuf, MAIN_SEPARATOR_STR};
 use std::sync::{mpsc, Arc};
+use std::time::UNIX_EPOCH;
 use threadpool::ThreadPool;
 
 /// INF驱动信息
@@ -32,8 +37,113 @@ pub struct InfInfo {
     pub(crate) Date: String,
     /// 驱动版本
     pub(crate) Version: String,
+    /// 驱动编录文件（CatalogFile），未指定时为空
+    #[serde(default)]
+    pub(crate) Catalog: String,
+    /// INF文件大小（用于增量更新）
+    #[serde(default)]
+    pub(crate) Size: u64,
+    /// INF文件修改时间（Unix 时间戳，用于增量更新）
+    #[serde(default)]
+    pub(crate) Modified: u64,
+    /// 编录文件及所需驱动文件的摘要（用于增量更新，见 `filesStamp`）
+    #[serde(default)]
+    pub(crate) FilesStamp: u64,
+    /// 所属驱动包文件名（仅合并索引记录，单个驱动包的索引为空）
+    #[serde(default)]
+    pub(crate) Package: String,
+    /// 缺失或为空的驱动文件（相对于INF所在目录，各个 Models 节的并集，完整的驱动为空）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) Missing: Vec<String>,
+    /// 签名状态（由编录文件校验，旧版索引为 Unknown）
+    #[serde(default)]
+    pub(crate) Signature: SignatureStatus,
+    /// 编录文件签名者证书主题，未签名时为空
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) Signer: String,
+    /// 签名适用的系统版本（编录文件的 OSAttr，如 `2:6.3,2:10.0`），未签名时为空
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) OSAttr: String,
     /// 驱动硬件id列表
-    pub(crate) DriverList: Vec<String>,
+    pub(crate) DriverList: Vec<DriverID>,
+    /// 驱动等级（匹配时计算，不保存到索引）
+    #[serde(skip)]
+    pub(crate) Rank: u32,
+}
+
+/// 驱动硬件id
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DriverID {
+    /// 硬件id（大写）
+    pub(crate) Id: String,
+    /// 来源 Models 节名称（不含修饰）
+    pub(crate) Models: String,
+    /// 来源 Models 节的修饰（TargetOSVersion），未修饰的节为空
+    pub(crate) Decoration: String,
+    /// 在 Models 行中的位置（0 为硬件id，其余为兼容id）
+    #[serde(default)]
+    pub(crate) Position: usize,
+    /// 功能分数（DDInstall 节中的 FeatureScore）
+    #[serde(default = "defaultFeatureScore")]
+    pub(crate) FeatureScore: u8,
+    /// 来源 Models 节所需的驱动文件缺失或为空
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) Incomplete: bool,
+}
+
+fn defaultFeatureScore() -> u8 {
+    DEFAULT_FEATURE_SCORE
+}
+
+/// 旧版索引中的INF驱动信息（硬件id不含修饰）
+#[derive(Deserialize)]
+struct LegacyInfInfo {
+    Path: String,
+    Inf: String,
+    Class: String,
+    Arch: Vec<String>,
+    Date: String,
+    Version: String,
+    DriverList: Vec<String>,
+}
+
+impl From<LegacyInfInfo> for InfInfo {
+    /// 旧版索引仅记录了驱动位宽，将每个硬件id视为来自对应架构的 Models 节
+    fn from(legacy: LegacyInfInfo) -> InfInfo {
+        let DriverList = legacy
+            .DriverList
+            .iter()
+            .flat_map(|id| {
+                legacy.Arch.iter().map(move |arch| DriverID {
+                    Id: id.clone(),
+                    Models: String::new(),
+                    Decoration: arch.clone(),
+                    Position: 0,
+                    FeatureScore: DEFAULT_FEATURE_SCORE,
+                    Incomplete: false,
+                })
+            })
+            .collect();
+        InfInfo {
+            Path: legacy.Path,
+            Inf: legacy.Inf,
+            Class: legacy.Class,
+            Arch: legacy.Arch,
+            Date: legacy.Date,
+            Version: legacy.Version,
+            Catalog: String::new(),
+            Size: 0,
+            Modified: 0,
+            FilesStamp: 0,
+            Package: String::new(),
+            Missing: Vec::new(),
+            Signature: SignatureStatus::Unknown,
+            Signer: String::new(),
+            OSAttr: String::new(),
+            DriverList,
+            Rank: 0,
+        }
+    }
 }
 
 impl InfInfo {
@@ -41,114 +151,177 @@ impl InfInfo {
     /// # 参数
     /// 1. inf 基本路径（父路径）
     /// 2. inf 文件路径
-    pub fn parsingInfFile(basePath: &Path, infFile: &Path) -> Result<InfInfo, Box<dyn Error>> {
+    /// 3. 是否校验全部驱动文件的哈希（为 false 时与压缩包一致仅校验INF本身，驱动文件在安装前校验）
+    pub fn parsingInfFile(basePath: &Path, infFile: &Path, verifyFiles: bool) -> Result<InfInfo, Box<dyn Error>> {
+        // 读取INF文件
+        let inf = InfFile::open(infFile)?;
+
+        // 获取驱动文件相对路径
+        let infDir = infFile.parent().unwrap();
+        let parentPath = infDir.strip_prefix(basePath)?;
+        let infName = infFile.file_name().unwrap().to_str().unwrap().to_string();
+
+        let (Size, Modified) = fileStamp(infFile)?;
+        let catalogList = catalogFiles(&inf);
+        let signature = verifySignature(&inf, &infName, None, &|file| {
+            let isRead = verifyFiles || file.eq_ignore_ascii_case(&infName) || catalogList.iter().any(|catalog| catalog.eq_ignore_ascii_case(file));
+            if isRead { fs::read(infDir.join(file)).ok() } else { None }
+        });
+        let missing = missingFiles(&inf, infDir);
+        Ok(InfInfo {
+            Size,
+            Modified,
+            FilesStamp: filesStamp(&inf, &|file| fileStamp(&infDir.join(file)).ok()),
+            Signature: signature.Status,
+            Signer: signature.Signer,
+            OSAttr: signature.OSAttr,
+            ..InfInfo::fromInf(parentPath.to_str().unwrap().to_string(), infName, &inf)
+        }
+        .withMissing(&missing))
+    }
+
+    /// 解析压缩包中的INF文件（不解压到磁盘）
+    /// # 参数
+    /// 1. 压缩包内的INF文件
+    /// 2. INF 文件内容
+    /// 3. 压缩包内的文件（用于检查驱动文件是否完整）
+    /// 4. INF引用的编录文件（查找键 => 内容，仅校验INF本身的哈希）
+    pub fn parsingInfEntry(entry: &ArchiveEntry, content: &[u8], files: &HashMap<String, (u64, u64)>, catalogs: &HashMap<String, Vec<u8>>) -> Result<InfInfo, Box<dyn Error>> {
+        let inf = InfFile::fromBytes(content)?;
+
+        // 驱动相对路径与解压到磁盘后的路径一致（使用系统路径分隔符）
+        let (parentPath, infName) = entry.Path.rsplit_once('/').unwrap_or(("", &entry.Path));
+        let signature = verifySignature(&inf, infName, None, &|file| {
+            if file.eq_ignore_ascii_case(infName) {
+                Some(content.to_vec())
+            } else {
+                catalogs.get(&archiveKey(parentPath, file)).cloned()
+            }
+        });
+        let missing = missingEntries(&inf, parentPath, files);
+        Ok(InfInfo {
+            Size: entry.Size,
+            Modified: entry.Modified.unwrap_or_default(),
+            FilesStamp: filesStamp(&inf, &|file| files.get(&archiveKey(parentPath, file)).copied()),
+            Signature: signature.Status,
+            Signer: signature.Signer,
+            OSAttr: signature.OSAttr,
+            ..InfInfo::fromInf(parentPath.replace('/', MAIN_SEPARATOR_STR), infName.to_string(), &inf)
+        }
+        .withMissing(&missing))
+    }
+
+    /// 记录缺失的驱动文件，并标记来自不完整的 Models 节的硬件id
+    /// # 参数
+    /// 1. 不完整的 Models 节及其缺失的文件（见 `missingFiles`）
+    pub fn withMissing(mut self, missing: &[SectionFiles]) -> InfInfo {
+        self.Missing = unionFiles(missing);
+        for driverID in self.DriverList.iter_mut() {
+            driverID.Incomplete = missing.iter().any(|section| section.Models == driverID.Models && section.Decoration == driverID.Decoration);
+        }
+        self
+    }
+
+    /// 从已解析的INF中提取驱动信息
+    /// 与 Windows 一致：由 [Manufacturer] 定位到各个 Models 节，再从 Models 节中读取硬件id
+    /// # 参数
+    /// 1. 驱动相对路径
+    /// 2. 驱动INF文件名
+    /// 3. 已解析的INF
+    pub fn fromInf(Path: String, Inf: String, inf: &InfFile) -> InfInfo {
         lazy_static! {
             // 系统架构
             static ref SYSTEMARCH: [&'static str; 5] = ["NTx86", "NTia64","NTamd64", "NTarm", "NTarm64"];
         }
 
-        // 读取INF文件
-        let mut file = File::open(infFile)?;
-        let mut fileBuf: Vec<u8> = Vec::new();
-        file.read_to_end(&mut fileBuf)?;
-
-        // 自动识别编码并以UTF-8读取
-        let result = detect(&fileBuf);
-        let coder = encoding_from_whatwg_label(charset2encoding(&result.0)).ok_or("Failed encoding")?;
-        let infContent = coder.decode(&fileBuf, DecoderTrap::Ignore)?;
+        let Class = inf.getValue("Version", "Class").unwrap_or_default().to_string();
 
-        // 去除INF内的所有 空格 与 tab符
-        let infContent = infContent.replace(" ", "").replace("	", "");
+        // 驱动版本、日期（DriverVer = 日期, 版本）
+        let driverVer = inf.section("Version").and_then(|section| section.getValues("DriverVer")).unwrap_or_default();
+        let Date = driverVer.first().cloned().unwrap_or_default();
+        let Version = driverVer.get(1).cloned().unwrap_or_default();
 
-        let mut idList: Vec<String> = Vec::new();
+        // 编录文件（CatalogFile 或 CatalogFile.<架构>）
+        let Catalog = inf
+            .section("Version")
+            .and_then(|section| {
+                section.Lines.iter().find(|line| {
+                    line.Key.as_deref().and_then(|key| key.get(..11)).is_some_and(|key| key.eq_ignore_ascii_case("CatalogFile"))
+                })
+            })
+            .and_then(|line| line.Values.first())
+            .cloned()
+            .unwrap_or_default();
 
-        let mut Class = String::new();
-        let mut Date = String::new();
-        let mut Version = String::new();
         let mut Arch: Vec<String> = Vec::new();
+        let mut idList: Vec<DriverID> = Vec::new();
 
-        // 按行读取
-        for line in infContent.lines() {
-            // 空行、行首注释
-            if line.is_empty() || line.starts_with(";") { continue; }
-            // 行尾注释
-            let line = line.split(';').next().unwrap_or(line).trim();
+        // 遍历厂商：%厂商% = Models节[, 修饰1, 修饰2...]
+        for line in inf.section("Manufacturer").map(|section| section.Lines.as_slice()).unwrap_or_default() {
+            let Some(models) = line.Values.first().filter(|models| !models.is_empty()) else { continue };
 
-            // 驱动类别
-            if let Some(class) = line.strip_prefix("Class=") {
-                Class = String::from(class);
-            }
-            // 驱动版本、日期
-            if let Some(dateAndVersion) = line.strip_prefix("DriverVer=") {
-                // 变量替换处理
-                let dateAndVersion = extract_vars(dateAndVersion).iter().fold(
-                    dateAndVersion.to_string(),
-                    |acc, ver| {
-                        infContent.get_string_center(&format!("{ver}="), "\r\n")
-                            .map(|v| acc.replace(&format!("%{ver}%"), v.trim_matches('"')))
-                            .unwrap_or(acc)
-                    },
-                );
+            // 未修饰的 Models 节与各个修饰的 Models 节
+            let mut decorations: Vec<&str> = vec![""];
+            decorations.extend(line.Values[1..].iter().map(String::as_str).filter(|item| !item.is_empty()));
 
-                let (date, version) = dateAndVersion.split_once(',')
-                    .map(|(d, v)| (d.trim(), v.trim()))
-                    .unwrap_or((&*dateAndVersion, ""));
-                Date = date.to_string();
-                Version = version.to_string();
-            }
-            // 驱动平台
-            for item in SYSTEMARCH.iter() {
-                if line.to_uppercase().contains(&*format!(".{}", item).to_uppercase()) && !Arch.contains(&item.to_string()) {
-                    Arch.push(item.to_string());
+            for decoration in decorations {
+                let Some(targetOSVersion) = TargetOSVersion::parse(decoration) else { continue };
+                let sectionName = if decoration.is_empty() { models.clone() } else { format!("{}.{}", models, decoration) };
+                let Some(section) = inf.section(&sectionName) else { continue };
+
+                // 未指定架构的节仅适用于 x86
+                let archName = targetOSVersion.Arch.as_deref().unwrap_or("NTx86");
+                let arch = SYSTEMARCH.iter().find(|item| item.eq_ignore_ascii_case(archName)).copied().unwrap_or(archName);
+                if !Arch.iter().any(|item| item == arch) {
+                    Arch.push(arch.to_string());
                 }
-            }
-            // 获取硬件ID
-            if let Some(equal_pos) = line.find('=') {
-                if let Some(comma_pos) = line[equal_pos..].find(',') {
-                    // 获取逗号之后的部分
-                    let potential_id = &line[(equal_pos + comma_pos + 1)..].trim();
-                    // 逗号分隔多个硬件ID
-                    for id in potential_id.split(",") {
-                        // 检查是否包含反斜杠或开头是否为星号
-                        if id == "\\" || (!id.contains('\\') && !id.starts_with('*')) {
-                            continue;
-                        }
-                        // 检查是否符合硬件ID格式
-                        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '\\' || c == '&' || c == '_' || c == '.' || c == '-' || c == '*') {
+
+                // %设备描述% = 安装节, 硬件id[, 兼容id...]
+                for modelLine in section.Lines.iter() {
+                    let FeatureScore = featureScore(inf, modelLine.Values.first().map(String::as_str).unwrap_or_default(), arch);
+                    for (Position, id) in modelLine.Values.iter().skip(1).enumerate() {
+                        if id.is_empty() {
                             continue;
                         }
-                        // 转为大写
-                        let id = id.to_uppercase();
-                        if !idList.contains(&id) {
-                            idList.push(id);
+                        let driverID = DriverID {
+                            Id: id.to_uppercase(),
+                            Models: models.clone(),
+                            Decoration: decoration.to_string(),
+                            Position,
+                            FeatureScore,
+                            Incomplete: false,
+                        };
+                        if !idList.contains(&driverID) {
+                            idList.push(driverID);
                         }
                     }
                 }
             }
         }
 
-        // 获取驱动文件相对路径
-        let parentPath = infFile.parent().unwrap().strip_prefix(basePath)?;
-
-        Ok(InfInfo {
-            Path: parentPath.to_str().unwrap().parse().unwrap(),
-            Inf: infFile
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .parse()
-                .unwrap(),
+        InfInfo {
+            Path,
+            Inf,
             Class,
             Arch,
             Date,
             Version,
+            Catalog,
+            Size: 0,
+            Modified: 0,
+            FilesStamp: 0,
+            Package: String::new(),
+            Missing: Vec::new(),
+            Signature: SignatureStatus::Unknown,
+            Signer: String::new(),
+            OSAttr: String::new(),
             DriverList: idList,
-        })
+            Rank: 0,
+        }
     }
 
     /// 解析INF文件列表（多线程）
-    /// 解析失败的INF将自动跳过
+    /// 解析失败的INF将自动跳过；仅校验INF本身的哈希，不读取驱动文件
     /// # 参数
     /// 1. inf 基本路径（父路径）
     /// 2. inf 文件路径列表
@@ -168,7 +341,7 @@ impl InfInfo {
             let base_path = Arc::clone(&base_path);
 
             pool.execute(move || {
-                if let Ok(inf_info) = InfInfo::parsingInfFile(&base_path, &inf_file) {
+                if let Ok(inf_info) = InfInfo::parsingInfFile(&base_path, &inf_file, false) {
                     // 发送到主线程
                     let _ = tx.send(inf_info);
                 }
@@ -182,42 +355,506 @@ impl InfInfo {
         rx.into_iter().collect()
     }
 
-    /// 保存INF数据（通过JSON）
-    /// #参数
-    /// 1. INF列表
-    /// 2. 索引文件保存路径
-    pub fn saveIndexFromJson(Data: &[InfInfo], savaPath: &Path) -> Result<(), Box<dyn Error>> {
-        let json = serde_json::to_string(&Data)?;
-        fs::write(savaPath, json)?;
+    /// 解析压缩包中的INF文件（多线程，不解压到磁盘）
+    /// 逐个读取INF文件内容并交由线程池解析，解析后即释放内容；引用编录文件的INF在读取编录文件后解析；解析失败的INF将自动跳过
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    pub fn parsingInfArchive(archivePath: &Path, password: Option<&str>) -> Result<Vec<InfInfo>, Box<dyn Error>> {
+        let pool = ThreadPool::new(num_cpus::get());
+        let (tx, rx) = mpsc::channel();
+
+        let backend = openArchive(archivePath)?;
+        let files = Arc::new(archiveFiles(&backend.list(archivePath, password)?));
+        let mut parse = |(entry, content): ArchiveFile, catalogs: HashMap<String, Vec<u8>>| {
+            let tx = tx.clone();
+            let files = Arc::clone(&files);
+            pool.execute(move || {
+                if let Ok(inf_info) = InfInfo::parsingInfEntry(&entry, &content, &files, &catalogs) {
+                    let _ = tx.send(inf_info);
+                }
+            });
+        };
+
+        // 未引用编录文件的INF立即解析，其余INF在读取编录文件后解析
+        let mut pendingList: Vec<(ArchiveFile, Vec<String>)> = Vec::new();
+        backend.visitFiles(archivePath, password, "*.inf", &mut |entry, content| {
+            if entry.isInf() {
+                let keyList = catalogKeys(&entry, &content, &files);
+                if keyList.is_empty() {
+                    parse((entry, content), HashMap::new());
+                } else {
+                    pendingList.push(((entry, content), keyList));
+                }
+            }
+            Ok(())
+        })?;
+        visitCatalogs(backend.as_ref(), archivePath, password, pendingList, &mut parse)?;
+
+        drop(tx);
+        Ok(rx.into_iter().collect())
+    }
+}
+
+/// 索引文件格式版本
+/// 1：旧版（仅INF信息数组）
+/// 2：带文件头的索引
+/// 3：支持合并多个驱动包的索引
+pub const INDEX_FORMAT_VERSION: u32 = 3;
+
+/// 索引格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexFormat {
+    /// JSON
+    Json,
+    /// 二进制（可内存映射查询，适用于大型驱动包）
+    Binary,
+}
+
+impl IndexFormat {
+    /// 由名称解析索引格式（不区分大小写）
+    pub fn fromName(name: &str) -> Option<IndexFormat> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(IndexFormat::Json),
+            "binary" | "bin" => Some(IndexFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// 索引文件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IndexFile {
+    /// 索引格式版本
+    pub(crate) FormatVersion: u32,
+    /// 生成索引的程序版本
+    pub(crate) GeneratorVersion: String,
+    /// 创建时间（RFC 3339）
+    pub(crate) CreatedAt: String,
+    /// 来源驱动包（从驱动目录创建时、合并索引为空）
+    pub(crate) Package: Option<PackageInfo>,
+    /// 合并索引包含的驱动包（INF驱动信息通过 Package 字段关联）
+    #[serde(default)]
+    pub(crate) Packages: Vec<PackageInfo>,
+    /// 驱动包是否需要密码
+    pub(crate) PasswordRequired: bool,
+    /// INF驱动信息列表
+    pub(crate) Entries: Vec<InfInfo>,
+}
+
+/// 驱动包指纹
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageInfo {
+    /// 驱动包文件名
+    pub(crate) FileName: String,
+    /// 驱动包大小
+    pub(crate) Size: u64,
+    /// 驱动包 SHA-256
+    pub(crate) Hash: String,
+}
+
+impl PackageInfo {
+    /// 计算驱动包指纹
+    /// # 参数
+    /// 1. 驱动包路径
+    pub fn fromPath(packagePath: &Path) -> Result<PackageInfo, Box<dyn Error>> {
+        let mut file = File::open(packagePath)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 1024 * 1024];
+        loop {
+            let len = file.read(&mut buffer)?;
+            if len == 0 {
+                break;
+            }
+            hasher.update(&buffer[..len]);
+        }
+        Ok(PackageInfo {
+            FileName: packagePath.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            Size: file.metadata()?.len(),
+            Hash: hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect(),
+        })
+    }
+
+    /// 驱动包是否与指纹一致（先比较大小，避免不必要的哈希计算）
+    /// # 参数
+    /// 1. 驱动包路径
+    /// # 返回
+    /// 一致时返回驱动包 SHA-256，否则返回 None
+    pub fn matchHash(&self, packagePath: &Path) -> Result<Option<String>, Box<dyn Error>> {
+        if fs::metadata(packagePath)?.len() != self.Size {
+            return Ok(None);
+        }
+        Ok(Some(PackageInfo::fromPath(packagePath)?.Hash).filter(|hash| hash.eq_ignore_ascii_case(&self.Hash)))
+    }
+}
+
+impl IndexFile {
+    /// 创建索引
+    /// # 参数
+    /// 1. INF驱动信息列表
+    /// 2. 驱动包路径（从驱动目录创建时为 None）
+    /// 3. 驱动包是否需要密码
+    pub fn new(Entries: Vec<InfInfo>, packagePath: Option<&Path>, PasswordRequired: bool) -> Result<IndexFile, Box<dyn Error>> {
+        Ok(IndexFile {
+            FormatVersion: INDEX_FORMAT_VERSION,
+            GeneratorVersion: env!("CARGO_PKG_VERSION").to_string(),
+            CreatedAt: Local::now().to_rfc3339(),
+            Package: packagePath.map(PackageInfo::fromPath).transpose()?,
+            Packages: Vec::new(),
+            PasswordRequired,
+            Entries,
+        })
+    }
+
+    /// 保存索引
+    /// # 参数
+    /// 1. 索引文件保存路径
+    /// 2. 索引格式
+    pub fn save(&self, savePath: &Path, format: IndexFormat) -> Result<(), Box<dyn Error>> {
+        match format {
+            IndexFormat::Json => {
+                let json = serde_json::to_string(&self)?;
+                fs::write(savePath, json)?;
+            }
+            IndexFormat::Binary => BinaryIndex::save(self, savePath)?,
+        }
         Ok(())
     }
 
-    /// 解析索引数据
+    /// 解析索引文件（自动识别 JSON 与二进制格式）
+    /// 旧版索引（INF信息数组）将自动迁移
     /// # 参数
     /// 1. 索引文件路径
-    pub fn parsingIndex(indexPath: &Path) -> Result<Vec<InfInfo>, Box<dyn Error>> {
+    pub fn load(indexPath: &Path) -> Result<IndexFile, Box<dyn Error>> {
+        if BinaryIndex::isBinaryIndex(indexPath) {
+            return BinaryIndex::open(indexPath)
+                .and_then(|index| index.toIndexFile())
+                .map_err(|_| getLocaleText("index-parsing-failed", None).into());
+        }
         let mut indexFile = File::open(indexPath)?;
         let mut indexContent = String::new();
         indexFile.read_to_string(&mut indexContent)?;
-        let json: Vec<InfInfo> = serde_json::from_str(&indexContent)?;
-        Ok(json)
+        IndexFile::parse(&indexContent)
+    }
+
+    /// 解析索引内容
+    /// # 参数
+    /// 1. 索引内容（JSON）
+    pub fn parse(indexContent: &str) -> Result<IndexFile, Box<dyn Error>> {
+        let value: serde_json::Value = serde_json::from_str(indexContent).map_err(|_| getLocaleText("index-parsing-failed", None))?;
+
+        // 旧版索引
+        if value.is_array() {
+            return IndexFile::migrate(value).ok_or_else(|| getLocaleText("index-parsing-failed", None).into());
+        }
+
+        let formatVersion = value.get("FormatVersion").and_then(serde_json::Value::as_u64).unwrap_or(0);
+        if formatVersion < 2 || formatVersion > INDEX_FORMAT_VERSION as u64 {
+            let arg = hash_map!("version".to_string() => formatVersion.to_string().into());
+            return Err(getLocaleText("index-version-unsupported", Some(&arg)).into());
+        }
+        let index: IndexFile = serde_json::from_value(value).map_err(|_| getLocaleText("index-parsing-failed", None))?;
+        Ok(index)
+    }
+
+    /// 迁移旧版索引
+    fn migrate(value: serde_json::Value) -> Option<IndexFile> {
+        let Entries = match serde_json::from_value::<Vec<InfInfo>>(value.clone()) {
+            Ok(list) => list,
+            Err(_) => serde_json::from_value::<Vec<LegacyInfInfo>>(value).ok()?.into_iter().map(InfInfo::from).collect(),
+        };
+        Some(IndexFile {
+            FormatVersion: 1,
+            GeneratorVersion: String::new(),
+            CreatedAt: String::new(),
+            Package: None,
+            Packages: Vec::new(),
+            PasswordRequired: false,
+            Entries,
+        })
+    }
+
+    /// 校验索引是否属于指定驱动包
+    /// 未记录驱动包指纹的索引（旧版索引、从驱动目录创建的索引）不校验
+    /// 合并索引校验同名的驱动包，不包含该驱动包时校验失败
+    /// # 参数
+    /// 1. 驱动包路径
+    /// # 返回
+    /// 校验时计算的驱动包 SHA-256（未校验时为 None），供解压缓存使用以免重复计算
+    pub fn verifyPackage(&self, packagePath: &Path) -> Result<Option<String>, Box<dyn Error>> {
+        let fileName = packagePath.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let package = match &self.Package {
+            Some(package) => Some(package),
+            None if self.Packages.is_empty() => return Ok(None),
+            None => self.Packages.iter().find(|item| item.FileName.eq_ignore_ascii_case(&fileName)),
+        };
+        let hash = package.map(|package| package.matchHash(packagePath)).transpose()?.flatten();
+        if hash.is_none() {
+            let arg = hash_map!("package".to_string() => package.map(|package| package.FileName.clone()).unwrap_or(fileName).into());
+            return Err(getLocaleText("index-package-mismatch", Some(&arg)).into());
+        }
+        Ok(hash)
+    }
+
+    /// 是否为合并索引
+    pub fn isMerged(&self) -> bool {
+        !self.Packages.is_empty()
+    }
+
+    /// 合并多个驱动包的索引
+    /// 同名驱动包以后出现的索引为准
+    /// # 参数
+    /// 1. 索引列表（单个驱动包的索引或合并索引，从驱动目录创建的索引无法合并）
+    pub fn merge(indexList: Vec<IndexFile>) -> Result<IndexFile, Box<dyn Error>> {
+        let mut merged = IndexFile::new(Vec::new(), None, false)?;
+        for index in indexList {
+            let (packages, entries) = match index.Package {
+                Some(package) => {
+                    let fileName = package.FileName.clone();
+                    let entries = index.Entries.into_iter().map(|item| InfInfo { Package: fileName.clone(), ..item }).collect();
+                    (vec![package], entries)
+                }
+                None if index.isMerged() => (index.Packages, index.Entries),
+                None => return Err(getLocaleText("merge-index-no-package", None).into()),
+            };
+
+            // 移除同名驱动包
+            let isReplaced = |name: &str| packages.iter().any(|package| package.FileName.eq_ignore_ascii_case(name));
+            merged.Packages.retain(|package| !isReplaced(&package.FileName));
+            merged.Entries.retain(|item| !isReplaced(&item.Package));
+
+            merged.Packages.extend(packages);
+            merged.Entries.extend(entries);
+            merged.PasswordRequired |= index.PasswordRequired;
+        }
+        Ok(merged)
+    }
+
+    /// 拆分合并索引为各个驱动包的索引
+    /// # 返回
+    /// 各个驱动包的索引（非合并索引返回自身）
+    pub fn split(&self) -> Vec<IndexFile> {
+        if !self.isMerged() {
+            return vec![self.clone()];
+        }
+        self.Packages
+            .iter()
+            .map(|package| IndexFile {
+                FormatVersion: INDEX_FORMAT_VERSION,
+                GeneratorVersion: env!("CARGO_PKG_VERSION").to_string(),
+                CreatedAt: Local::now().to_rfc3339(),
+                Package: Some(package.clone()),
+                Packages: Vec::new(),
+                PasswordRequired: self.PasswordRequired,
+                Entries: self
+                    .Entries
+                    .iter()
+                    .filter(|item| item.Package.eq_ignore_ascii_case(&package.FileName))
+                    .map(|item| InfInfo { Package: String::new(), ..item.clone() })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// 获取安装节（DDInstall）中的功能分数
+/// 与 Windows 一致：依次查找 [安装节.架构]、[安装节.NT]、[安装节]
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 安装节名称
+/// 3. 架构（如 NTamd64）
+fn featureScore(inf: &InfFile, install: &str, arch: &str) -> u8 {
+    if install.is_empty() {
+        return DEFAULT_FEATURE_SCORE;
+    }
+    [format!("{}.{}", install, arch), format!("{}.NT", install), install.to_string()]
+        .iter()
+        .find_map(|name| inf.section(name))
+        .and_then(|section| section.getValue("FeatureScore"))
+        .and_then(|value| match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(hex) => u8::from_str_radix(hex, 16).ok(),
+            None => value.parse().ok(),
+        })
+        .unwrap_or(DEFAULT_FEATURE_SCORE)
+}
+
+/// 获取文件大小与修改时间（Unix 时间戳），用于判断INF是否变更
+/// # 参数
+/// 1. 文件路径
+fn fileStamp(path: &Path) -> Result<(u64, u64), Box<dyn Error>> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata.modified()?.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+    Ok((metadata.len(), modified))
+}
+
+/// 编录文件及所需驱动文件的摘要（用于增量更新）
+/// 任一文件的大小或修改时间变化、文件新增或删除时摘要随之变化
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 获取文件的大小与修改时间（参数为相对于INF所在目录的路径，文件不存在时为 None）
+fn filesStamp(inf: &InfFile, stat: &dyn Fn(&str) -> Option<(u64, u64)>) -> u64 {
+    let mut fileList = catalogFiles(inf);
+    for file in requiredFiles(inf) {
+        if !fileList.iter().any(|item| item.eq_ignore_ascii_case(&file)) {
+            fileList.push(file);
+        }
+    }
+    let mut hasher = Sha256::new();
+    for file in fileList {
+        let (size, modified) = stat(&file).unwrap_or((u64::MAX, 0));
+        hasher.update(file.to_lowercase().as_bytes());
+        hasher.update([0]);
+        hasher.update(size.to_le_bytes());
+        hasher.update(modified.to_le_bytes());
+    }
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+/// 待索引的INF
+enum InfSource {
+    /// 驱动目录中的INF文件
+    File(PathBuf),
+    /// 压缩包中的INF文件及其内容
+    Entry(ArchiveEntry, Vec<u8>),
+}
+
+impl InfSource {
+    /// 显示路径
+    fn display(&self) -> String {
+        match self {
+            InfSource::File(path) => path.display().to_string(),
+            InfSource::Entry(entry, _) => entry.Path.clone(),
+        }
+    }
+
+    /// INF在索引中的标识
+    /// # 参数
+    /// 1. 驱动目录
+    fn key(&self, basePath: &Path) -> String {
+        match self {
+            InfSource::File(path) => path.strip_prefix(basePath).map(|path| path.to_string_lossy().to_lowercase()).unwrap_or_default(),
+            InfSource::Entry(entry, _) => entry.Path.replace('/', MAIN_SEPARATOR_STR).to_lowercase(),
+        }
+    }
+
+    /// 大小与修改时间
+    fn stamp(&self) -> Result<(u64, u64), Box<dyn Error>> {
+        match self {
+            InfSource::File(path) => fileStamp(path),
+            InfSource::Entry(entry, _) => Ok((entry.Size, entry.Modified.unwrap_or_default())),
+        }
+    }
+
+    /// 编录文件及所需驱动文件的摘要（见 `filesStamp`，INF无法读取时为 None）
+    /// # 参数
+    /// 1. 压缩包内的文件（驱动目录时为空）
+    fn filesStamp(&self, files: &HashMap<String, (u64, u64)>) -> Option<u64> {
+        match self {
+            InfSource::File(path) => {
+                let infDir = path.parent()?;
+                Some(filesStamp(&InfFile::open(path).ok()?, &|file| fileStamp(&infDir.join(file)).ok()))
+            }
+            InfSource::Entry(entry, content) => {
+                let parentPath = entry.Path.rsplit_once('/').map(|(parentPath, _)| parentPath).unwrap_or_default();
+                Some(filesStamp(&InfFile::fromBytes(content).ok()?, &|file| files.get(&archiveKey(parentPath, file)).copied()))
+            }
+        }
     }
+
+    /// 解析INF
+    /// # 参数
+    /// 1. 驱动目录
+    /// 2. 压缩包内的文件（驱动目录时为空）
+    /// 3. INF引用的编录文件（驱动目录时为空）
+    fn parse(&self, basePath: &Path, files: &HashMap<String, (u64, u64)>, catalogs: &HashMap<String, Vec<u8>>) -> Result<InfInfo, Box<dyn Error>> {
+        match self {
+            InfSource::File(path) => InfInfo::parsingInfFile(basePath, path, true),
+            InfSource::Entry(entry, content) => InfInfo::parsingInfEntry(entry, content, files, catalogs),
+        }
+    }
+}
+
+/// INF引用且存在于压缩包中的编录文件（查找键）
+/// # 参数
+/// 1. 压缩包内的INF文件
+/// 2. INF 文件内容
+/// 3. 压缩包内的文件（见 `archiveFiles`）
+fn catalogKeys(entry: &ArchiveEntry, content: &[u8], files: &HashMap<String, (u64, u64)>) -> Vec<String> {
+    let parentPath = entry.Path.rsplit_once('/').map(|(parentPath, _)| parentPath).unwrap_or_default();
+    let catalogList = InfFile::fromBytes(content).map(|inf| catalogFiles(&inf)).unwrap_or_default();
+    catalogList.iter().map(|catalog| archiveKey(parentPath, catalog)).filter(|key| files.contains_key(key)).collect()
 }
 
-pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Path) -> Result<(), Box<dyn Error>> {
-    let zip = sevenZip::new()?;
+/// 按INF读取压缩包中的编录文件
+/// 编录文件逐个读取，INF引用的编录文件均已读取后即交给处理函数，不再被其他INF引用的编录文件随即释放
+/// # 参数
+/// 1. 压缩包后端
+/// 2. 压缩包路径
+/// 3. 压缩包密码
+/// 4. 待处理的INF及其引用的编录文件（见 `catalogKeys`）
+/// 5. 处理函数（参数为INF、其引用的编录文件，查找键 => 内容）
+fn visitCatalogs<T>(backend: &dyn ArchiveBackend, archivePath: &Path, password: Option<&str>, infList: Vec<(T, Vec<String>)>, visit: &mut dyn FnMut(T, HashMap<String, Vec<u8>>)) -> Result<(), Box<dyn Error>> {
+    // 未引用编录文件的INF直接处理
+    let (readyList, mut pendingList): (Vec<_>, Vec<_>) = infList.into_iter().partition(|(_, keyList)| keyList.is_empty());
+    for (item, _) in readyList {
+        visit(item, HashMap::new());
+    }
+    if pendingList.is_empty() {
+        return Ok(());
+    }
+    let select = |catalogs: &HashMap<String, Vec<u8>>, keyList: &[String]| -> HashMap<String, Vec<u8>> { keyList.iter().filter_map(|key| catalogs.get(key).map(|content| (key.clone(), content.clone()))).collect() };
+
+    let mut catalogs: HashMap<String, Vec<u8>> = HashMap::new();
+    backend.visitFiles(archivePath, password, "*.cat", &mut |entry, content| {
+        let key = archiveKey("", &entry.Path);
+        if entry.IsDir || !pendingList.iter().any(|(_, keyList)| keyList.contains(&key)) {
+            return Ok(());
+        }
+        catalogs.insert(key, content);
+        let (readyList, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut pendingList).into_iter().partition(|(_, keyList)| keyList.iter().all(|key| catalogs.contains_key(key)));
+        pendingList = rest;
+        for (item, keyList) in readyList {
+            visit(item, select(&catalogs, &keyList));
+        }
+        catalogs.retain(|key, _| pendingList.iter().any(|(_, keyList)| keyList.contains(key)));
+        Ok(())
+    })?;
 
-    // INF文件父路径
-    let infParentPath;
+    // 无法读取的编录文件按缺失处理
+    for (item, keyList) in pendingList {
+        visit(item, select(&catalogs, &keyList));
+    }
+    Ok(())
+}
+
+/// INF在索引中的标识（相对路径，不区分大小写）
+/// # 参数
+/// 1. 驱动相对路径
+/// 2. 驱动INF文件名
+fn entryKey(path: &str, inf: &str) -> String {
+    Path::new(path).join(inf).to_string_lossy().to_lowercase()
+}
+
+/// 创建索引
+/// # 参数
+/// 1. 驱动目录或驱动包路径
+/// 2. 驱动包密码
+/// 3. 索引文件保存路径
+/// 4. 索引格式（为 None 时使用 JSON，增量更新时沿用已有索引的格式）
+/// 5. 是否增量更新（仅重新解析新增或变更的INF）
+pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Path, format: Option<IndexFormat>, update: bool) -> Result<(), Box<dyn Error>> {
     // INF文件列表
-    let infList;
+    let infList: Vec<InfSource>;
+    // 压缩包后端及压缩包内的文件（用于检查驱动文件是否完整、读取编录文件）
+    let mut archive: Option<Box<dyn ArchiveBackend>> = None;
+    let mut files: HashMap<String, (u64, u64)> = HashMap::new();
     // 保存索引路径
     let indexPath;
 
     if drivePath.is_dir() {
         // 从驱动目录中创建索引文件
-        infList = getFileList(drivePath, "*.inf").unwrap();
-        infParentPath = drivePath.to_path_buf();
+        infList = getFileList(drivePath, "*.inf").unwrap().into_iter().map(InfSource::File).collect();
         // 如果输入的索引路径是相对路径，则令实际路径为驱动目录所在路径
         indexPath = if saveIndexPath.is_relative() {
             drivePath.join(saveIndexPath)
@@ -225,14 +862,12 @@ pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Pat
             saveIndexPath.to_path_buf()
         };
     } else {
-        // 从文件中创建索引文件
-        infParentPath = TEMP_PATH.join(drivePath.file_stem().unwrap());
-        // 解压INF文件
-        if !zip.extractFilesFromPath(drivePath, password, "*.inf", &infParentPath)? {
-            return Err(getLocaleText("driver-unzip-failed", None).into());
-        }
-
-        infList = getFileList(&infParentPath, "*.inf")?;
+        // 从文件中创建索引文件，直接读取压缩包中的INF文件（不解压到临时目录）
+        let backend = openArchive(drivePath)?;
+        let entryList = backend.readFiles(drivePath, password, "*.inf").map_err(|_| getLocaleText("driver-unzip-failed", None))?;
+        files = archiveFiles(&backend.list(drivePath, password)?);
+        infList = entryList.into_iter().filter(|(entry, _)| entry.isInf()).map(|(entry, content)| InfSource::Entry(entry, content)).collect();
+        archive = Some(backend);
         // 如果输入的索引路径是相对路径，则令实际实际为驱动包所在路径
         indexPath = if saveIndexPath.is_relative() {
             drivePath.parent().unwrap().join(saveIndexPath)
@@ -245,19 +880,86 @@ pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Pat
         return Err(getLocaleText("no-inf-find", None).into());
     }
 
+    // 增量更新：读取已有索引
+    let format = format.unwrap_or(if update && BinaryIndex::isBinaryIndex(&indexPath) { IndexFormat::Binary } else { IndexFormat::Json });
+    let mut oldEntries: HashMap<String, InfInfo> = HashMap::new();
+    if update && indexPath.exists() {
+        oldEntries = IndexFile::load(&indexPath)?.Entries.into_iter().map(|item| (entryKey(&item.Path, &item.Inf), item)).collect();
+    }
+    let oldCount = oldEntries.len();
+
     let mut infInfoList: Vec<InfInfo> = Vec::new();
 
     let (mut successCount, mut ErrorCount, mut blankCount) = (0, 0, 0);
+    let (mut addedCount, mut changedCount, mut unchangedCount) = (0, 0, 0);
+
+    // INF、编录文件及所需驱动文件的大小与修改时间均未变化的INF直接沿用已有索引（缺少文件或未校验签名的驱动重新检查）
+    let previousList: Vec<Option<InfInfo>> = infList.iter().map(|item| oldEntries.remove(&item.key(drivePath))).collect();
+    let reuseList: Vec<bool> = infList
+        .iter()
+        .zip(previousList.iter())
+        .map(|(item, previous)| {
+            previous.as_ref().is_some_and(|previous| {
+                previous.Missing.is_empty()
+                    && previous.Signature != SignatureStatus::Unknown
+                    && item.stamp().is_ok_and(|stamp| stamp == (previous.Size, previous.Modified))
+                    && item.filesStamp(&files) == Some(previous.FilesStamp)
+            })
+        })
+        .collect();
+
+    // 压缩包中需要解析的INF：读取其引用的编录文件后解析，解析后即释放编录文件
+    let mut parsedList: Vec<Option<Result<InfInfo, Box<dyn Error>>>> = infList.iter().map(|_| None).collect();
+    if let Some(backend) = archive.as_ref() {
+        let pendingList: Vec<(usize, Vec<String>)> = infList
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !reuseList[*index])
+            .filter_map(|(index, item)| match item {
+                InfSource::Entry(entry, content) => Some((index, catalogKeys(entry, content, &files))),
+                InfSource::File(_) => None,
+            })
+            .collect();
+        visitCatalogs(backend.as_ref(), drivePath, password, pendingList, &mut |index, catalogs| {
+            parsedList[index] = Some(infList[index].parse(drivePath, &files, &catalogs));
+        })?;
+    }
 
     // 遍历INF文件
-    for item in infList.iter() {
-        let arg = hash_map!("path".to_string() => item.to_str().unwrap().into());
-        if let Ok(currentInfo) = InfInfo::parsingInfFile(&infParentPath, item) {
+    for (index, item) in infList.iter().enumerate() {
+        let arg = hash_map!("path".to_string() => item.display().into());
+
+        let previous = &previousList[index];
+        if reuseList[index] {
+            unchangedCount += 1;
+            successCount += 1;
+            infInfoList.extend(previous.clone());
+            continue;
+        }
+
+        let result = parsedList[index].take().unwrap_or_else(|| item.parse(drivePath, &files, &HashMap::new()));
+        if let Ok(currentInfo) = result {
             if currentInfo.DriverList.is_empty() {
                 blankCount += 1;
                 writeConsole(ConsoleType::Warning, &getLocaleText("no-hardware", Some(&arg)));
                 continue;
             }
+            // 缺少文件的驱动仍写入索引并标记，匹配时跳过
+            if !currentInfo.Missing.is_empty() {
+                let arg: HashMap<String, FluentValue> = hash_map!(
+                    "path".to_string() => item.display().into(),
+                    "files".to_string() => currentInfo.Missing.join(", ").into(),
+                );
+                writeConsole(ConsoleType::Warning, &getLocaleText("driver-files-missing", Some(&arg)));
+            }
+            if currentInfo.Signature == SignatureStatus::Tampered {
+                writeConsole(ConsoleType::Warning, &getLocaleText("driver-signature-tampered", Some(&arg)));
+            }
+            if previous.is_some() {
+                changedCount += 1;
+            } else {
+                addedCount += 1;
+            }
             successCount += 1;
             infInfoList.push(currentInfo);
         } else {
@@ -266,7 +968,9 @@ pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Pat
         }
     }
 
-    if let Err(_e) = InfInfo::saveIndexFromJson(&infInfoList, &indexPath) {
+    let packagePath = if drivePath.is_file() { Some(drivePath) } else { None };
+    let index = IndexFile::new(infInfoList, packagePath, password.is_some())?;
+    if let Err(_e) = index.save(&indexPath, format) {
         writeConsole(ConsoleType::Err, &getLocaleText("index-save-failed", None));
         return Err(getLocaleText("index-save-failed", None).into());
     }
@@ -277,6 +981,16 @@ pub fn createIndex(drivePath: &Path, password: Option<&str>, saveIndexPath: &Pat
         "blankCount".to_string() => blankCount.to_string().into(),
     );
     writeConsole(ConsoleType::Info, &getLocaleText("total-info", Some(&arg)));
+    if update {
+        // 不再存在或无法解析的INF视为已删除
+        let arg: HashMap<String, FluentValue> = hash_map!(
+            "added".to_string() => addedCount.to_string().into(),
+            "changed".to_string() => changedCount.to_string().into(),
+            "removed".to_string() => (oldCount - unchangedCount - changedCount).to_string().into(),
+            "unchanged".to_string() => unchangedCount.to_string().into(),
+        );
+        writeConsole(ConsoleType::Info, &getLocaleText("update-info", Some(&arg)));
+    }
     let arg: HashMap<String, FluentValue> = hash_map!("path".to_string() => indexPath.to_str().unwrap().into());
     writeConsole(ConsoleType::Success, &getLocaleText("saveInfo", Some(&arg)));
     Ok(())
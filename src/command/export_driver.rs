@@ -1,14 +1,23 @@
+use crate::command::match_driver::ReportFormat;
 use crate::i18n::getLocaleText;
+use crate::utils::console::{formatTable, writeConsole, ConsoleType};
+use crate::utils::driverDatabase::DriverDatabase;
+use crate::utils::driverRepository::{scanDriverStore, DriverPackage};
 use crate::utils::drvstoreAPI::DriverStore;
 use crate::utils::setupAPI;
 use crate::utils::util::{copy_dir, getArchCode, getFileList};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-pub fn export_driver(systemDrive: &Path, outPath: &Path, name: Option<&str>, class: Option<&str>) -> Result<(), Box<dyn Error>> {
+pub fn export_driver(systemDrive: &Path, outPath: &Path, name: Option<&str>, class: Option<&str>, inUse: bool) -> Result<(), Box<dyn Error>> {
     let systemRoot = systemDrive.join("Windows");
 
+    // 仅导出正在被设备使用的驱动
+    let database = if inUse { Some(DriverDatabase::open(systemDrive)?) } else { None };
+
     // 获取系统架构
     let archCode = match getArchCode(systemDrive) {
         Ok(code @ (0x014c | 0x8664 | 0xAA64)) => code,
@@ -34,6 +43,11 @@ pub fn export_driver(systemDrive: &Path, outPath: &Path, name: Option<&str>, cla
 
         // 遍历驱动库
         for item in getFileList(&*systemRoot.join("INF"), "oem*.inf")? {
+            if let Some(database) = &database {
+                if !database.isInUse(&item.file_name().unwrap_or_default().to_string_lossy()) {
+                    continue;
+                }
+            }
             if let Some((path, info_opt)) = driverStore.find_driver_package(handle, &item, arch) {
                 let inf_path = PathBuf::from(&path);
 
@@ -64,3 +78,95 @@ pub fn export_driver(systemDrive: &Path, outPath: &Path, name: Option<&str>, cla
         result
     }
 }
+
+/// 列出系统驱动库中的驱动（包括系统自带驱动）
+/// 直接读取驱动库目录，不调用 DriverStore API
+/// # 参数
+/// 1. 系统盘
+/// 2. 驱动名称（发布名称或INF文件名）
+/// 3. 驱动类别
+/// 4. 报告格式
+/// 5. 是否仅列出正在被设备使用的驱动
+pub fn listDrivers(systemDrive: &Path, name: Option<&str>, class: Option<&str>, format: ReportFormat, inUse: bool) -> Result<(), Box<dyn Error>> {
+    // 驱动数据库（Windows 10 以下的系统、在线系统不可读取时不显示使用驱动的设备）
+    let database = match DriverDatabase::open(systemDrive) {
+        Ok(database) => Some(database),
+        Err(e) if inUse => return Err(e),
+        Err(_) => None,
+    };
+
+    let packageList: Vec<DriverPackage> = scanDriverStore(systemDrive)?
+        .into_iter()
+        .filter(|package| name.is_none_or(|name| package.isNamed(name)))
+        .filter(|package| class.is_none_or(|class| package.Class.eq_ignore_ascii_case(class)))
+        .map(|mut package| {
+            package.Devices = database.as_ref().map(|database| {
+                database.Packages.iter().filter(|item| package.isNamed(&item.PublishedName)).flat_map(|item| item.Devices.clone()).collect()
+            });
+            package
+        })
+        .filter(|package| !inUse || package.Devices.as_ref().is_some_and(|devices| !devices.is_empty()))
+        .collect();
+
+    match format {
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&packageList)?),
+        ReportFormat::Table if packageList.is_empty() => return Err(getLocaleText("no-inf-find", None).into()),
+        ReportFormat::Table => {
+            for line in driverTable(&packageList) {
+                println!("    {}", line);
+            }
+            println!();
+            let args: HashMap<String, FluentValue> = hash_map!(
+                "count".to_string() => packageList.len().into(),
+                "oem".to_string() => packageList.iter().filter(|package| !package.Inbox).count().into(),
+            );
+            writeConsole(ConsoleType::Info, &getLocaleText("driver-list-summary", Some(&args)));
+        }
+    }
+    Ok(())
+}
+
+/// 以表格形式显示驱动列表
+/// # 参数
+/// 1. 驱动包列表
+/// # 返回
+/// 表格各行（含表头）
+pub(crate) fn driverTable(packageList: &[DriverPackage]) -> Vec<String> {
+    let mut rows: Vec<Vec<String>> = vec![
+        ["driver-list-published-name", "report-inf", "report-class", "driver-list-provider", "report-version", "report-date", "driver-list-size", "driver-list-devices"]
+            .iter()
+            .map(|id| getLocaleText(id, None))
+            .collect(),
+    ];
+    for package in packageList.iter() {
+        rows.push(vec![
+            package.PublishedName.clone(),
+            package.Inf.clone(),
+            package.Class.clone(),
+            package.Provider.clone(),
+            package.Version.clone(),
+            package.Date.clone(),
+            formatSize(package.Size),
+            package.Devices.as_ref().map(|devices| devices.len().to_string()).unwrap_or_default(),
+        ]);
+    }
+    formatTable(&rows)
+}
+
+/// 格式化文件大小（如 1.5 MB）
+/// # 参数
+/// 1. 大小（字节）
+pub(crate) fn formatSize(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
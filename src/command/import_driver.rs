@@ -1,77 +1,69 @@
 use crate::command::create_index::InfInfo;
-use crate::command::load_driver::getMatchInfo;
+use crate::command::load_driver::{applySignaturePolicy, checkDriverFiles, getMatchInfo};
 use crate::i18n::getLocaleText;
+use crate::utils::archiveBackend::openArchive;
 use crate::utils::console::{writeConsole, ConsoleType};
-use crate::utils::devcon::Devcon;
+use crate::utils::deviceBackend::{openBackend, DeviceBackend};
 use crate::utils::drvstoreAPI::DriverStore;
-use crate::utils::sevenZIP::sevenZip;
+use crate::utils::hardwareIdIndex::HardwareIdIndex;
+use crate::utils::offlineRegistry::OfflineRegistry;
+use crate::utils::signaturePolicy::SignaturePolicy;
+use crate::utils::targetOS::TargetOS;
 use crate::utils::util::{getArchCode, getFileList, isOfflineSystem};
 use crate::TEMP_PATH;
+use std::collections::BTreeSet;
 use std::error::Error;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-pub fn import_driver(systemDrive: &Path, driverPath: &Path, password: Option<&str>, matchDevice: bool) -> Result<(), Box<dyn Error>> {
+pub fn import_driver(systemDrive: &Path, driverPath: &Path, password: Option<&str>, matchDevice: bool, inventoryPath: Option<&Path>, policy: SignaturePolicy) -> Result<(), Box<dyn Error>> {
+    // 指定硬件清单时按清单中的设备匹配
+    let matchDevice = matchDevice || inventoryPath.is_some();
     let mut real_driver_path = driverPath.to_path_buf();
-    let zip = sevenZip::new()?;
+
+    // 匹配设备时使用的目标系统
+    let mut matchTarget: Option<TargetOS> = None;
 
     // 判断是否为驱动包
     if driverPath.is_file() {
+        let zip = openArchive(driverPath)?;
         if !zip.isDriverPackage(driverPath).unwrap_or(false) {
             return Err(String::from(&getLocaleText("no-driver-package", None)).into());
         }
 
         let driversPath = TEMP_PATH.join(driverPath.file_stem().unwrap());
-        if matchDevice {
-            // 解压全部INF文件
-            if !zip.extractFilesFromPath(driverPath, password, "*.inf", &driversPath)? {
-                return Err(getLocaleText("driver-unzip-failed", None).into());
-            }
-        } else {
+        // 匹配设备时直接读取压缩包中的INF文件，仅解压匹配的驱动目录
+        if !matchDevice {
             // 解压全部驱动文件
-            if !zip.extractFilesFromPath(driverPath, password, "*", &driversPath)? {
+            if !zip.extractPattern(driverPath, password, "*", &driversPath)? {
                 return Err(String::from(&getLocaleText("driver-unzip-failed", None)).into());
             };
         }
         real_driver_path = driversPath;
     }
 
-    // 遍历INF文件列表
-    let mut infList = getFileList(&real_driver_path, "*.inf")?;
+    // INF文件列表（匹配设备时仅包含匹配的驱动）
+    let mut infList: Vec<PathBuf>;
 
     // 匹配当前设备驱动
     if matchDevice {
-        // 获取真实硬件信息
-        let devcon = Devcon::new()?;
-        let hwIDList = devcon.getRealIdInfo(None)?;
-        if hwIDList.is_empty() {
-            return Err(getLocaleText("no-device", None).into());
-        }
-
-        // 解析INF文件
-        let mut infInfoList: Vec<InfInfo> = Vec::new();
-        for infPath in &infList {
-            if let Ok(currentInfo) = InfInfo::parsingInfFile(&real_driver_path, infPath) {
-                if !currentInfo.DriverList.is_empty() { infInfoList.push(currentInfo); }
-            }
-        }
+        // 获取真实硬件信息（或硬件清单中的设备，离线系统从其注册表中读取曾识别过的设备）
+        let backend: Arc<dyn DeviceBackend> = match inventoryPath {
+            None if isOfflineSystem(systemDrive)? => Arc::new(OfflineRegistry::open(systemDrive)?),
+            _ => openBackend(inventoryPath)?,
+        };
 
-        // 匹配驱动
-        let matchHardwareAndDriver = getMatchInfo(&hwIDList, &infInfoList, None);
-        if matchHardwareAndDriver.is_empty() {
-            return Err(String::from(&getLocaleText("no-found-driver-currently", None)).into());
-        }
+        // 目标系统（硬件清单以清单记录的系统为准）
+        let target = match backend.target() {
+            Some(target) => target,
+            None => systemTarget(systemDrive)?,
+        };
 
-        infList.clear();
-        for (_hardware, infInfo) in matchHardwareAndDriver.iter() {
-            // 仅匹配第一个最佳的驱动
-            if let Some(InfInfo) = infInfo.first() {
-                if !zip.extractFilesFromPath(driverPath, password, &InfInfo.Path, &real_driver_path)? {
-                    writeConsole(ConsoleType::Err, &getLocaleText("driver-unzip-failed", None));
-                    continue;
-                }
-                infList.push(real_driver_path.join(InfInfo.Path.clone()).join(InfInfo.Inf.clone()));
-            }
-        }
+        infList = matchDeviceDrivers(&*backend, driverPath, password, &real_driver_path, policy, &target)?;
+        matchTarget = Some(target);
+    } else {
+        // 遍历INF文件列表
+        infList = getFileList(&real_driver_path, "*.inf")?;
     }
 
     // 获取系统架构
@@ -95,6 +87,20 @@ pub fn import_driver(systemDrive: &Path, driverPath: &Path, password: Option<&st
     let mut fail_count = 0;
     let systemRoot = systemDrive.join("Windows");
 
+    // 导入前检查驱动文件是否完整、是否与编录文件一致、是否符合签名策略
+    let target = match matchTarget {
+        Some(target) => target,
+        None => systemTarget(systemDrive)?,
+    };
+    infList.retain(|infPath| match checkDriverFiles(infPath, policy, &target) {
+        Some(message) => {
+            writeConsole(ConsoleType::Err, &message);
+            fail_count += 1;
+            false
+        }
+        None => true,
+    });
+
     if !isOfflineSystem(systemDrive)? {
         // 在线导入驱动
         unsafe {
@@ -152,3 +158,71 @@ pub fn import_driver(systemDrive: &Path, driverPath: &Path, password: Option<&st
     writeConsole(ConsoleType::Info, &getLocaleText("driver-import-summary", Some(&arg)));
     Ok(())
 }
+
+/// 匹配设备的驱动
+/// 驱动包直接读取压缩包中的INF文件并按压缩包的文件列表检查驱动文件是否完整，仅解压匹配的驱动目录
+/// # 参数
+/// 1. 设备后端
+/// 2. 驱动包或驱动目录路径
+/// 3. 驱动包密码
+/// 4. 驱动解压路径（驱动目录时与驱动目录路径相同）
+/// 5. 签名策略
+/// 6. 目标系统
+/// # 返回
+/// 匹配驱动的INF文件路径列表（每个设备仅取最佳的驱动）
+pub(crate) fn matchDeviceDrivers(
+    backend: &dyn DeviceBackend,
+    driverPath: &Path,
+    password: Option<&str>,
+    driversPath: &Path,
+    policy: SignaturePolicy,
+    target: &TargetOS,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let hwIDList = backend.devices()?;
+    if hwIDList.is_empty() {
+        return Err(getLocaleText("no-device", None).into());
+    }
+
+    // 解析INF文件
+    let infInfoList = if driverPath.is_file() {
+        InfInfo::parsingInfArchive(driverPath, password).map_err(|_| getLocaleText("driver-unzip-failed", None))?
+    } else {
+        InfInfo::parsingInfFileList(driverPath, &getFileList(driverPath, "*.inf")?)
+    };
+    let infInfoList: Vec<InfInfo> = infInfoList.into_iter().filter(|infInfo| !infInfo.DriverList.is_empty()).collect();
+
+    // 匹配驱动（排除不符合签名策略的驱动）
+    let matchHardwareAndDriver = getMatchInfo(&hwIDList, &HardwareIdIndex::new(infInfoList), None, target);
+    let matchHardwareAndDriver = applySignaturePolicy(matchHardwareAndDriver, policy, target, &mut BTreeSet::new());
+    if matchHardwareAndDriver.is_empty() {
+        return Err(String::from(&getLocaleText("no-found-driver-currently", None)).into());
+    }
+
+    // 仅匹配第一个最佳的驱动
+    let mut bestList: Vec<&InfInfo> = Vec::new();
+    for infInfo in matchHardwareAndDriver.iter().filter_map(|(_hardware, infInfo)| infInfo.first()) {
+        if !bestList.iter().any(|item| item.Path == infInfo.Path && item.Inf == infInfo.Inf) {
+            bestList.push(infInfo);
+        }
+    }
+
+    // 一次解压全部匹配的驱动目录
+    if driverPath.is_file() {
+        let pathList: Vec<String> = bestList.iter().map(|infInfo| infInfo.Path.clone()).collect::<BTreeSet<String>>().into_iter().collect();
+        if !openArchive(driverPath)?.extractPatterns(driverPath, password, &pathList, driversPath)? {
+            return Err(getLocaleText("driver-unzip-failed", None).into());
+        }
+    }
+    Ok(bestList.iter().map(|infInfo| driversPath.join(&infInfo.Path).join(&infInfo.Inf)).collect())
+}
+
+/// 导入目标系统的版本（离线系统的架构、版本从其系统文件中读取）
+/// # 参数
+/// 1. 系统盘
+fn systemTarget(systemDrive: &Path) -> Result<TargetOS, Box<dyn Error>> {
+    if isOfflineSystem(systemDrive)? {
+        TargetOS::offline(systemDrive).map_err(|_| getLocaleText("offline-Arch-Err", None).into())
+    } else {
+        Ok(TargetOS::current())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::i18n::getLocaleText;
+use crate::utils::console::{writeConsole, ConsoleType};
+use crate::utils::devcon::Devcon;
+use crate::utils::inventory::Inventory;
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// 保存当前系统的硬件清单
+/// # 参数
+/// 1. 保存路径
+pub fn saveInventory(savePath: &Path) -> Result<(), Box<dyn Error>> {
+    let devcon = Devcon::new()?;
+    let inventory = Inventory::capture(&devcon)?;
+    if let Err(_e) = inventory.save(savePath) {
+        return Err(getLocaleText("inventory-save-failed", None).into());
+    }
+
+    let arg: HashMap<String, FluentValue> = hash_map!(
+        "count".to_string() => inventory.Devices.len().into(),
+        "path".to_string() => savePath.to_str().unwrap().into(),
+    );
+    writeConsole(ConsoleType::Success, &getLocaleText("inventory-saved", Some(&arg)));
+    Ok(())
+}
@@ -1,18 +1,29 @@
-use crate::command::create_index::InfInfo;
+use crate::command::create_index::{IndexFile, InfInfo};
 use crate::i18n::getLocaleText;
+use crate::utils::binaryIndex::BinaryIndex;
 use crate::utils::console::{writeConsole, ConsoleType};
-use crate::utils::devcon::{Devcon, HwID};
-use crate::utils::sevenZIP::sevenZip;
-use crate::utils::util::{compareVersion, getFileList};
-use crate::utils::{newdevAPI, setupAPI};
+use crate::utils::devcon::HwID;
+use crate::utils::archiveBackend::openArchive;
+use crate::utils::extractCache::ExtractCache;
+use crate::utils::catalog::SignatureStatus;
+use crate::utils::driverFiles::{missingFiles, unionFiles, verifySignature, SectionFiles};
+use crate::utils::signaturePolicy::SignaturePolicy;
+use crate::utils::infParser::InfFile;
+use crate::utils::targetOS::{TargetOS, TargetOSVersion};
+use crate::utils::driverRank::{computeRank, SignatureTier};
+use crate::utils::hardwareIdIndex::HardwareIdIndex;
+use crate::utils::deviceBackend::{openBackend, DeviceBackend, DeviceFilter};
+use crate::utils::util::{getFileList, parseDriverDate};
 use crate::TEMP_PATH;
 use fluent_templates::fluent_bundle::FluentValue;
-use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::env;
+use std::borrow::Cow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::time::Instant;
 use threadpool::ThreadPool;
 
 /// 加载驱动包。支持驱动包路径、驱动路径
@@ -20,20 +31,37 @@ use threadpool::ThreadPool;
 /// 1. 驱动包路径
 /// 2. 驱动包密码
 /// 3. 索引Option
-/// 4. 是否为精确匹配
+/// 4. 设备筛选
 /// 5. 驱动类别
-/// 6. 释放路径
+/// 6. 签名策略
+/// 7. 释放路径
+/// 8. 硬件清单路径（为 None 时使用当前设备）
+/// 9. 解压缓存（为 None 时解压到临时目录）
+#[allow(clippy::too_many_arguments)]
 pub fn loadDriver(
     driverPackPath: &Path,
     password: Option<&str>,
     indexPath: Option<PathBuf>,
-    isAllDevice: bool,
+    filter: &DeviceFilter,
     driveClass: Option<String>,
+    policy: SignaturePolicy,
     extractPath: Option<&str>,
+    inventoryPath: Option<&Path>,
+    cache: Option<&mut ExtractCache>,
 ) -> Result<(), Box<dyn Error>> {
-    let zip = sevenZip::new()?;
-    let devcon = Devcon::new()?;
+    let backend = openBackend(inventoryPath)?;
+    let group = packageMatchGroup(driverPackPath, password, indexPath, extractPath)?;
+    installMatchDrivers(&backend, &group, password, filter, driveClass, policy, extractPath.is_some(), cache)
+}
 
+/// 获取驱动包的匹配组
+/// 依次使用指定的索引、同目录下的同名索引、压缩包内的索引，均不可用时即时建立索引
+/// # 参数
+/// 1. 驱动包路径
+/// 2. 驱动包密码
+/// 3. 索引Option
+/// 4. 释放路径
+pub(crate) fn packageMatchGroup(driverPackPath: &Path, password: Option<&str>, indexPath: Option<PathBuf>, extractPath: Option<&str>) -> Result<MatchGroup, Box<dyn Error>> {
     // 当前临时驱动解压路径
     let driversPath = if driverPackPath.is_dir() {
         driverPackPath.to_path_buf()
@@ -41,7 +69,10 @@ pub fn loadDriver(
         TEMP_PATH.join(driverPackPath.file_stem().unwrap())
     };
 
-    let infInfoList = {
+    // 压缩包驱动需校验索引与驱动包是否一致
+    let packagePath = if driverPackPath.is_file() { Some(driverPackPath) } else { None };
+
+    let driverIndex = {
         // 指定索引文件
         if let Some(idx) = indexPath {
             // 根据相对/绝对路径定位
@@ -51,117 +82,316 @@ pub fn loadDriver(
             } else {
                 idx.to_path_buf()
             };
-
-            let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => index.to_str().unwrap().into());
-            writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
-            InfInfo::parsingIndex(&index).unwrap_or_else(|_| {
-                writeConsole(ConsoleType::Warning, &getLocaleText("index-parsing-failed", None));
-                Vec::new()
-            })
+            loadIndex(openIndex(&index), packagePath, password)
         } else {
-            Vec::new()
+            None
         }
     };
 
     // 自动检测同目录下的索引文件
-    let infInfoList = if !infInfoList.is_empty() {
-        infInfoList
-    } else {
+    let driverIndex = driverIndex.or_else(|| {
         let same_index = driverPackPath
             .parent()
             .unwrap()
             .join(format!("{}.index", driverPackPath.file_stem().unwrap().to_string_lossy()));
         if same_index.exists() {
-            let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => same_index.to_str().unwrap().into());
-            writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
-            InfInfo::parsingIndex(&same_index).unwrap_or_else(|_| {
-                writeConsole(ConsoleType::Warning, &getLocaleText("index-parsing-failed", None));
-                Vec::new()
-            })
+            loadIndex(openIndex(&same_index), packagePath, password)
         } else {
-            Vec::new()
+            None
         }
-    };
+    });
 
 
     // 自动检测压缩包内索引文件
-    let infInfoList = if infInfoList.is_empty() && driverPackPath.is_file() {
-        // 解压所有索引文件到临时目录
-        if zip.extractFilesFromPath(driverPackPath, password, "*.index", &driversPath)? == true {
-            // 目前假设只有一个 index 文件，直接 glob 查找
-            if let Some(found) = glob::glob(&format!("{}/**/*.index", driversPath.display()))?
-                .filter_map(Result::ok)
-                .next() {
-                let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => found.file_name().unwrap().to_str().unwrap().into());
-                writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
-                InfInfo::parsingIndex(&found).unwrap_or_else(|_| {
-                    writeConsole(ConsoleType::Warning, &getLocaleText("index-parsing-failed", None));
-                    Vec::new()
-                })
+    let driverIndex = match driverIndex {
+        Some(driverIndex) => Some(driverIndex),
+        None if driverPackPath.is_file() => {
+            // 直接读取压缩包内的索引文件（不解压到临时目录），目前假设只有一个 index 文件
+            openArchive(driverPackPath)?
+                .readFiles(driverPackPath, password, "*.index")
+                .ok()
+                .and_then(|fileList| fileList.into_iter().next())
+                // 压缩包内的索引无法记录自身所在驱动包的指纹，不校验
+                .and_then(|(entry, content)| loadIndex(openIndexContent(&driverPackPath.join(&entry.Path), content), None, password))
+                .map(|(driverIndex, _)| (driverIndex, None))
+        }
+        None => None,
+    };
+
+    // 即时建立索引
+    let (driverIndex, packageHash) = match driverIndex {
+        Some(found) => found,
+        None => {
+            let infInfoList = if driverPackPath.is_file() {
+                // 直接读取压缩包中的 INF 文件并在内存中建立索引（不解压到临时目录）
+                InfInfo::parsingInfArchive(driverPackPath, password).map_err(|_| {
+                    writeConsole(ConsoleType::Err, &getLocaleText("driver-unzip-failed", None));
+                    getLocaleText("driver-unzip-failed", None)
+                })?
             } else {
-                Vec::new()
+                // 多线程解析INF文件
+                InfInfo::parsingInfFileList(driverPackPath, &getFileList(driverPackPath, "*.inf")?)
+            };
+            if infInfoList.is_empty() {
+                writeConsole(ConsoleType::Err, &getLocaleText("no-driver-package", None));
+                return Err(getLocaleText("no-driver-package", None).into());
             }
-        } else {
-            Vec::new()
+            (DriverIndex::List(HardwareIdIndex::new(infInfoList)), None)
         }
-    } else {
-        Vec::new()
     };
 
-    // 即时建立索引
-    let infInfoList = if !infInfoList.is_empty() {
-        infInfoList
+    // 驱动来源（合并索引中的INF驱动信息以驱动包文件名关联）
+    let source = DriverSource {
+        packagePath: driverPackPath.to_path_buf(),
+        driversPath: extractPath.map(PathBuf::from).unwrap_or(driversPath),
+        packageHash,
+    };
+    let mut sources: HashMap<String, DriverSource> = HashMap::new();
+    sources.insert(String::new(), source.clone());
+    sources.insert(packageKey(driverPackPath), source);
+
+    Ok(MatchGroup { driverIndex, sources })
+}
+
+/// 加载多个驱动包（共用一个合并索引）
+/// 合并索引中的驱动包只匹配一次，仅从包含最优驱动的驱动包中解压；未包含在索引中的驱动包逐个加载
+/// # 参数
+/// 1. 驱动包路径列表
+/// 2. 驱动包密码
+/// 3. 索引文件列表（单个驱动包的索引或合并索引，多个索引将合并使用）
+/// 4. 设备筛选
+/// 5. 驱动类别
+/// 6. 签名策略
+/// 7. 释放路径
+/// 8. 硬件清单路径（为 None 时使用当前设备）
+/// 9. 解压缓存（为 None 时解压到临时目录）
+#[allow(clippy::too_many_arguments)]
+pub fn loadDriverPackages(
+    packageList: &[PathBuf],
+    password: Option<&str>,
+    indexList: &[PathBuf],
+    filter: &DeviceFilter,
+    driveClass: Option<String>,
+    policy: SignaturePolicy,
+    extractPath: Option<&str>,
+    inventoryPath: Option<&Path>,
+    mut cache: Option<&mut ExtractCache>,
+) -> Result<(), Box<dyn Error>> {
+    let backend = openBackend(inventoryPath)?;
+    for group in packagesMatchGroups(packageList, password, indexList, extractPath)? {
+        installMatchDrivers(&backend, &group, password, filter, driveClass.clone(), policy, extractPath.is_some(), cache.as_deref_mut())?;
+    }
+    Ok(())
+}
+
+/// 获取多个驱动包的匹配组
+/// 索引包含的驱动包共用一个匹配组，未包含在索引中的驱动包各自建立匹配组
+/// # 参数
+/// 1. 驱动包路径列表
+/// 2. 驱动包密码
+/// 3. 索引文件列表（单个驱动包的索引或合并索引，多个索引将合并使用）
+/// 4. 释放路径
+pub(crate) fn packagesMatchGroups(packageList: &[PathBuf], password: Option<&str>, indexList: &[PathBuf], extractPath: Option<&str>) -> Result<Vec<MatchGroup>, Box<dyn Error>> {
+    let mut groups: Vec<MatchGroup> = Vec::new();
+
+    // 读取索引
+    let mut openedList: Vec<(IndexFile, DriverIndex)> = Vec::new();
+    for indexPath in indexList.iter() {
+        match openIndex(indexPath) {
+            Ok((header, _)) if header.Package.is_none() && !header.isMerged() => {
+                writeConsole(ConsoleType::Warning, &getLocaleText("merge-index-no-package", None));
+            }
+            Ok(opened) => openedList.push(opened),
+            Err(e) => writeConsole(ConsoleType::Warning, &e.to_string()),
+        }
+    }
+
+    // 多个索引合并为一个
+    let opened = if openedList.len() > 1 {
+        let indexFiles = openedList.into_iter().map(|(header, driverIndex)| driverIndex.toIndexFile(header)).collect::<Result<Vec<IndexFile>, _>>()?;
+        let mut merged = IndexFile::merge(indexFiles)?;
+        let entries = std::mem::take(&mut merged.Entries);
+        Some((merged, DriverIndex::List(HardwareIdIndex::new(entries))))
     } else {
-        let driversPath = if driverPackPath.is_file() {
-            // 解压所有 INF 文件
-            if !zip.extractFilesFromPath(driverPackPath, password, "*.inf", &driversPath)? {
-                writeConsole(ConsoleType::Err, &getLocaleText("driver-unzip-failed", None));
-                return Err(getLocaleText("driver-unzip-failed", None).into());
+        openedList.pop()
+    };
+
+    // 区分索引包含的驱动包与未包含的驱动包
+    let mut sources: HashMap<String, DriverSource> = HashMap::new();
+    let mut uncoveredList: Vec<&PathBuf> = Vec::new();
+    for packagePath in packageList.iter() {
+        let Some((header, _)) = &opened else {
+            uncoveredList.push(packagePath);
+            continue;
+        };
+        let isMember = match &header.Package {
+            Some(package) => package.FileName.eq_ignore_ascii_case(&packagePath.file_name().unwrap_or_default().to_string_lossy()),
+            None => header.Packages.iter().any(|package| package.FileName.eq_ignore_ascii_case(&packagePath.file_name().unwrap_or_default().to_string_lossy())),
+        };
+        if !isMember {
+            uncoveredList.push(packagePath);
+            continue;
+        }
+        let packageHash = match header.verifyPackage(packagePath) {
+            Ok(packageHash) => packageHash,
+            Err(e) => {
+                writeConsole(ConsoleType::Warning, &e.to_string());
+                uncoveredList.push(packagePath);
+                continue;
             }
-            &driversPath
-        } else {
-            driverPackPath
         };
 
-        // 列出INF文件
-        let infList = getFileList(&driversPath, "*.inf")?;
-        if infList.is_empty() {
-            writeConsole(ConsoleType::Err, &getLocaleText("no-driver-package", None));
-            return Err(getLocaleText("no-driver-package", None).into());
+        let source = DriverSource {
+            packagePath: packagePath.to_path_buf(),
+            driversPath: extractPath.map(PathBuf::from).unwrap_or_else(|| TEMP_PATH.join(packagePath.file_stem().unwrap())),
+            packageHash,
+        };
+        // 单个驱动包的索引中INF驱动信息未记录所属驱动包
+        if !header.isMerged() {
+            sources.insert(String::new(), source.clone());
         }
+        sources.insert(packageKey(packagePath), source);
+    }
 
-        // 多线程解析INF文件
-        InfInfo::parsingInfFileList(&driversPath, &infList)
-    };
+    if let Some((header, driverIndex)) = opened.filter(|_| !sources.is_empty()) {
+        if header.PasswordRequired && password.is_none() {
+            writeConsole(ConsoleType::Warning, &getLocaleText("index-password-required", None));
+        }
+        for packagePath in packageList.iter().filter(|item| !uncoveredList.contains(item)) {
+            let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => packagePath.to_str().unwrap().into());
+            writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
+        }
+        groups.push(MatchGroup { driverIndex, sources });
+    }
+
+    // 未包含在索引中的驱动包
+    for packagePath in uncoveredList {
+        let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => packagePath.to_str().unwrap().into());
+        writeConsole(ConsoleType::Info, &getLocaleText("load-driver-package", Some(&args)));
+
+        groups.push(packageMatchGroup(packagePath, password, None, extractPath)?);
+    }
+    Ok(groups)
+}
+
+/// 驱动来源
+#[derive(Debug, Clone)]
+pub(crate) struct DriverSource {
+    /// 驱动包或驱动目录路径
+    pub(crate) packagePath: PathBuf,
+    /// 驱动解压路径
+    pub(crate) driversPath: PathBuf,
+    /// 驱动包 SHA-256（校验索引时已计算，解压缓存无需重复计算）
+    pub(crate) packageHash: Option<String>,
+}
+
+/// 匹配组：驱动索引及其驱动来源
+pub(crate) struct MatchGroup {
+    /// 驱动索引
+    driverIndex: DriverIndex,
+    /// 所属驱动包 => 驱动来源（合并索引中的INF驱动信息以驱动包文件名关联）
+    sources: HashMap<String, DriverSource>,
+}
+
+impl MatchGroup {
+    /// 获取匹配驱动的信息（仅保留可用驱动来源中的驱动）
+    /// # 参数
+    /// 1. 硬件ID列表
+    /// 2. 驱动类别
+    /// 3. 目标系统
+    pub(crate) fn matchDevices(&self, idInfo: &[HwID], driveClass: Option<String>, target: &TargetOS) -> Vec<(HwID, Vec<InfInfo>)> {
+        let hardwareIdIndex = self.driverIndex.candidates(idInfo);
+        getMatchInfo(idInfo, &hardwareIdIndex, driveClass, target)
+            .into_iter()
+            .filter_map(|(hardware, infInfo)| {
+                let infInfo: Vec<InfInfo> = infInfo.into_iter().filter(|item| self.source(item).is_some()).collect();
+                (!infInfo.is_empty()).then_some((hardware, infInfo))
+            })
+            .collect()
+    }
+
+    /// 获取INF驱动信息所属的驱动来源
+    /// # 参数
+    /// 1. INF驱动信息
+    pub(crate) fn source(&self, infInfo: &InfInfo) -> Option<&DriverSource> {
+        self.sources.get(&infInfo.Package.to_lowercase())
+    }
+}
+
+/// 获取当前设备
+/// # 参数
+/// 1. 设备后端
+/// 2. 设备筛选
+pub(crate) fn currentDevices(backend: &dyn DeviceBackend, filter: &DeviceFilter) -> Result<Vec<HwID>, Box<dyn Error>> {
+    // 获取真实硬件信息
+    let hwIDList = backend.devices()?;
+    if hwIDList.is_empty() {
+        writeConsole(ConsoleType::Err, &getLocaleText("no-device", None));
+        return Err(getLocaleText("no-device", None).into());
+    }
+
+    // 判断是否需要获取硬件的运行状态
+    if *filter == DeviceFilter::All {
+        return Ok(hwIDList);
+    }
+    let hwIDList = backend.queryStatus(hwIDList)?;
+    Ok(hwIDList.into_iter().filter(|hwID| filter.isMatch(hwID)).collect())
+}
+
+/// 驱动包在驱动来源中的键（文件名，不区分大小写）
+fn packageKey(packagePath: &Path) -> String {
+    packagePath.file_name().unwrap_or_default().to_string_lossy().to_lowercase()
+}
+
+/// 匹配并安装驱动
+/// # 参数
+/// 1. 设备后端
+/// 2. 匹配组
+/// 3. 驱动包密码
+/// 4. 设备筛选
+/// 5. 驱动类别
+/// 6. 签名策略
+/// 7. 是否仅解压
+/// 8. 解压缓存（仅解压到指定路径时不使用）
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn installMatchDrivers(
+    backend: &Arc<dyn DeviceBackend>,
+    group: &MatchGroup,
+    password: Option<&str>,
+    filter: &DeviceFilter,
+    driveClass: Option<String>,
+    policy: SignaturePolicy,
+    onlyExtract: bool,
+    mut cache: Option<&mut ExtractCache>,
+) -> Result<(), Box<dyn Error>> {
+    let mut sources = group.sources.clone();
+    if onlyExtract {
+        cache = None;
+    }
+
+    // 目标系统
+    let target = backend.target().unwrap_or_else(TargetOS::current);
 
     let mut totalList: Vec<HwID> = Vec::new();
+    // 已提示不符合签名策略的驱动
+    let mut rejectedList: BTreeSet<String> = BTreeSet::new();
 
     // 3次匹配，避免部分驱动安装不全
     for scanCount in 0..3 {
         // 扫描以发现新的硬件
         // devcon.rescan().unwrap();
-        unsafe { setupAPI::rescan(); }
+        backend.rescan();
 
-        // 获取真实硬件信息
-        let mut hwIDList = devcon.getRealIdInfo(None).unwrap();
-        if hwIDList.is_empty() {
-            writeConsole(ConsoleType::Err, &getLocaleText("no-device", None));
-            return Err(getLocaleText("no-device", None).into());
-        }
-
-        // 判断是否需要获取有问题的硬件信息
-        if !isAllDevice {
-            hwIDList = devcon.getProblemIdInfo(hwIDList).unwrap();
-            if hwIDList.is_empty() {
-                // 没有需要安装驱动的设备
-                writeConsole(ConsoleType::Err, &getLocaleText("no-found-driver-currently", None));
-                return Err(getLocaleText("no-found-driver-currently", None).into());
-            }
+        // 获取硬件信息
+        let hwIDList = currentDevices(backend.as_ref(), filter)?;
+        if scanCount == 0 && hwIDList.is_empty() {
+            // 没有需要安装驱动的设备
+            writeConsole(ConsoleType::Err, &getLocaleText("no-found-driver-currently", None));
+            return Err(getLocaleText("no-found-driver-currently", None).into());
         }
 
         // 过滤前一次安装的硬件信息
         let hwIDList: Vec<HwID> = hwIDList
-            .clone()
             .into_iter()
             .filter(|item| !totalList.contains(item))
             .collect();
@@ -174,215 +404,558 @@ pub fn loadDriver(
         }
 
         // 获取匹配硬件id
-        let matchHardwareAndDriver = getMatchInfo(&hwIDList, &infInfoList, driveClass.clone());
+        let matchHardwareAndDriver = group.matchDevices(&hwIDList, driveClass.clone(), &target);
+        let matchHardwareAndDriver = applySignaturePolicy(matchHardwareAndDriver, policy, &target, &mut rejectedList);
         if scanCount == 0 && matchHardwareAndDriver.is_empty() {
             writeConsole(ConsoleType::Err, &getLocaleText("no-found-driver-currently", None));
             break;
         }
 
-        // 任务列表
-        let pool = ThreadPool::new(num_cpus::get());
-        let (tx, rx) = channel();
+        // 先一次性解压并安装每个设备的首选驱动
+        let firstList: Vec<(HwID, Vec<InfInfo>)> = matchHardwareAndDriver.iter().map(|(hardware, infInfo)| (hardware.clone(), infInfo[..1].to_vec())).collect();
+        extractMatchDrivers(&mut sources, &firstList, password, cache.as_deref_mut());
+        let time = Instant::now();
+        let deviceCount = matchHardwareAndDriver.len();
+        let mut fallbackList: Vec<(HwID, Vec<InfInfo>)> = Vec::new();
+        for (index, result) in installDevices(backend, &sources, &firstList, policy, &target, onlyExtract) {
+            match result {
+                Ok(msg) => writeConsole(ConsoleType::Success, &msg),
+                // 首选驱动安装失败时改用其余候选驱动
+                Err(_) if matchHardwareAndDriver[index].1.len() > 1 => {
+                    let (hardware, infInfo) = &matchHardwareAndDriver[index];
+                    fallbackList.push((hardware.clone(), infInfo[1..].to_vec()));
+                }
+                Err(err) => writeConsole(ConsoleType::Err, &err),
+            }
+        }
 
-        // 循环匹配信息
-        for (hardware, infInfo) in matchHardwareAndDriver {
-            // 当前状态：一个设备中有一个或多个驱动
-            let driverPackPath = driverPackPath.to_path_buf();
-            let password = password.map(|password| password.to_string());
-            let onlyExtract = extractPath.is_some();
+        // 再一次性解压并安装首选驱动安装失败的设备的其余候选驱动
+        if !fallbackList.is_empty() {
+            extractMatchDrivers(&mut sources, &fallbackList, password, cache.as_deref_mut());
+            for (_index, result) in installDevices(backend, &sources, &fallbackList, policy, &target, onlyExtract) {
+                match result {
+                    Ok(msg) => writeConsole(ConsoleType::Success, &msg),
+                    Err(err) => writeConsole(ConsoleType::Err, &err),
+                }
+            }
+        }
+        if !onlyExtract {
+            let arg = hash_map!(
+                "count".to_string() => deviceCount.into(),
+                "time".to_string() => format!("{:.2}", time.elapsed().as_secs_f64()).into(),
+            );
+            writeConsole(ConsoleType::Info, &getLocaleText("driver-install-time", Some(&arg)));
+        }
+    }
+    Ok(())
+}
 
-            let driversPath = match extractPath {
-                None => driversPath.clone(),
-                Some(path) => PathBuf::from(path)
-            };
-            let hardware = hardware.clone();
-            let infInfo = infInfo.clone();
-
-            // 为每个设备分配一个线程
-            let tx = tx.clone();
-
-            pool.execute(move || {
-                let result = installDriver(
-                    &driverPackPath,
-                    password.clone(),
-                    &driversPath,
-                    &hardware,
-                    &infInfo,
-                    onlyExtract,
+/// 多线程安装设备的驱动，返回 匹配信息中的序号 => 安装结果
+/// # 参数
+/// 1. 设备后端
+/// 2. 驱动来源（所属驱动包 => 驱动来源）
+/// 3. 匹配信息
+/// 4. 签名策略
+/// 5. 目标系统
+/// 6. 是否仅解压
+fn installDevices(
+    backend: &Arc<dyn DeviceBackend>,
+    sources: &HashMap<String, DriverSource>,
+    matchList: &[(HwID, Vec<InfInfo>)],
+    policy: SignaturePolicy,
+    target: &TargetOS,
+    onlyExtract: bool,
+) -> Vec<(usize, Result<String, String>)> {
+    let sources = Arc::new(sources.clone());
+
+    // 任务列表
+    let pool = ThreadPool::new(num_cpus::get());
+    let (tx, rx) = channel();
+
+    // 循环匹配信息
+    for (index, (hardware, infInfo)) in matchList.iter().enumerate() {
+        // 当前状态：一个设备中有一个或多个驱动
+        let sources = Arc::clone(&sources);
+        let backend = Arc::clone(backend);
+        let hardware = hardware.clone();
+        let infInfo = infInfo.clone();
+        let target = target.clone();
+
+        // 为每个设备分配一个线程
+        let tx = tx.clone();
+
+        pool.execute(move || {
+            let result = installDriver(
+                backend.as_ref(),
+                &sources,
+                &hardware,
+                &infInfo,
+                policy,
+                &target,
+                onlyExtract,
+            );
+            tx.send((index, result)).expect("send result");
+        });
+    }
+
+    // 等待所有线程执行完成
+    drop(tx);  // 关闭发送端
+    rx.iter().collect()
+}
+
+/// 解压匹配的驱动
+/// # 参数
+/// 1. 驱动来源（所属驱动包 => 驱动来源，使用解压缓存时解压路径改为缓存中的驱动包目录）
+/// 2. 匹配信息
+/// 3. 驱动包密码
+/// 4. 解压缓存
+/// # 规则
+/// 1. 先收集每个驱动包中匹配信息内全部驱动的目录，每个驱动包只解压一次（固实压缩包无需重复解压固实块）
+/// 2. 驱动目录无需解压
+/// 3. 已缓存且完整的驱动目录不再解压
+fn extractMatchDrivers(sources: &mut HashMap<String, DriverSource>, matchList: &[(HwID, Vec<InfInfo>)], password: Option<&str>, mut cache: Option<&mut ExtractCache>) {
+    // 所属驱动包 => 需要解压的驱动目录
+    let mut extractList: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for infInfo in matchList.iter().flat_map(|(_, infInfo)| infInfo) {
+        let key = infInfo.Package.to_lowercase();
+        if sources.get(&key).is_some_and(|source| source.packagePath.is_file()) {
+            extractList.entry(key).or_default().insert(infInfo.Path.clone());
+        }
+    }
+
+    for (key, pathList) in extractList {
+        let Some(DriverSource { packagePath, driversPath, packageHash }) = sources.get_mut(&key) else { continue };
+        let time = Instant::now();
+        let mut pathList: Vec<String> = pathList.into_iter().collect();
+
+        // 使用缓存中已解压的驱动目录（无法计算驱动包指纹时解压到临时目录）
+        let mut cached = cache.as_deref_mut().and_then(|cache| Some((cache.packageHash(packagePath, packageHash.as_deref()).ok()?, cache)));
+        if let Some((hash, cache)) = cached.as_mut() {
+            *driversPath = cache.packageDir(hash);
+            let count = pathList.len();
+            pathList.retain(|path| !cache.lookup(hash, path));
+            if pathList.len() < count {
+                let arg = hash_map!(
+                    "path".to_string() => packagePath.display().to_string().into(),
+                    "count".to_string() => (count - pathList.len()).into(),
                 );
-                tx.send((hardware, result)).expect("send result");
-            });
+                writeConsole(ConsoleType::Info, &getLocaleText("driver-cache-hit", Some(&arg)));
+            }
+        }
+        if pathList.is_empty() {
+            continue;
         }
 
-        // 等待所有线程执行完成
-        drop(tx);  // 关闭发送端
-        for (_hardware, result) in rx.iter() {
-            match result {
-                Ok(msg) => writeConsole(ConsoleType::Success, &msg),
-                Err(err) => writeConsole(ConsoleType::Err, &err),
+        let result = openArchive(packagePath).and_then(|archive| archive.extractPatterns(packagePath, password, &pathList, driversPath)).unwrap_or(false);
+        if let Some((hash, cache)) = cached {
+            if result {
+                cache.record(packagePath, &hash, &pathList);
             }
+            cache.save().ok();
         }
+        if !result {
+            writeConsole(ConsoleType::Err, &getLocaleText("driver-unzip-failed", None));
+            continue;
+        }
+        let arg = hash_map!(
+            "path".to_string() => packagePath.display().to_string().into(),
+            "count".to_string() => pathList.len().into(),
+            "time".to_string() => format!("{:.2}", time.elapsed().as_secs_f64()).into(),
+        );
+        writeConsole(ConsoleType::Info, &getLocaleText("driver-extract-time", Some(&arg)));
     }
-    Ok(())
 }
 
+/// 驱动索引
+enum DriverIndex {
+    /// INF信息列表（JSON 索引、即时建立的索引）
+    List(HardwareIdIndex),
+    /// 内存映射的二进制索引（按硬件id读取所需的INF信息）
+    Binary(BinaryIndex),
+}
+
+impl DriverIndex {
+    /// 转换为完整的索引文件
+    /// # 参数
+    /// 1. 索引文件头
+    fn toIndexFile(self, header: IndexFile) -> Result<IndexFile, Box<dyn Error>> {
+        let Entries = match self {
+            DriverIndex::List(index) => index.into_entries(),
+            DriverIndex::Binary(index) => index.toIndexFile()?.Entries,
+        };
+        Ok(IndexFile { Entries, ..header })
+    }
+
+    /// 获取可能与设备匹配的INF信息
+    /// # 参数
+    /// 1. 硬件ID列表
+    fn candidates(&self, idInfo: &[HwID]) -> Cow<'_, HardwareIdIndex> {
+        match self {
+            DriverIndex::List(index) => Cow::Borrowed(index),
+            DriverIndex::Binary(index) => {
+                let mut entries: Vec<usize> = idInfo
+                    .iter()
+                    .flat_map(HardwareIdIndex::deviceIDs)
+                    .flat_map(|(_, _, id)| index.lookup(&id))
+                    .collect();
+                entries.sort_unstable();
+                entries.dedup();
+                Cow::Owned(HardwareIdIndex::new(entries.into_iter().map(|entry| index.entry(entry)).collect()))
+            }
+        }
+    }
+}
+
+/// 打开索引文件
+/// 二进制索引仅读取文件头，INF信息在匹配时按需读取
+/// # 参数
+/// 1. 索引文件路径
+/// # 返回
+/// (索引文件头, 驱动索引)
+fn openIndex(indexPath: &Path) -> Result<(IndexFile, DriverIndex), Box<dyn Error>> {
+    let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => indexPath.to_str().unwrap().into());
+    writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
+
+    if BinaryIndex::isBinaryIndex(indexPath) {
+        BinaryIndex::open(indexPath)
+            .and_then(|index| Ok((index.header()?, DriverIndex::Binary(index))))
+            .map_err(|_| getLocaleText("index-parsing-failed", None).into())
+    } else {
+        IndexFile::load(indexPath).map(|mut index| {
+            let entries = std::mem::take(&mut index.Entries);
+            (index, DriverIndex::List(HardwareIdIndex::new(entries)))
+        })
+    }
+}
+
+/// 打开压缩包内的索引（索引内容已读取到内存中）
+/// # 参数
+/// 1. 索引在压缩包内的路径（仅用于提示）
+/// 2. 索引内容
+/// # 返回
+/// (索引文件头, 驱动索引)
+fn openIndexContent(indexPath: &Path, content: Vec<u8>) -> Result<(IndexFile, DriverIndex), Box<dyn Error>> {
+    let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => indexPath.display().to_string().into());
+    writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
+
+    if BinaryIndex::isBinaryContent(&content) {
+        BinaryIndex::fromBytes(content)
+            .and_then(|index| Ok((index.header()?, DriverIndex::Binary(index))))
+            .map_err(|_| getLocaleText("index-parsing-failed", None).into())
+    } else {
+        let content = String::from_utf8(content).map_err(|_| getLocaleText("index-parsing-failed", None))?;
+        IndexFile::parse(&content).map(|mut index| {
+            let entries = std::mem::take(&mut index.Entries);
+            (index, DriverIndex::List(HardwareIdIndex::new(entries)))
+        })
+    }
+}
+
+/// 加载索引
+/// 索引不可用（解析失败、版本不支持、与驱动包不一致）时返回 None，以便改为即时建立索引
+/// # 参数
+/// 1. 打开索引的结果
+/// 2. 驱动包路径（为 None 时不校验驱动包指纹）
+/// 3. 驱动包密码
+/// # 返回
+/// (驱动索引, 校验时计算的驱动包 SHA-256)
+fn loadIndex(result: Result<(IndexFile, DriverIndex), Box<dyn Error>>, packagePath: Option<&Path>, password: Option<&str>) -> Option<(DriverIndex, Option<String>)> {
+    let (header, driverIndex) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            writeConsole(ConsoleType::Warning, &e.to_string());
+            return None;
+        }
+    };
+
+    let packageHash = match packagePath.map(|packagePath| header.verifyPackage(packagePath)).transpose() {
+        Ok(packageHash) => packageHash.flatten(),
+        Err(e) => {
+            writeConsole(ConsoleType::Warning, &e.to_string());
+            return None;
+        }
+    };
+    if header.PasswordRequired && password.is_none() {
+        writeConsole(ConsoleType::Warning, &getLocaleText("index-password-required", None));
+    }
+    match &driverIndex {
+        DriverIndex::List(index) if index.entries().is_empty() => None,
+        DriverIndex::Binary(index) if index.is_empty() => None,
+        _ => Some((driverIndex, packageHash)),
+    }
+}
 
 /// 安装驱动包
 /// # 参数
-/// 1. 驱动包路径
-/// 2. 解压密码
-/// 3. 驱动解压路径
-/// 4. 硬件信息
-/// 5. INF信息列表
-/// 6. 是否仅解压
+/// 1. 设备后端
+/// 2. 驱动来源（所属驱动包 => 驱动来源）
+/// 3. 硬件信息
+/// 4. INF信息列表
+/// 5. 签名策略
+/// 6. 目标系统
+/// 7. 是否仅解压
 fn installDriver(
-    driverPackPath: &Path,
-    password: Option<String>,
-    driversPath: &Path,
+    backend: &dyn DeviceBackend,
+    sources: &HashMap<String, DriverSource>,
     hardware: &HwID,
     infInfo: &[InfInfo],
+    policy: SignaturePolicy,
+    target: &TargetOS,
     onlyExtract: bool,
 ) -> Result<String, String> {
-    lazy_static! {
-        pub static ref ZIP: sevenZip = sevenZip::new().unwrap();
-    }
-
     // 遍历匹配的驱动
-    unsafe {
-        for infInfoItem in infInfo.iter() {
-            let arg: HashMap<String, FluentValue> = hash_map!(
-                "class".to_string() => infInfoItem.Class.clone().into(),
-                "deviceName".to_string() => hardware.Name.clone().into(),
-                "deviceID".to_string() => hardware.HardwareIDs.first().unwrap_or(&"".to_string()).clone().into(),
-                "driver".to_string() => infInfoItem.Inf.clone().into(),
-                "version".to_string() => infInfoItem.Version.clone().into(),
-            );
-
-            // 获取解压路径（相对于解压所有INF文件的路径）
-            let extractPath = &infInfoItem.Path;
-            let password = password.as_deref();
-
-            // 解压匹配的驱动
-            if driverPackPath.is_file() && !ZIP.extractFilesFromPath(driverPackPath, password, extractPath.as_str(), driversPath).unwrap() {
-                if Some(infInfoItem) != infInfo.last() {
-                    continue;
-                } else {
-                    return Err(getLocaleText("install-message", Some(&arg)));
-                }
-            }
-
-            // 仅解压驱动
-            if onlyExtract {
-                return Ok(getLocaleText("install-message", Some(&arg)));
+    for infInfoItem in infInfo.iter() {
+        let arg: HashMap<String, FluentValue> = hash_map!(
+            "class".to_string() => infInfoItem.Class.clone().into(),
+            "deviceName".to_string() => hardware.Name.clone().into(),
+            "deviceID".to_string() => hardware.HardwareIDs.first().unwrap_or(&"".to_string()).clone().into(),
+            "driver".to_string() => infInfoItem.Inf.clone().into(),
+            "version".to_string() => infInfoItem.Version.clone().into(),
+        );
+
+        // 获取所属驱动包的解压路径（驱动已在安装前解压）
+        let Some(DriverSource { driversPath, .. }) = sources.get(&infInfoItem.Package.to_lowercase()) else { continue };
+
+        // 获取INF路径
+        let driveInfPath = driversPath.join(&infInfoItem.Path).join(&infInfoItem.Inf);
+
+        // 驱动未能解压
+        if !driveInfPath.is_file() {
+            if Some(infInfoItem) != infInfo.last() {
+                continue;
+            } else {
+                return Err(getLocaleText("install-message", Some(&arg)));
             }
+        }
 
-            // 获取INF路径
-            let driveInfPath = driversPath.join(extractPath).join(&infInfoItem.Inf);
-
-            // 加载驱动
-            let result: bool = infInfoItem
-                .DriverList
-                .iter()
-                .any(|hwId| newdevAPI::updateDriverForPlugAndPlayDevices(&driveInfPath, hwId));
-            // 如果当前驱动加载失败则加载下一驱动
-            if !result {
-                if Some(infInfoItem) != infInfo.last() {
-                    continue;
-                } else {
-                    return Err(getLocaleText("install-message", Some(&arg)));
-                }
+        // 安装前检查驱动文件是否完整、是否与编录文件一致、是否符合签名策略
+        if let Some(message) = checkDriverFiles(&driveInfPath, policy, target) {
+            writeConsole(ConsoleType::Warning, &message);
+            if Some(infInfoItem) != infInfo.last() {
+                continue;
+            } else {
+                return Err(getLocaleText("install-message", Some(&arg)));
             }
+        }
 
+        // 仅解压驱动
+        if onlyExtract {
             return Ok(getLocaleText("install-message", Some(&arg)));
         }
-        Err(getLocaleText("driver-install-failed", None))
+
+        // 加载驱动
+        let result: bool = infInfoItem
+            .DriverList
+            .iter()
+            .any(|hwId| backend.installInf(&driveInfPath, &hwId.Id));
+        // 如果当前驱动加载失败则加载下一驱动
+        if !result {
+            if Some(infInfoItem) != infInfo.last() {
+                continue;
+            } else {
+                return Err(getLocaleText("install-message", Some(&arg)));
+            }
+        }
+
+        return Ok(getLocaleText("install-message", Some(&arg)));
     }
+    Err(getLocaleText("driver-install-failed", None))
+}
+
+/// 安装前检查解压后的驱动文件
+/// # 参数
+/// 1. INF路径
+/// 2. 签名策略
+/// 3. 目标系统
+/// # 返回
+/// 适用于目标系统的 Models 节缺少驱动文件、与编录文件不一致或不符合签名策略时返回提示信息
+pub(crate) fn checkDriverFiles(infPath: &Path, policy: SignaturePolicy, target: &TargetOS) -> Option<String> {
+    let inf = InfFile::open(infPath).ok()?;
+    let infDir = infPath.parent()?;
+    let infName = infPath.file_name()?.to_string_lossy().to_string();
+
+    // 仅检查适用于目标系统的 Models 节
+    let infInfo = InfInfo::fromInf(String::new(), infName.clone(), &inf);
+    let applicable = applicableModels(&infInfo, target);
+    let missingList: Vec<SectionFiles> = missingFiles(&inf, infDir).into_iter().filter(|section| applicable.contains(&(section.Models.as_str(), section.Decoration.as_str()))).collect();
+    let missing = unionFiles(&missingList);
+    if !missing.is_empty() {
+        let arg: HashMap<String, FluentValue> = hash_map!(
+            "path".to_string() => infPath.display().to_string().into(),
+            "files".to_string() => missing.join(", ").into(),
+        );
+        return Some(getLocaleText("driver-files-missing", Some(&arg)));
+    }
+    let signature = verifySignature(&inf, &infName, Some(&target.Arch), &|file| std::fs::read(infDir.join(file)).ok());
+    if signature.Status == SignatureStatus::Tampered {
+        let arg: HashMap<String, FluentValue> = hash_map!(
+            "path".to_string() => infPath.display().to_string().into(),
+            "files".to_string() => signature.Mismatched.join(", ").into(),
+        );
+        return Some(getLocaleText("driver-signature-mismatch", Some(&arg)));
+    }
+    policy.checkSignature(&signature, target).err().map(|violation| violation.message(&infPath.display().to_string(), target))
+}
+
+/// 排除不符合签名策略的驱动
+/// 按索引中记录的签名检查，每个驱动仅提示一次；排除后没有候选驱动的设备不再匹配
+/// # 参数
+/// 1. 匹配信息
+/// 2. 签名策略
+/// 3. 目标系统
+/// 4. 已提示的驱动
+pub(crate) fn applySignaturePolicy(matchList: Vec<(HwID, Vec<InfInfo>)>, policy: SignaturePolicy, target: &TargetOS, rejectedList: &mut BTreeSet<String>) -> Vec<(HwID, Vec<InfInfo>)> {
+    matchList
+        .into_iter()
+        .filter_map(|(hardware, infInfo)| {
+            let infInfo: Vec<InfInfo> = infInfo
+                .into_iter()
+                .filter(|item| match policy.checkInf(item, target) {
+                    Ok(_) => true,
+                    Err(violation) => {
+                        let path = Path::new(&item.Package).join(&item.Path).join(&item.Inf).display().to_string();
+                        if rejectedList.insert(path.clone()) {
+                            writeConsole(ConsoleType::Warning, &violation.message(&path, target));
+                        }
+                        false
+                    }
+                })
+                .collect();
+            (!infInfo.is_empty()).then_some((hardware, infInfo))
+        })
+        .collect()
 }
 
 /// 获取匹配驱动的信息
 /// # 参数
 /// 1. 硬件ID列表
-/// 2. INF驱动信息列表
+/// 2. 硬件id索引
+/// 3. 驱动类别
+/// 4. 目标系统
 /// # 规则
-/// 1. 专用驱动优先级大于公版
-/// 2. 高版本优先级大于低版本
-pub fn getMatchInfo<T1>(idInfo: &[HwID], infInfoList: &[InfInfo], driveClass: T1) -> Vec<(HwID, Vec<InfInfo>)>
+/// 1. 仅匹配适用于目标系统的 Models 节（每个厂商选用最具体的修饰），跳过索引中标记为缺少文件的 Models 节
+/// 2. 按驱动等级排序，等级越小优先级越高（签名 > 功能分数 > 匹配的id类型 > 匹配的id位置）
+/// 3. 等级相同时，新日期优先级大于旧日期，高版本优先级大于低版本
+pub fn getMatchInfo<T1>(idInfo: &[HwID], index: &HardwareIdIndex, driveClass: T1, target: &TargetOS) -> Vec<(HwID, Vec<InfInfo>)>
 where
     T1: Into<Option<String>> + Clone,
 {
     // 提示：
-    // 循环次数少的放在外层，减少内层变量的操作次数
     // 一个设备信息 对应 多个匹配驱动信息
+    // 同一INF以等级最小（最优）的硬件id计算驱动等级
 
-    // 当前系统架构
-    let currentArch = match env::consts::ARCH {
-        "x86" => "NTx86",
-        "x86_64" => "NTamd64",
-        "arm" => "NTarm",
-        _ => "",
-    };
+    let driveClass: Option<String> = driveClass.into();
+    let entries = index.entries();
 
-    // 闭包函数-匹配
-    let matchFn = |haID: &String| {
-        let mut macthList: Vec<InfInfo> = Vec::new();
-        // 遍历INF信息列表
-        for infInfoItem in infInfoList.iter() {
-            // 如果INF不适用当前系统则进行匹配下一个INF
-            if !infInfoItem.Arch.contains(&currentArch.to_string()) {
-                continue;
-            }
-            // 如果指定了驱动类别且类别不匹配则匹配下一个INF
-            if let Some(class) = driveClass.clone().into() {
-                if class.to_lowercase() != infInfoItem.Class.to_lowercase() {
-                    continue;
-                }
-            }
-
-            let mut matchInfInfo = InfInfo {
-                DriverList: vec![],
-                ..infInfoItem.clone()
-            };
-            // 遍历INF中的硬件id
-            let mut driverList: Vec<String> = infInfoItem
-                .DriverList
-                .clone()
-                .into_iter()
-                .filter(|infID| haID.to_lowercase() == infID.to_lowercase())
-                .collect();
-            matchInfInfo.DriverList.append(&mut driverList);
-            if !matchInfInfo.DriverList.is_empty() {
-                macthList.push(matchInfInfo.clone());
-            }
-        }
-        // 排序：高版本优先级大于低版本
-        macthList.sort_by(|b, a| compareVersion(&a.Version, &b.Version).unwrap_or(Ordering::Less));
-        macthList
-    };
+    // 各INF适用于目标系统的 Models 节（仅计算被查询到的INF）
+    let mut applicableList: HashMap<usize, Vec<(&str, &str)>> = HashMap::new();
 
     // 匹配驱动信息
     let mut macthInfo: Vec<(HwID, Vec<InfInfo>)> = Vec::new();
 
     // 遍历有问题的硬件id信息
     for idInfo in idInfo.iter() {
-        // 创建匹配信息列表
-        let mut macthList: Vec<InfInfo> = Vec::new();
-
-        // 优先对比硬件id
-        for haID in idInfo.HardwareIDs.iter() {
-            macthList.append(&mut matchFn(haID));
-        }
-
-        // 对比兼容id
-        for haID in idInfo.CompatibleIDs.iter() {
-            macthList.append(&mut matchFn(haID));
+        // INF下标 => 匹配的硬件id及其等级（按INF顺序排列，保证排序稳定）
+        let mut matchList: BTreeMap<usize, Vec<(u32, usize)>> = BTreeMap::new();
+
+        for (isCompatibleID, deviceIndex, deviceID) in HardwareIdIndex::deviceIDs(idInfo) {
+            for &(entryIndex, driverIndex) in index.lookup(&deviceID) {
+                let infInfo = &entries[entryIndex];
+                let driverID = &infInfo.DriverList[driverIndex];
+                // 跳过来自不完整的 Models 节的硬件id（旧版索引未标记时跳过整个驱动）
+                if driverID.Incomplete || (!infInfo.Missing.is_empty() && !infInfo.DriverList.iter().any(|item| item.Incomplete)) {
+                    continue;
+                }
+                // 如果指定了驱动类别且类别不匹配则匹配下一个INF
+                if driveClass.as_ref().is_some_and(|class| !class.eq_ignore_ascii_case(&infInfo.Class)) {
+                    continue;
+                }
+                // 仅匹配适用于目标系统的 Models 节
+                let applicable = applicableList.entry(entryIndex).or_insert_with(|| applicableModels(infInfo, target));
+                if !applicable.contains(&(driverID.Models.as_str(), driverID.Decoration.as_str())) {
+                    continue;
+                }
+                let rank = computeRank(SignatureTier::fromInfInfo(infInfo), driverID.FeatureScore, isCompatibleID, deviceIndex, driverID.Position);
+                matchList.entry(entryIndex).or_default().push((rank, driverIndex));
+            }
         }
 
         // 没有匹配到该设备的驱动信息，则匹配下一个设备
-        if macthList.is_empty() {
+        if matchList.is_empty() {
             continue;
         }
 
+        // 创建匹配信息列表
+        let mut macthList: Vec<InfInfo> = Vec::new();
+        for (entryIndex, mut driverList) in matchList {
+            // 同一硬件id与多个设备id匹配时仅保留等级最小的
+            driverList.sort_by_key(|(rank, _)| *rank);
+            let mut seen: Vec<usize> = Vec::new();
+            driverList.retain(|(_, driverIndex)| {
+                let isNew = !seen.contains(driverIndex);
+                seen.push(*driverIndex);
+                isNew
+            });
+
+            let infInfo = &entries[entryIndex];
+            macthList.push(InfInfo {
+                Rank: driverList[0].0,
+                DriverList: driverList.into_iter().map(|(_, driverIndex)| infInfo.DriverList[driverIndex].clone()).collect(),
+                ..infInfo.clone()
+            });
+        }
+
+        // 排序：等级 > 日期 > 版本
+        macthList.sort_by_cached_key(matchSortKey);
+
         macthInfo.push((idInfo.clone(), macthList));
     }
     macthInfo
 }
+
+/// 匹配驱动的排序键（等级, 日期, 版本）
+type MatchSortKey = (u32, Reverse<Option<(u32, u32, u32)>>, Reverse<Option<Vec<u32>>>);
+
+/// 匹配驱动的排序键：等级 > 日期 > 版本
+pub(crate) fn matchSortKey(infInfo: &InfInfo) -> MatchSortKey {
+    (infInfo.Rank, Reverse(parseDriverDate(&infInfo.Date)), Reverse(versionKey(&infInfo.Version)))
+}
+
+/// 版本号排序键（忽略末尾的 0，无法解析时排在最后）
+fn versionKey(version: &str) -> Option<Vec<u32>> {
+    let mut numbers: Vec<u32> = version.split('.').map(|item| item.trim().parse().ok()).collect::<Option<_>>()?;
+    while numbers.last() == Some(&0) {
+        numbers.pop();
+    }
+    Some(numbers)
+}
+
+/// 获取INF中适用于目标系统的 Models 节
+/// 与 Windows 一致：同一厂商存在多个适用的修饰时，仅选用最具体的一个
+/// # 参数
+/// 1. INF驱动信息
+/// 2. 目标系统
+/// # 返回
+/// (Models 节名称, 修饰) 列表
+fn applicableModels<'a>(infInfo: &'a InfInfo, target: &TargetOS) -> Vec<(&'a str, &'a str)> {
+    let mut bestList: Vec<(&str, &str, TargetOSVersion)> = Vec::new();
+    for driverID in infInfo.DriverList.iter() {
+        let (models, decoration) = (driverID.Models.as_str(), driverID.Decoration.as_str());
+        if bestList.iter().any(|(m, d, _)| *m == models && *d == decoration) {
+            continue;
+        }
+        let Some(targetOSVersion) = TargetOSVersion::parse(decoration) else { continue };
+        if !targetOSVersion.isApplicable(target) {
+            continue;
+        }
+        match bestList.iter_mut().find(|(m, _, _)| *m == models) {
+            Some(best) => {
+                if targetOSVersion.compareSpecificity(&best.2) == Ordering::Greater {
+                    *best = (models, decoration, targetOSVersion);
+                }
+            }
+            None => bestList.push((models, decoration, targetOSVersion)),
+        }
+    }
+    bestList.into_iter().map(|(models, decoration, _)| (models, decoration)).collect()
+}
@@ -1,6 +1,8 @@
 use crate::command::load_driver::loadDriver;
 use crate::i18n::getLocaleText;
 use crate::utils::console::{writeConsole, ConsoleType};
+use crate::utils::deviceBackend::DeviceFilter;
+use crate::utils::signaturePolicy::SignaturePolicy;
 use crate::utils::util::findOfflineSystemDrive;
 use fluent_templates::fluent_bundle::FluentValue;
 use std::collections::HashMap;
@@ -20,7 +22,7 @@ pub fn load_offline_driver(systemDrive: Option<&Path>, isAllDevice: bool, driveC
         }
         let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => systemDrive.to_str().unwrap().into());
         writeConsole(ConsoleType::Info, &getLocaleText("load-offline-driver", Some(&args)));
-        return loadDriver(&driverPath, None, None, false, None, None);
+        return loadDriver(&driverPath, None, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, None, None, None);
     }
 
     // 未指定系统盘，全盘搜索离线系统驱动
@@ -35,7 +37,7 @@ pub fn load_offline_driver(systemDrive: Option<&Path>, isAllDevice: bool, driveC
     for systemDrive in findOfflineSystemDrive() {
         let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => systemDrive.to_str().unwrap().into());
         writeConsole(ConsoleType::Info, &getLocaleText("loading-offline-driver", Some(&args)));
-        loadDriver(&systemDrive, None, None, isAllDevice, driveClass.clone(), None)?;
+        loadDriver(&systemDrive, None, None, &DeviceFilter::new(isAllDevice, Vec::new()), driveClass.clone(), SignaturePolicy::Any, None, None, None)?;
     }
     Ok(())
 }
@@ -0,0 +1,175 @@
+use crate::command::create_index::InfInfo;
+use crate::command::load_driver::{currentDevices, matchSortKey, MatchGroup};
+use crate::i18n::getLocaleText;
+use crate::utils::console::{formatTable, writeConsole, ConsoleType};
+use crate::utils::devcon::HwID;
+use crate::utils::driverRank::{formatRank, isCompatibleMatch};
+use crate::utils::deviceBackend::{openBackend, DeviceFilter};
+use crate::utils::targetOS::TargetOS;
+use fluent_templates::fluent_bundle::FluentValue;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// 匹配报告格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    /// 表格
+    Table,
+    /// JSON
+    Json,
+}
+
+impl ReportFormat {
+    /// 由名称解析报告格式（不区分大小写）
+    pub fn fromName(name: &str) -> Option<ReportFormat> {
+        match name.to_lowercase().as_str() {
+            "table" => Some(ReportFormat::Table),
+            "json" => Some(ReportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// 设备匹配报告
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DeviceReport {
+    /// 设备名称
+    pub(crate) Device: String,
+    /// 设备实例路径
+    pub(crate) InstancePath: String,
+    /// 候选驱动（按驱动等级排列，第一个为将要安装的驱动）
+    pub(crate) Candidates: Vec<CandidateReport>,
+}
+
+/// 候选驱动
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CandidateReport {
+    /// 驱动等级（0xSSGGTHHH）
+    pub(crate) Rank: String,
+    /// 匹配的id
+    pub(crate) MatchedID: String,
+    /// 匹配类型（HardwareID：设备硬件id，CompatibleID：设备兼容id）
+    pub(crate) MatchType: String,
+    /// INF路径（相对于驱动包）
+    pub(crate) Inf: String,
+    /// 驱动版本
+    pub(crate) Version: String,
+    /// 驱动日期
+    pub(crate) Date: String,
+    /// 驱动类别
+    pub(crate) Class: String,
+    /// 驱动包或驱动目录路径
+    pub(crate) Package: String,
+}
+
+/// 预览驱动匹配结果，与加载驱动使用相同的匹配流程，但不解压、不安装驱动
+/// # 参数
+/// 1. 匹配组列表
+/// 2. 设备筛选
+/// 3. 驱动类别
+/// 4. 报告格式
+/// 5. 硬件清单路径（为 None 时使用当前设备）
+pub fn matchDriver(groups: &[MatchGroup], filter: &DeviceFilter, driveClass: Option<String>, format: ReportFormat, inventoryPath: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let backend = openBackend(inventoryPath)?;
+    let hwIDList = currentDevices(backend.as_ref(), filter)?;
+    if hwIDList.is_empty() {
+        writeConsole(ConsoleType::Err, &getLocaleText("no-found-driver-currently", None));
+        return Err(getLocaleText("no-found-driver-currently", None).into());
+    }
+    let report = matchReport(groups, &hwIDList, driveClass, &backend.target().unwrap_or_else(TargetOS::current));
+
+    match format {
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        ReportFormat::Table if report.is_empty() => writeConsole(ConsoleType::Warning, &getLocaleText("no-found-driver-currently", None)),
+        ReportFormat::Table => {
+            for device in report.iter() {
+                let args: HashMap<String, FluentValue> = hash_map!(
+                    "deviceName".to_string() => device.Device.clone().into(),
+                    "path".to_string() => device.InstancePath.clone().into(),
+                );
+                writeConsole(ConsoleType::Info, &getLocaleText("report-device", Some(&args)));
+                for line in reportTable(device) {
+                    println!("    {}", line);
+                }
+                println!();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 生成匹配报告
+/// 合并各个匹配组中的候选驱动，并按与加载驱动相同的规则排序
+/// # 参数
+/// 1. 匹配组列表
+/// 2. 硬件ID列表
+/// 3. 驱动类别
+/// 4. 目标系统
+pub(crate) fn matchReport(groups: &[MatchGroup], hwIDList: &[HwID], driveClass: Option<String>, target: &TargetOS) -> Vec<DeviceReport> {
+    let mut report: Vec<DeviceReport> = Vec::new();
+    for hwID in hwIDList.iter() {
+        let mut candidates: Vec<(InfInfo, PathBuf)> = Vec::new();
+        for group in groups.iter() {
+            for (_, infInfo) in group.matchDevices(std::slice::from_ref(hwID), driveClass.clone(), target) {
+                for item in infInfo {
+                    let packagePath = group.source(&item).map(|source| source.packagePath.clone()).unwrap_or_default();
+                    candidates.push((item, packagePath));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            continue;
+        }
+        candidates.sort_by_cached_key(|(item, _)| matchSortKey(item));
+
+        report.push(DeviceReport {
+            Device: hwID.Name.clone(),
+            InstancePath: hwID.DeviceInstancePath.clone(),
+            Candidates: candidates
+                .into_iter()
+                .map(|(item, packagePath)| CandidateReport {
+                    Rank: formatRank(item.Rank),
+                    MatchedID: item.DriverList.first().map(|driverID| driverID.Id.clone()).unwrap_or_default(),
+                    MatchType: if isCompatibleMatch(item.Rank) { "CompatibleID" } else { "HardwareID" }.to_string(),
+                    Inf: Path::new(&item.Path).join(&item.Inf).to_string_lossy().to_string(),
+                    Version: item.Version,
+                    Date: item.Date,
+                    Class: item.Class,
+                    Package: packagePath.to_string_lossy().to_string(),
+                })
+                .collect(),
+        });
+    }
+    report
+}
+
+/// 以表格形式显示设备的候选驱动
+/// # 参数
+/// 1. 设备匹配报告
+/// # 返回
+/// 表格各行（含表头）
+pub(crate) fn reportTable(device: &DeviceReport) -> Vec<String> {
+    let mut rows: Vec<Vec<String>> = vec![
+        ["report-rank", "report-matched-id", "report-match-type", "report-inf", "report-version", "report-date", "report-class", "report-package"]
+            .iter()
+            .map(|id| getLocaleText(id, None))
+            .collect(),
+    ];
+    for item in device.Candidates.iter() {
+        let matchType = if item.MatchType == "CompatibleID" { "report-compatible-id" } else { "report-hardware-id" };
+        rows.push(vec![
+            item.Rank.clone(),
+            item.MatchedID.clone(),
+            getLocaleText(matchType, None),
+            item.Inf.clone(),
+            item.Version.clone(),
+            item.Date.clone(),
+            item.Class.clone(),
+            item.Package.clone(),
+        ]);
+    }
+
+    formatTable(&rows)
+}
@@ -0,0 +1,39 @@
+use crate::command::create_index::{IndexFile, IndexFormat};
+use crate::i18n::getLocaleText;
+use crate::utils::console::{writeConsole, ConsoleType};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// 合并多个驱动包的索引
+/// # 参数
+/// 1. 索引文件列表（单个驱动包的索引或合并索引）
+/// 2. 合并索引保存路径
+/// 3. 索引格式
+pub fn mergeIndex(indexList: &[PathBuf], savePath: &Path, format: IndexFormat) -> Result<(), Box<dyn Error>> {
+    if indexList.is_empty() {
+        return Err(getLocaleText("no-index-find", None).into());
+    }
+
+    let mut indexFiles: Vec<IndexFile> = Vec::new();
+    for indexPath in indexList.iter() {
+        let args: HashMap<String, FluentValue> = hash_map!("path".to_string() => indexPath.to_str().unwrap().into());
+        writeConsole(ConsoleType::Info, &getLocaleText("load-index", Some(&args)));
+        indexFiles.push(IndexFile::load(indexPath)?);
+    }
+
+    let merged = IndexFile::merge(indexFiles)?;
+    if let Err(_e) = merged.save(savePath, format) {
+        return Err(getLocaleText("index-save-failed", None).into());
+    }
+
+    let arg: HashMap<String, FluentValue> = hash_map!(
+        "count".to_string() => merged.Packages.len().into(),
+        "total".to_string() => merged.Entries.len().into(),
+    );
+    writeConsole(ConsoleType::Info, &getLocaleText("merge-info", Some(&arg)));
+    let arg: HashMap<String, FluentValue> = hash_map!("path".to_string() => savePath.to_str().unwrap().into());
+    writeConsole(ConsoleType::Success, &getLocaleText("saveInfo", Some(&arg)));
+    Ok(())
+}
@@ -1,8 +1,13 @@
 pub mod create_index;
+pub mod merge_index;
+pub mod split_index;
 pub mod load_driver;
+pub mod match_driver;
+pub mod inventory;
 pub mod load_offline_driver;
 pub mod import_driver;
 pub mod remove_driver;
 pub mod classify_driver;
 pub mod create_driver;
 pub mod export_driver;
+pub mod cache;
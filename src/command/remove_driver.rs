@@ -1,5 +1,6 @@
 use crate::i18n::getLocaleText;
 use crate::utils::console::{writeConsole, ConsoleType};
+use crate::utils::driverDatabase::DriverDatabase;
 use crate::utils::drvstoreAPI::DriverStore;
 use crate::utils::util::{getArchCode, getFileList, isOfflineSystem};
 use fluent_templates::fluent_bundle::FluentValue;
@@ -7,9 +8,17 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
-pub fn remove_driver(systemDrive: &Path, driveName: Option<&str>, class: Option<&str>) -> Result<(), Box<dyn Error>> {
+pub fn remove_driver(systemDrive: &Path, driveName: Option<&str>, class: Option<&str>, unused: bool) -> Result<(), Box<dyn Error>> {
     let systemRoot = systemDrive.join("Windows");
 
+    // 仅删除未被任何设备使用的第三方驱动
+    let unusedList: Option<Vec<String>> = if unused {
+        let database = DriverDatabase::open(systemDrive)?;
+        Some(database.unusedPackages().iter().map(|package| package.PublishedName.to_lowercase()).collect())
+    } else {
+        None
+    };
+
     // 获取系统架构
     let archCode = match getArchCode(systemDrive) {
         Ok(code @ (0x014c | 0x8664 | 0xAA64)) => code,
@@ -34,6 +43,11 @@ pub fn remove_driver(systemDrive: &Path, driveName: Option<&str>, class: Option<
         let handle = driverStore.open_store(&systemRoot, systemDrive)?;
 
         for item in getFileList(&*systemRoot.join("INF"), "oem*.inf")? {
+            if let Some(unusedList) = &unusedList {
+                if !unusedList.contains(&item.file_name().unwrap_or_default().to_string_lossy().to_lowercase()) {
+                    continue;
+                }
+            }
             if let Some((path, _info_opt)) = driverStore.find_driver_package(handle, &item, arch) {
                 let inf_path = PathBuf::from(&path);
 
@@ -0,0 +1,34 @@
+use crate::command::create_index::{IndexFile, IndexFormat};
+use crate::i18n::getLocaleText;
+use crate::utils::binaryIndex::BinaryIndex;
+use crate::utils::console::{writeConsole, ConsoleType};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// 拆分合并索引为各个驱动包的索引（驱动包名.index）
+/// # 参数
+/// 1. 合并索引路径
+/// 2. 拆分索引保存目录
+/// 3. 索引格式（为 None 时沿用合并索引的格式）
+pub fn splitIndex(indexPath: &Path, saveDir: &Path, format: Option<IndexFormat>) -> Result<(), Box<dyn Error>> {
+    let format = format.unwrap_or(if BinaryIndex::isBinaryIndex(indexPath) { IndexFormat::Binary } else { IndexFormat::Json });
+    let index = IndexFile::load(indexPath)?;
+    if !index.isMerged() {
+        return Err(getLocaleText("split-index-not-merged", None).into());
+    }
+
+    fs::create_dir_all(saveDir)?;
+    for item in index.split() {
+        let fileName = item.Package.as_ref().map(|package| package.FileName.clone()).unwrap_or_default();
+        let savePath = saveDir.join(format!("{}.index", Path::new(&fileName).file_stem().unwrap_or_default().to_string_lossy()));
+        if let Err(_e) = item.save(&savePath, format) {
+            return Err(getLocaleText("index-save-failed", None).into());
+        }
+        let arg: HashMap<String, FluentValue> = hash_map!("path".to_string() => savePath.to_str().unwrap().into());
+        writeConsole(ConsoleType::Success, &getLocaleText("saveInfo", Some(&arg)));
+    }
+    Ok(())
+}
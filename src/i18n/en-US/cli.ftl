@@ -24,8 +24,19 @@ opened-debug = Debug mode is open. The log is kept at { $path }
 # subcommand
 
 ## create-index
-create-index = Create a driver index. Index format: JSON
+create-index = Create a driver index. Index format: JSON or binary
 save-index-path = Index file save location
+index-format = Index file format: json (default), binary
+update-index = Update the existing index, only reparse new or changed INF files
+
+## merge-index
+merge-index = Merge the indexes of multiple driver packages, so that loading drivers only needs to match once
+merge-index-path = Index file path, supports wildcards
+merge-save-path = Merged index file save location
+
+## split-index
+split-index = Split a merged index into the index of each driver package
+split-save-path = Directory where the split indexes are saved, defaults to the directory of the merged index
 
 ## load-driver
 load-driver = Install the matching driver. Automatically match the driver in the compressed package, decompress and install
@@ -33,24 +44,32 @@ package-path = Compressed package path
 index-path = index file path
 package-password = Compressed package password
 match-all-device = Match all device
+problem-code = Only match devices with the specified problem codes, separated by commas, e.g. 28,1,10 (28: no driver installed)
 driver-category = Set the install driver category
 only-unzip = Only unzip the driver without installing
 offline-import = Offline import driver
 eject-driver-cd = Eject virtual CD-ROM to detect the actual USB device.
 
+## match-driver
+match-driver = Preview the driver match result and show the candidate drivers of each device, without decompressing or installing drivers
+report-format = Report format: table (default), json
+
 ## load-offline-driver
 load-offline-driver = Load offline system driver
 
 ## import-driver
 import-driver = Import drivers, supports online and offline systems
-match-device = Match the native device
+match-device = Match the native device (the devices recorded in the registry when importing into an offline system)
 
 ## export-driver
 export-driver = Export drivers, supports online and offline systems
 driver-name = Driver name
+driver-list = List the drivers in the driver store (including inbox drivers) instead of exporting them
+driver-in-use = Only the drivers used by a device (offline Windows 10 and later systems)
 
 ## remove-driver
 remove-driver = Remove drivers, supports online and offline systems
+driver-unused = Only the third-party drivers not used by any device (offline Windows 10 and later systems)
 
 ## classify-driver
 classify-driver = sort out the driver
@@ -60,6 +79,18 @@ rename-driver = Rename the driver's parent directory
 create-driver = Create a driver package. Packages are packaged with programs and drivers for easy distribution
 driver-package-program-path = Driver package program path
 
+## inventory
+inventory = Save the hardware inventory of this computer, used to match drivers on other computers
+inventory-save-path = Hardware inventory save location
+inventory-path = Hardware inventory path, match the devices in the inventory instead of the devices of this computer
+cache-path = Extraction cache directory, extracted drivers are kept there and reused by later runs
+cache-size = Size limit of the extraction cache in MB (default 4096), the least recently used drivers are removed first
+signature-policy = Signature policy: whql (only WHQL drivers signed for the target system version), signed (WHQL or third-party signed drivers), any (default)
+
+## cache
+cache = List or purge the extraction cache of load-driver
+cache-purge = Remove all cached drivers
+
 ## scan-devices
 scan-devices = Scan devices
 scan-devices-success = Scan devices success
@@ -69,4 +100,9 @@ scan-devices-failed = Scan devices failed
 path-not-exist = The path does not exist, please make sure the entered directory exists
 dir-not-exist = The directory does not exist, please make sure the entered directory exists
 not-driver-category = The driver category is incorrect, please enter the correct driver category
+not-index-format = The index format is incorrect, please enter json or binary
+not-report-format = The report format is incorrect, please enter table or json
+not-problem-code = The problem code is incorrect, please enter numbers separated by commas, e.g. 28,1,10
+not-cache-size = The cache size is incorrect, please enter a positive number of MB, e.g. 4096
+not-signature-policy = The signature policy is incorrect, please enter whql, signed or any
 not-system-path = The system disk is invalid, make sure that the drive letter you entered exists in the operating system
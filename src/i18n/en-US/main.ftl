@@ -10,17 +10,36 @@ temp-remove-failed=Temporary directory deletion failed
 processing = In process, please wait……
 no-hardware = The hardware id in this file is not detected: { $path }
 inf-parsing-err = INF parsing error: { $path }
+driver-files-missing = Driver files are missing or empty: { $path } ({ $files })
+driver-signature-tampered = Driver files do not match the catalog: { $path }
+driver-signature-mismatch = Driver files do not match the catalog: { $path } ({ $files })
+driver-policy-rejected = Driver rejected by the signature policy: { $path } ({ $reason })
+policy-unsigned = not signed
+policy-tampered = driver files do not match the catalog
+policy-unverified = the signature or certificate chain cannot be verified, signer: { $signer }
+policy-not-whql = not WHQL signed, signer: { $signer }
+policy-unsupported-os = the WHQL signature does not cover Windows { $version }, OSAttr: { $osAttr }
 index-save-failed = Failed to save index file
 no-inf-find = No driver information found
 total-info = Total {$total} items，Processed {$success} items，{$error} items failed to process，{$blankCount} items may not have hardware id information
+update-info = Added {$added} items，changed {$changed} items，removed {$removed} items，{$unchanged} items unchanged
 saveInfo = The drive index is saved in { $path }
 
+# Merge and split index
+merge-index-no-package = The index was not created from a driver package and cannot be merged
+merge-info = Merged {$count} driver packages，total {$total} items
+split-index-not-merged = The index is not a merged index
+no-index-find = No index file was found
+
 # load-driver
 load-driver-package = Load driver package: { $path }
 no-device = Failed to obtain hardware information
 load-index = load index: {$path}
 unzip-index-failed = Failed to unzip the index file, please confirm whether the index file exists in the compressed package
 index-parsing-failed = Index file parsing failed, Please regenerate the index file
+index-version-unsupported = Unsupported index format version: { $version }, Please regenerate the index file
+index-package-mismatch = The index file does not match the driver package ({ $package }), Please regenerate the index file
+index-password-required = The driver package of this index requires a password
 no-driver-package = No driver detected in the driver package
 no-found-driver-currently = Cannot find the device that currently needs to install the driver
 install-message =
@@ -31,8 +50,24 @@ driver-unzip-failed = Driver unzip failed
 driver-unzip-success = Driver unzip successfully
 driver-install-failed = Driver installation failed
 driver-install-success = Driver installed successfully
+driver-extract-time = Extracted { $count } driver directories from { $path } in { $time } seconds
+driver-install-time = Installed drivers for { $count } devices in { $time } seconds
+driver-cache-hit = Reused { $count } cached driver directories of { $path }
 ejecting-driver-cd = Eject virtual optical drive without drive device ({ $drive })
 
+# match-driver
+report-device = Device: { $deviceName } ({ $path })
+report-rank = Rank
+report-matched-id = Matched ID
+report-match-type = Match type
+report-inf = INF
+report-version = Version
+report-date = Date
+report-class = Class
+report-package = Package
+report-hardware-id = Hardware ID
+report-compatible-id = Compatible ID
+
 # load-offline-driver
 loading-offline-driver = Load offline system driver ({ $path })
 not-found-offline-system = No offline systems found
@@ -46,6 +81,11 @@ driver-import-summary = Processed { $total } drivers, success { $success }, fail
 # export-driver
 driver-export-success = Driver export successfully
 driver-export-failed = Driver export failed
+driver-list-published-name = Published name
+driver-list-provider = Provider
+driver-list-size = Size
+driver-list-devices = Devices
+driver-list-summary = { $count } driver packages, { $oem } of them third-party
 
 # remove-driver
 driver-remove-success = Driver remove successfully
@@ -59,6 +99,26 @@ Drivers-finishing-failed = Drivers finishing failed
 Driver-finishing-create = Driver package program created successfully
 Pack-Driver-failed = Driver package creation failed
 
+# inventory
+inventory-saved = Saved { $count } devices to the hardware inventory: { $path }
+inventory-save-failed = Failed to save hardware inventory
+inventory-parsing-failed = Failed to parse the hardware inventory
+inventory-version-unsupported = Unsupported hardware inventory version: { $version }, Please save the hardware inventory again
+
+# cache
+cache-list-path = Directory
+cache-list-last-used = Last used
+cache-list-summary = { $count } cached driver directories, { $size } in total
+cache-empty = The extraction cache is empty
+cache-purged = The extraction cache has been purged: { $path }
+
+# registry hive
+hive-parsing-failed = Failed to read the registry hive: { $path }
+
+# archive
+archive-parsing-failed = Failed to read the archive: { $path }
+archive-format-unsupported = Unsupported archive format: { $path }
+
 # Drive category
 # ADAPTER = ADAPTER
 # BATTERY = BATTERY
@@ -24,8 +24,19 @@ opened-debug = 调试模式已打开，日志保存在 { $path }
 # 子命令
 
 ## create-index
-create-index = 创建驱动索引。索引格式：JSON
+create-index = 创建驱动索引。索引格式：JSON 或二进制
 save-index-path = 索引文件保存位置
+index-format = 索引文件格式：json（默认）、binary
+update-index = 增量更新已有索引，仅重新解析新增或变更的INF文件
+
+## merge-index
+merge-index = 合并多个驱动包的索引，加载驱动时只需匹配一次
+merge-index-path = 索引文件路径，支持通配符
+merge-save-path = 合并索引保存位置
+
+## split-index
+split-index = 将合并索引拆分为各个驱动包的索引
+split-save-path = 拆分索引保存目录，默认为合并索引所在目录
 
 ## load-driver
 load-driver = 安装匹配驱动。自动匹配压缩包中的驱动程序，解压并安装
@@ -33,24 +44,32 @@ package-path = 压缩包路径
 index-path = 索引文件路径
 package-password = 设置压缩包密码
 match-all-device = 匹配所有设备
+problem-code = 仅匹配指定问题代码的设备，以逗号分隔，如 28,1,10（28：未安装驱动）
 driver-category = 设置安装的驱动程序类别
 only-unzip = 仅解压驱动程序而不安装
 offline-import = 离线导入驱动
 eject-driver-cd = 弹出免驱设备虚拟光驱以识别真实USB设备
 
+## match-driver
+match-driver = 预览驱动匹配结果，显示每个设备的候选驱动，不解压、不安装驱动
+report-format = 报告格式：table（默认）、json
+
 ## load-offline-driver
 load-offline-driver = 加载离线系统驱动
 
 ## import-driver
 import-driver = 导入驱动。支持在线系统、离线系统
-match-device = 匹配本机设备
+match-device = 匹配本机设备（导入离线系统时匹配离线系统注册表中记录的设备）
 
 ## export-driver
 export-driver = 导出驱动。支持在线系统、离线系统
 driver-name = 驱动名称
+driver-list = 列出驱动库中的驱动（包括系统自带驱动），不导出
+driver-in-use = 仅正在被设备使用的驱动（Windows 10 及以上版本的离线系统）
 
 ## remove-driver
 remove-driver = 删除驱动。支持在线系统、离线系统
+driver-unused = 仅未被任何设备使用的第三方驱动（Windows 10 及以上版本的离线系统）
 
 ## classify-driver
 classify-driver = 整理驱动程序
@@ -60,6 +79,18 @@ rename-driver = 重命名驱动父目录
 create-driver = 创建驱动包程序。打包程序与驱动包，便于分发
 driver-package-program-path = 驱动包程序路径
 
+## inventory
+inventory = 保存本机的硬件清单，用于在其他计算机上匹配驱动
+inventory-save-path = 硬件清单保存位置
+inventory-path = 硬件清单路径，匹配清单中的设备而非本机设备
+cache-path = 解压缓存目录，解压的驱动保留在此目录中供之后的加载使用
+cache-size = 解压缓存大小上限（MB，默认 4096），超出时优先删除最久未使用的驱动
+signature-policy = 签名策略：whql（仅适用于目标系统版本的 WHQL 驱动）、signed（WHQL 或第三方签名的驱动）、any（默认）
+
+## 解压缓存
+cache = 列出或清空加载驱动的解压缓存
+cache-purge = 删除全部缓存的驱动
+
 ## scan-devices
 scan-devices = 扫描硬件设备更改
 scan-devices-success = 扫描硬件设备更改成功
@@ -69,4 +100,9 @@ scan-devices-failed = 扫描硬件设备更改失败
 path-not-exist = 路径不存在，请确保输入的目录存在
 dir-not-exist = 目录不存在，请确保输入的目录存在
 not-driver-category = 驱动程序类别不正确，请输入正确的驱动程序类别
+not-index-format = 索引格式不正确，请输入 json 或 binary
+not-report-format = 报告格式不正确，请输入 table 或 json
+not-problem-code = 问题代码不正确，请输入以逗号分隔的数字，如 28,1,10
+not-cache-size = 缓存大小不正确，请输入以 MB 为单位的正整数，例如 4096
+not-signature-policy = 签名策略不正确，请输入 whql、signed 或 any
 not-system-path = 无效的系统盘，请确保输入的盘符存在操作系统
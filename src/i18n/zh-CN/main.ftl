@@ -10,17 +10,36 @@ temp-remove-failed=临时目录删除失败
 processing = 正在处理中，请稍候······
 no-hardware = 未检测到此文件中的硬件ID: { $path }
 inf-parsing-err = INF解析错误: { $path }
+driver-files-missing = 驱动文件缺失或为空: { $path } ({ $files })
+driver-signature-tampered = 驱动文件与编录文件不一致: { $path }
+driver-signature-mismatch = 驱动文件与编录文件不一致: { $path } ({ $files })
+driver-policy-rejected = 驱动不符合签名策略: { $path } ({ $reason })
+policy-unsigned = 未签名
+policy-tampered = 驱动文件与编录文件不一致
+policy-unverified = 签名或证书链无法验证，签名者: { $signer }
+policy-not-whql = 非 WHQL 签名，签名者: { $signer }
+policy-unsupported-os = WHQL 签名不适用于 Windows { $version }，OSAttr: { $osAttr }
 index-save-failed = 索引文件保存失败
 no-inf-find = 没有找到驱动信息
 total-info = 总 {$total} 个项目，已处理 {$success} 个项目，{$error} 个项目无法处理，{$blankCount} 个项目可能没有硬件ID信息
+update-info = 新增 {$added} 个项目，变更 {$changed} 个项目，删除 {$removed} 个项目，{$unchanged} 个项目未变更
 saveInfo = 驱动索引保存在 { $path }
 
+# 合并、拆分索引
+merge-index-no-package = 该索引不是从驱动包创建的，无法合并
+merge-info = 已合并 {$count} 个驱动包，共 {$total} 个项目
+split-index-not-merged = 该索引不是合并索引
+no-index-find = 没有找到索引文件
+
 # 加载驱动
 load-driver-package = 加载驱动包: { $path }
 no-device = 获取硬件信息失败
 load-index = 加载索引: {$path}
 unzip-index-failed = 无法解压索引文件，请确认压缩包中是否存在索引文件
 index-parsing-failed = 索引文件解析失败，请重新生成索引文件
+index-version-unsupported = 不支持的索引格式版本: { $version }，请重新生成索引文件
+index-package-mismatch = 索引文件与驱动包（{ $package }）不一致，请重新生成索引文件
+index-password-required = 此索引对应的驱动包需要密码
 no-driver-package = 在驱动程序包中未检测到驱动程序
 no-found-driver-currently = 找不到当前需要安装驱动的设备
 install-message =
@@ -31,8 +50,24 @@ driver-unzip-failed = 驱动程序解压失败
 driver-unzip-success = 驱动程序解压成功
 driver-install-failed = 驱动程序安装失败
 driver-install-success = 驱动程序安装成功
+driver-extract-time = 已从 { $path } 解压 { $count } 个驱动目录，用时 { $time } 秒
+driver-install-time = 已为 { $count } 个设备安装驱动，用时 { $time } 秒
+driver-cache-hit = 已复用 { $path } 的 { $count } 个已缓存驱动目录
 ejecting-driver-cd = 弹出免驱设备虚拟光驱 ({ $drive })
 
+# 预览匹配结果
+report-device = 设备: { $deviceName } ({ $path })
+report-rank = 等级
+report-matched-id = 匹配的ID
+report-match-type = 匹配类型
+report-inf = INF
+report-version = 版本
+report-date = 日期
+report-class = 类别
+report-package = 驱动包
+report-hardware-id = 硬件ID
+report-compatible-id = 兼容ID
+
 # 加载离线驱动
 loading-offline-driver = 加载离线系统驱动 ({ $path })
 not-found-offline-system = 没有找到离线系统
@@ -46,6 +81,11 @@ driver-import-summary = 共处理 { $total } 个驱动，成功 { $success }，
 # 导出驱动
 driver-export-success = 驱动导出成功
 driver-export-failed = 驱动导出失败
+driver-list-published-name = 发布名称
+driver-list-provider = 提供商
+driver-list-size = 大小
+driver-list-devices = 设备数
+driver-list-summary = 共 { $count } 个驱动包，其中第三方驱动 { $oem } 个
 
 ## 删除驱动
 driver-remove = 删除驱动: { $inf }
@@ -60,6 +100,26 @@ Drivers-finishing-failed = 驱动整理失败
 Driver-finishing-create = 驱动包程序创建成功
 Pack-Driver-failed = 创建驱动包程序失败
 
+# 硬件清单
+inventory-saved = 已保存 { $count } 个设备到硬件清单：{ $path }
+inventory-save-failed = 硬件清单保存失败
+inventory-parsing-failed = 硬件清单解析失败
+inventory-version-unsupported = 不支持的硬件清单版本: { $version }，请重新保存硬件清单
+
+# 解压缓存
+cache-list-path = 目录
+cache-list-last-used = 最近使用
+cache-list-summary = 共缓存 { $count } 个驱动目录，总计 { $size }
+cache-empty = 解压缓存为空
+cache-purged = 已清空解压缓存: { $path }
+
+# 注册表配置单元
+hive-parsing-failed = 注册表配置单元读取失败：{ $path }
+
+# archive
+archive-parsing-failed = 压缩包读取失败：{ $path }
+archive-format-unsupported = 不支持的压缩包格式：{ $path }
+
 # 驱动类别
 # ADAPTER = 适配器
 # BATTERY = 电池
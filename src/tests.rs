@@ -6,6 +6,7 @@ mod Tests {
     use crate::command::create_index::InfInfo;
     use crate::utils::devcon::Devcon;
     use crate::utils::drvstoreAPI::DriverStore;
+    use crate::utils::hardwareIdIndex::HardwareIdIndex;
     use crate::utils::setupAPI;
     use crate::utils::setupAPI::get_class_description;
     use crate::utils::sevenZIP::sevenZip;
@@ -19,6 +20,7 @@ mod Tests {
     // 文件解压测试
     #[test]
     fn unzipTest() {
+        use crate::utils::archiveBackend::ArchiveBackend;
         use crate::utils::sevenZIP::sevenZip;
 
         let basePath =
@@ -26,7 +28,7 @@ mod Tests {
         let outPath = PathBuf::from(r"C:\Users\Administrator.W10-20201229857\Desktop\outPath");
 
         let zip = sevenZip::new().unwrap();
-        println!("{:?}", zip.extractFilesFromPath(&basePath, None, "", &outPath));
+        println!("{:?}", zip.extractPattern(&basePath, None, "", &outPath));
     }
 
     // 文件遍历测试
@@ -88,10 +90,1866 @@ mod Tests {
 
         println!(
             "{:?}",
-            InfInfo::parsingInfFile(&infPath.parent().unwrap(), &infPath).unwrap()
+            InfInfo::parsingInfFile(&infPath.parent().unwrap(), &infPath, true).unwrap()
         );
     }
 
+    // INF语法解析测试
+    #[test]
+    fn infParserTest() {
+        use crate::utils::infParser::InfFile;
+
+        let inf = InfFile::parse(concat!(
+            "; 文件头注释\r\n",
+            "[Version]\r\n",
+            "Signature = \"$Windows NT$\"\r\n",
+            "Class      = Net ; 行尾注释\r\n",
+            "DriverVer  = 07/15/2021, 1.2.3.4\r\n",
+            "[Install]\r\n",
+            "AddReg = Reg1, \\\r\n",
+            "         Reg2\r\n",
+            "Desc = \"A;B, \"\"C\"\"\" , %Unknown%, 100%%\r\n",
+            "[Version]\r\n",
+            "Provider = %Mfg%\r\n",
+            "[Strings]\r\n",
+            "Mfg = \"Realtek Semiconductor\"\r\n",
+        ));
+
+        assert_eq!(inf.getValue("version", "class"), Some("Net"));
+        assert_eq!(inf.section("Version").unwrap().getValues("DriverVer").unwrap(), ["07/15/2021", "1.2.3.4"]);
+        // 重复的节合并，%token% 替换
+        assert_eq!(inf.getValue("Version", "Provider"), Some("Realtek Semiconductor"));
+        // 续行
+        assert_eq!(inf.section("Install").unwrap().getValues("AddReg").unwrap(), ["Reg1", "Reg2"]);
+        // 引号内的分号、逗号与转义引号，未定义的 token 保持不变
+        assert_eq!(inf.section("Install").unwrap().getValues("Desc").unwrap(), ["A;B, \"C\"", "%Unknown%", "100%"]);
+        assert_eq!(inf.getString("mfg"), Some("Realtek Semiconductor"));
+    }
+
+    // INF驱动信息提取测试
+    #[test]
+    fn infInfoFromManufacturerTest() {
+        use crate::command::create_index::InfInfo;
+        use crate::utils::infParser::InfFile;
+
+        let inf = InfFile::parse(concat!(
+            "[Version]\n",
+            "Class = Net\n",
+            "DriverVer = %DrvDate%, 2.0.0.1\n",
+            "[Manufacturer]\n",
+            "%Mfg% = Realtek, NTamd64, NTarm64.10.0...16299\n",
+            "[Realtek.NTamd64]\n",
+            "%Dev% = Install, USB\\VID_0BDA&PID_B711&REV_0200, USB\\VID_0BDA&PID_B711\n",
+            "[Realtek.NTarm64.10.0...16299]\n",
+            "%Dev% = Install, usb\\vid_0bda&pid_b711\n",
+            "[Install]\n",
+            "CopyFiles = Copy\n",
+            "[Copy]\n",
+            "rtl8188gu.sys, , , 0x4000\n",
+            "[Strings]\n",
+            "DrvDate = 07/15/2021\n",
+            "Dev = \"USB\\VID_FFFF&PID_FFFF, Wireless\"\n",
+        ));
+
+        let info = InfInfo::fromInf("Net".to_string(), "netrtl8188gu.inf".to_string(), &inf);
+        assert_eq!(info.Class, "Net");
+        assert_eq!(info.Date, "07/15/2021");
+        assert_eq!(info.Version, "2.0.0.1");
+        assert_eq!(info.Arch, ["NTamd64", "NTarm64"]);
+        // [Strings] 与其它节中的内容不会被当作硬件id
+        let idList: Vec<(&str, &str)> = info.DriverList.iter().map(|item| (item.Id.as_str(), item.Decoration.as_str())).collect();
+        assert_eq!(idList, [
+            (r"USB\VID_0BDA&PID_B711&REV_0200", "NTamd64"),
+            (r"USB\VID_0BDA&PID_B711", "NTamd64"),
+            (r"USB\VID_0BDA&PID_B711", "NTarm64.10.0...16299"),
+        ]);
+    }
+
+    // INF修饰解析测试
+    #[test]
+    fn targetOSVersionTest() {
+        use crate::utils::targetOS::{TargetOS, TargetOSVersion};
+
+        let decoration = TargetOSVersion::parse("NTamd64.10.0.0x1..16299").unwrap();
+        assert_eq!(decoration.Arch.as_deref(), Some("NTamd64"));
+        assert_eq!((decoration.Major, decoration.Minor, decoration.ProductType, decoration.SuiteMask, decoration.Build), (Some(10), Some(0), Some(1), None, Some(16299)));
+        assert!(TargetOSVersion::parse("Win7").is_none());
+
+        let win7 = TargetOS { Arch: "NTamd64".to_string(), Major: 6, Minor: 1, ProductType: 1, SuiteMask: 0x100, Build: 7601 };
+        let win10 = TargetOS { Major: 10, Minor: 0, Build: 19045, ..win7.clone() };
+        assert!(!decoration.isApplicable(&win7));
+        assert!(decoration.isApplicable(&win10));
+        assert!(!TargetOSVersion::parse("NTamd64...3").unwrap().isApplicable(&win10));
+        assert!(TargetOSVersion::parse("NTamd64....0x100").unwrap().isApplicable(&win7));
+        // 未修饰及未指定架构的节仅适用于 x86
+        assert!(!TargetOSVersion::parse("").unwrap().isApplicable(&win10));
+        assert!(TargetOSVersion::parse("NT.6.1").unwrap().isApplicable(&TargetOS { Arch: "NTx86".to_string(), ..win7.clone() }));
+    }
+
+    // 按目标系统选择 Models 节测试
+    #[test]
+    fn matchDecorationTest() {
+        use crate::command::create_index::InfInfo;
+        use crate::command::load_driver::getMatchInfo;
+        use crate::utils::devcon::HwID;
+        use crate::utils::infParser::InfFile;
+        use crate::utils::targetOS::TargetOS;
+
+        let inf = InfFile::parse(concat!(
+            "[Manufacturer]\n",
+            "%Mfg% = Intel, NTamd64.6.1, NTamd64.10.0...17763\n",
+            "[Intel.NTamd64.6.1]\n",
+            "%Win7% = Install.Win7, PCI\\VEN_8086&DEV_15B8\n",
+            "[Intel.NTamd64.10.0...17763]\n",
+            "%Win10% = Install.Win10, PCI\\VEN_8086&DEV_15B8\n",
+        ));
+        let infInfoList = vec![InfInfo::fromInf("Lan".to_string(), "e1d.inf".to_string(), &inf)];
+        let hwIdList = vec![HwID {
+            DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8\3&11583659&0&FE".to_string(),
+            Name: "Ethernet Controller".to_string(),
+            HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8".to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        }];
+
+        let win7 = TargetOS { Arch: "NTamd64".to_string(), Major: 6, Minor: 1, ProductType: 1, SuiteMask: 0, Build: 7601 };
+        let win10 = TargetOS { Major: 10, Minor: 0, Build: 19045, ..win7.clone() };
+        let arm64 = TargetOS { Arch: "NTarm64".to_string(), ..win10.clone() };
+
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &win7);
+        assert_eq!(matchInfo[0].1[0].DriverList[0].Decoration, "NTamd64.6.1");
+        // 两个修饰都适用时选用最具体的
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &win10);
+        assert_eq!(matchInfo[0].1.len(), 1);
+        assert_eq!(matchInfo[0].1[0].DriverList[0].Decoration, "NTamd64.10.0...17763");
+        assert!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &arm64).is_empty());
+    }
+
+    // ARM64 驱动匹配测试
+    #[test]
+    fn matchArm64Test() {
+        use crate::command::create_index::InfInfo;
+        use crate::command::load_driver::getMatchInfo;
+        use crate::utils::devcon::HwID;
+        use crate::utils::infParser::InfFile;
+        use crate::utils::targetOS::TargetOS;
+
+        assert_eq!(TargetOS::archFromMachine(0x014c), Some("NTx86"));
+        assert_eq!(TargetOS::archFromMachine(0x8664), Some("NTamd64"));
+        assert_eq!(TargetOS::archFromMachine(0xAA64), Some("NTarm64"));
+        assert_eq!(TargetOS::archFromMachine(0x0200), None);
+
+        let inf = InfFile::parse(concat!(
+            "[Manufacturer]\n",
+            "%Mfg% = Qcom, NTamd64, NTarm64.10.0...22000\n",
+            "[Qcom.NTamd64]\n",
+            "%Dev% = Install, ACPI\\QCOM0C5A\n",
+            "[Qcom.NTarm64.10.0...22000]\n",
+            "%Dev% = Install, ACPI\\QCOM0C5A\n",
+        ));
+        let infInfoList = vec![InfInfo::fromInf("Qcom".to_string(), "qcom.inf".to_string(), &inf)];
+        assert_eq!(infInfoList[0].Arch, vec!["NTamd64", "NTarm64"]);
+        let hwIdList = vec![HwID {
+            DeviceInstancePath: r"ACPI\QCOM0C5A\0".to_string(),
+            Name: "Qualcomm Device".to_string(),
+            HardwareIDs: vec![r"ACPI\QCOM0C5A".to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        }];
+
+        let arm64 = TargetOS { Arch: "NTarm64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 22631 };
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &arm64);
+        assert_eq!(matchInfo[0].1[0].DriverList[0].Decoration, "NTarm64.10.0...22000");
+        // 低于要求的内部版本号时不匹配
+        let oldArm64 = TargetOS { Build: 19045, ..arm64.clone() };
+        assert!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &oldArm64).is_empty());
+    }
+
+    // 索引文件格式测试
+    #[test]
+    fn indexFileTest() {
+        use crate::command::create_index::{IndexFile, IndexFormat, InfInfo, INDEX_FORMAT_VERSION};
+        use crate::utils::infParser::InfFile;
+
+        let tempPath = env::temp_dir().join("DriverIndexer-indexFileTest");
+        std::fs::create_dir_all(&tempPath).unwrap();
+        let packagePath = tempPath.join("Drivers.7z");
+        std::fs::write(&packagePath, b"driver package").unwrap();
+
+        let inf = InfFile::parse("[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_15B8\n");
+        let entries = vec![InfInfo::fromInf("Lan".to_string(), "e1d.inf".to_string(), &inf)];
+
+        // 保存与读取
+        let index = IndexFile::new(entries.clone(), Some(&packagePath), true).unwrap();
+        let indexPath = tempPath.join("Drivers.index");
+        index.save(&indexPath, IndexFormat::Json).unwrap();
+        let loaded = IndexFile::load(&indexPath).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.FormatVersion, INDEX_FORMAT_VERSION);
+        assert_eq!(loaded.Package.as_ref().unwrap().FileName, "Drivers.7z");
+        assert_eq!(loaded.Package.as_ref().unwrap().Size, 14);
+        assert!(loaded.PasswordRequired);
+        assert_eq!(loaded.verifyPackage(&packagePath).unwrap(), Some(loaded.Package.as_ref().unwrap().Hash.clone()));
+
+        // 驱动包被重新打包后拒绝使用索引
+        std::fs::write(&packagePath, b"driver packagf").unwrap();
+        assert!(loaded.verifyPackage(&packagePath).is_err());
+
+        // 旧版索引自动迁移
+        let legacy = r#"[{"Path":"Lan","Inf":"e1d.inf","Class":"Net","Arch":["NTamd64"],"Date":"","Version":"","DriverList":["PCI\\VEN_8086&DEV_15B8"]}]"#;
+        let migrated = IndexFile::parse(legacy).unwrap();
+        assert_eq!(migrated.FormatVersion, 1);
+        assert!(migrated.Package.is_none());
+        assert_eq!(migrated.Entries[0].DriverList[0].Id, r"PCI\VEN_8086&DEV_15B8");
+        assert!(IndexFile::parse(&serde_json::to_string(&entries).unwrap()).is_ok());
+
+        // 不支持的版本与错误格式
+        assert!(IndexFile::parse(r#"{"FormatVersion":99,"Entries":[]}"#).is_err());
+        assert!(IndexFile::parse(r#"{"FormatVersion":2}"#).is_err());
+
+        std::fs::remove_dir_all(&tempPath).ok();
+    }
+
+    // 硬件id索引测试
+    #[test]
+    fn hardwareIdIndexTest() {
+        use crate::command::create_index::InfInfo;
+        use crate::command::load_driver::getMatchInfo;
+        use crate::utils::devcon::HwID;
+        use crate::utils::infParser::InfFile;
+        use crate::utils::targetOS::TargetOS;
+
+        assert_eq!(
+            HardwareIdIndex::variants(r"pci\ven_8086&dev_15b8&subsys_00008086&rev_03"),
+            [r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086", r"PCI\VEN_8086&DEV_15B8&REV_03", r"PCI\VEN_8086&DEV_15B8"]
+        );
+        assert!(HardwareIdIndex::variants(r"ACPI\PNP0A08").is_empty());
+
+        let inf = InfFile::parse("[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_15B8,PCI\\CC_0200\n");
+        let index = HardwareIdIndex::new(vec![InfInfo::fromInf("Lan".to_string(), "e1d.inf".to_string(), &inf)]);
+        assert_eq!(index.lookup(r" pci\ven_8086&dev_15b8 "), [(0, 0)]);
+        assert_eq!(index.lookup(r"PCI\CC_0200"), [(0, 1)]);
+        assert!(index.lookup(r"PCI\VEN_8086").is_empty());
+
+        // 设备仅报告了完整的硬件id（如硬件快照），简化形式作为优先级较低的硬件id参与匹配
+        let hwIdList = vec![HwID {
+            DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03\3&11583659&0&FE".to_string(),
+            Name: "Ethernet Controller".to_string(),
+            HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03".to_string()],
+            CompatibleIDs: vec![r"PCI\CC_0200".to_string()],
+            Status: None,
+        }];
+        let deviceIDs = HardwareIdIndex::deviceIDs(&hwIdList[0]);
+        assert_eq!(deviceIDs[3], (false, 3, r"PCI\VEN_8086&DEV_15B8".to_string()));
+        assert_eq!(deviceIDs[4], (true, 0, r"PCI\CC_0200".to_string()));
+
+        let win10 = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+        let matchInfo = getMatchInfo(&hwIdList, &index, None, &win10);
+        assert_eq!(matchInfo[0].1[0].Rank, 0x80FF0300);
+        assert_eq!(matchInfo[0].1[0].DriverList.len(), 2);
+
+        // 简化形式已作为兼容id报告时按兼容id计算等级
+        let hwIdList = vec![HwID {
+            CompatibleIDs: vec![r"PCI\VEN_8086&DEV_15B8&REV_03".to_string(), r"PCI\VEN_8086&DEV_15B8".to_string(), r"PCI\CC_0200".to_string()],
+            ..hwIdList[0].clone()
+        }];
+        let deviceIDs = HardwareIdIndex::deviceIDs(&hwIdList[0]);
+        assert_eq!(deviceIDs.len(), 5);
+        assert_eq!(deviceIDs[1], (false, 1, r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086".to_string()));
+        assert_eq!(deviceIDs[3], (true, 1, r"PCI\VEN_8086&DEV_15B8".to_string()));
+        let matchInfo = getMatchInfo(&hwIdList, &index, None, &win10);
+        assert_eq!(matchInfo[0].1[0].Rank, 0x80FF2100);
+    }
+
+    // 匹配性能测试（50000 个INF、300 个设备）
+    // cargo test --release matchBenchmark -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn matchBenchmark() {
+        use crate::command::create_index::{DriverID, InfInfo};
+        use crate::command::load_driver::getMatchInfo;
+        use crate::utils::catalog::SignatureStatus;
+        use crate::utils::devcon::HwID;
+        use crate::utils::targetOS::TargetOS;
+        use std::time::Instant;
+
+        let infInfoList: Vec<InfInfo> = (0..50000)
+            .map(|index| InfInfo {
+                Path: format!("Drivers\\{}", index),
+                Inf: format!("oem{}.inf", index),
+                Class: "Net".to_string(),
+                Arch: vec!["NTamd64".to_string()],
+                Date: "06/01/2020".to_string(),
+                Version: format!("1.0.{}.0", index % 100),
+                Catalog: format!("oem{}.cat", index),
+                Size: 0,
+                Modified: 0,
+                FilesStamp: 0,
+                Package: String::new(),
+                Missing: Vec::new(),
+                Signature: SignatureStatus::Unknown,
+                Signer: String::new(),
+                OSAttr: String::new(),
+                DriverList: (0..8)
+                    .map(|id| DriverID {
+                        Id: format!("PCI\\VEN_{:04X}&DEV_{:04X}", index % 2000, index / 2000 * 8 + id),
+                        Models: "Mfg".to_string(),
+                        Decoration: "NTamd64".to_string(),
+                        Position: 0,
+                        FeatureScore: 0xFF,
+                        Incomplete: false,
+                    })
+                    .collect(),
+                Rank: 0,
+            })
+            .collect();
+        let hwIdList: Vec<HwID> = (0..300)
+            .map(|index| {
+                let id = format!("PCI\\VEN_{:04X}&DEV_{:04X}", index * 7 % 2000, index % 200);
+                HwID {
+                    DeviceInstancePath: format!("{}\\{}", id, index),
+                    Name: format!("Device {}", index),
+                    HardwareIDs: vec![format!("{}&SUBSYS_00000000&REV_01", id), format!("{}&SUBSYS_00000000", id), format!("{}&REV_01", id), id],
+                    CompatibleIDs: vec!["PCI\\CC_020000".to_string(), "PCI\\CC_0200".to_string()],
+                    Status: None,
+                }
+            })
+            .collect();
+        let target = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+
+        let time = Instant::now();
+        let index = HardwareIdIndex::new(infInfoList);
+        let buildTime = time.elapsed();
+
+        let time = Instant::now();
+        let matchInfo = getMatchInfo(&hwIdList, &index, None, &target);
+        let matchTime = time.elapsed();
+
+        println!("建立索引耗时：{:?}", buildTime);
+        println!("匹配耗时：{:?}，匹配设备 {} 个，{:.0} 设备/秒", matchTime, matchInfo.len(), hwIdList.len() as f64 / matchTime.as_secs_f64());
+        assert!(!matchInfo.is_empty());
+    }
+
+    // 增量更新索引测试
+    #[test]
+    fn updateIndexTest() {
+        use crate::command::create_index::{createIndex, IndexFile, IndexFormat};
+
+        let drivePath = env::temp_dir().join("DriverIndexer-updateIndexTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        std::fs::create_dir_all(drivePath.join("Lan")).unwrap();
+        std::fs::create_dir_all(drivePath.join("Wlan")).unwrap();
+        std::fs::create_dir_all(drivePath.join("Audio")).unwrap();
+        let infContent = |id: &str| format!("[Version]\nClass=Net\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,{}\n", id);
+        std::fs::write(drivePath.join("Lan").join("e1d.inf"), infContent(r"PCI\VEN_8086&DEV_15B8")).unwrap();
+        std::fs::write(drivePath.join("Wlan").join("netwtw.inf"), infContent(r"PCI\VEN_8086&DEV_2723")).unwrap();
+        std::fs::write(drivePath.join("Audio").join("hdaudio.inf"), infContent(r"HDAUDIO\FUNC_01&VEN_10EC&DEV_0236")).unwrap();
+
+        let indexPath = drivePath.join("Drivers.index");
+        createIndex(&drivePath, None, &indexPath, Some(IndexFormat::Binary), false).unwrap();
+        let mut index = IndexFile::load(&indexPath).unwrap();
+        assert_eq!(index.Entries.len(), 3);
+        assert!(index.Entries.iter().all(|item| item.Size > 0 && item.Modified > 0));
+
+        // 标记已有记录，以确认未变更的INF没有被重新解析
+        for item in index.Entries.iter_mut() {
+            item.Class = "Reused".to_string();
+        }
+        index.save(&indexPath, IndexFormat::Binary).unwrap();
+
+        // 新增、变更、删除各一个INF
+        std::fs::create_dir_all(drivePath.join("Bluetooth")).unwrap();
+        std::fs::write(drivePath.join("Bluetooth").join("ibtusb.inf"), infContent(r"USB\VID_8087&PID_0026")).unwrap();
+        std::fs::write(drivePath.join("Lan").join("e1d.inf"), infContent(r"PCI\VEN_8086&DEV_15BB&SUBSYS_00008086")).unwrap();
+        std::fs::remove_file(drivePath.join("Wlan").join("netwtw.inf")).unwrap();
+
+        // 未指定格式时沿用已有索引的格式
+        createIndex(&drivePath, None, &indexPath, None, true).unwrap();
+        let updated = IndexFile::load(&indexPath).unwrap();
+        let mut infList: Vec<(&str, &str)> = updated.Entries.iter().map(|item| (item.Inf.as_str(), item.Class.as_str())).collect();
+        infList.sort();
+        assert_eq!(infList, [("e1d.inf", "Net"), ("hdaudio.inf", "Reused"), ("ibtusb.inf", "Net")]);
+
+        // 再次更新时全部沿用
+        createIndex(&drivePath, None, &indexPath, None, true).unwrap();
+        assert!(IndexFile::load(&indexPath).unwrap().Entries.iter().all(|item| item.Inf == "hdaudio.inf" || item.Class == "Net"));
+
+        // INF未变更，但编录文件或驱动文件变更时重新解析
+        std::fs::create_dir_all(drivePath.join("Storage")).unwrap();
+        let storageContent = "[Version]\nClass=SCSIAdapter\nCatalogFile=iastor.cat\n[SourceDisksNames]\n1=%Disk%\n[SourceDisksFiles]\niastor.sys=1\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_2822\n[Install.NTamd64]\nCopyFiles=@iastor.sys\n";
+        std::fs::write(drivePath.join("Storage").join("iastor.inf"), storageContent).unwrap();
+        std::fs::write(drivePath.join("Storage").join("iastor.sys"), "sys").unwrap();
+        std::fs::write(drivePath.join("Storage").join("iastor.cat"), "cat").unwrap();
+        createIndex(&drivePath, None, &indexPath, None, true).unwrap();
+        let storageClass = |change: &dyn Fn()| {
+            let mut index = IndexFile::load(&indexPath).unwrap();
+            for item in index.Entries.iter_mut() {
+                item.Class = "Reused".to_string();
+            }
+            index.save(&indexPath, IndexFormat::Binary).unwrap();
+            change();
+            createIndex(&drivePath, None, &indexPath, None, true).unwrap();
+            IndexFile::load(&indexPath).unwrap().Entries.iter().find(|item| item.Inf == "iastor.inf").unwrap().Class.clone()
+        };
+        assert_eq!(storageClass(&|| {}), "Reused");
+        assert_eq!(storageClass(&|| std::fs::write(drivePath.join("Storage").join("iastor.cat"), "cat v2").unwrap()), "SCSIAdapter");
+        assert_eq!(storageClass(&|| std::fs::write(drivePath.join("Storage").join("iastor.sys"), "sys v2").unwrap()), "SCSIAdapter");
+        assert_eq!(storageClass(&|| std::fs::remove_file(drivePath.join("Storage").join("iastor.cat")).unwrap()), "SCSIAdapter");
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 驱动文件完整性测试
+    #[test]
+    fn driverFilesTest() {
+        use crate::command::create_index::{createIndex, IndexFile, IndexFormat, InfInfo};
+        use crate::command::load_driver::{checkDriverFiles, getMatchInfo};
+        use crate::utils::archiveBackend::ArchiveBackend;
+        use crate::utils::devcon::HwID;
+        use crate::utils::driverFiles::{missingFiles, requiredFiles, sectionFiles};
+        use crate::utils::signaturePolicy::SignaturePolicy;
+        use crate::utils::hardwareIdIndex::HardwareIdIndex;
+        use crate::utils::infParser::InfFile;
+        use crate::utils::nativeArchive::NativeArchive;
+        use crate::utils::targetOS::TargetOS;
+
+        let content = "[Version]\nClass=Net\nCatalogFile.NTamd64=e1d.cat\n[SourceDisksNames]\n1=%Disk%,,,\\x64\n2=%Disk%,,,\\x86\n[SourceDisksFiles]\ne1d.sys=1\n[SourceDisksFiles.amd64]\ne1dmsg.dll=1,msg\n[SourceDisksFiles.x86]\ne1d32.sys=2\n[Manufacturer]\n%Mfg%=Mfg,NTamd64,NTx86\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_15B8\n[Mfg.NTx86]\n%Dev%=Install,PCI\\VEN_8086&DEV_15B8\n[Install.NTamd64]\nCopyFiles=Driver.CopyFiles,@e1d.din\n[Install.NTamd64.CoInstallers]\nCopyFiles=@e1dmsg.dll\n[Install.NTx86]\nCopyFiles=@e1d32.sys\n[Driver.CopyFiles]\ne1d.sys\nnetio.sys\n";
+        // 未在 SourceDisksFiles 中列出的文件（netio.sys、e1d.din）由系统提供
+        assert_eq!(requiredFiles(&InfFile::parse(content)), vec!["e1d.cat", "x64/e1d.sys", "x64/msg/e1dmsg.dll", "x86/e1d32.sys"]);
+        // 各个 Models 节仅需要该架构的编录文件及驱动文件
+        let sectionList = sectionFiles(&InfFile::parse(content));
+        assert_eq!(sectionList.len(), 2);
+        assert_eq!((sectionList[1].Decoration.as_str(), sectionList[1].Arch.as_str()), ("NTx86", "NTx86"));
+        assert_eq!(sectionList[1].Files, vec!["x86/e1d32.sys"]);
+
+        let drivePath = env::temp_dir().join("DriverIndexer-driverFilesTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        std::fs::create_dir_all(drivePath.join("Lan").join("x64").join("msg")).unwrap();
+        std::fs::create_dir_all(drivePath.join("Lan").join("x86")).unwrap();
+        std::fs::write(drivePath.join("Lan").join("x86").join("e1d32.sys"), "sys").unwrap();
+        std::fs::write(drivePath.join("Lan").join("e1d.inf"), content).unwrap();
+        std::fs::write(drivePath.join("Lan").join("e1d.cat"), "cat").unwrap();
+        std::fs::write(drivePath.join("Lan").join("x64").join("e1d.sys"), "").unwrap();
+        std::fs::write(drivePath.join("Lan").join("x64").join("msg").join("e1dmsg.dll"), "dll").unwrap();
+        // 为空的文件视为缺失
+        let missing = missingFiles(&InfFile::parse(content), &drivePath.join("Lan"));
+        assert_eq!(missing.len(), 1);
+        assert_eq!((missing[0].Decoration.as_str(), missing[0].Files.clone()), ("NTamd64", vec!["x64/e1d.sys".to_string()]));
+
+        // 索引中标记缺少文件的 Models 节（驱动目录及压缩包），匹配时跳过
+        let packagePath = env::temp_dir().join("DriverIndexer-driverFilesTest.zip");
+        NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+        assert_eq!(InfInfo::parsingInfArchive(&packagePath, None).unwrap()[0].Missing, vec!["x64/e1d.sys"]);
+        let indexPath = drivePath.join("Drivers.index");
+        createIndex(&drivePath, None, &indexPath, Some(IndexFormat::Binary), false).unwrap();
+        let index = IndexFile::load(&indexPath).unwrap();
+        assert_eq!(index.Entries[0].Missing, vec!["x64/e1d.sys"]);
+
+        let hwIdList = vec![HwID {
+            DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8\3&11583659&0&FE".to_string(),
+            Name: "Ethernet Controller".to_string(),
+            HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8".to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        }];
+        let win10 = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+        assert!(index.Entries[0].DriverList.iter().all(|driverID| driverID.Incomplete == (driverID.Decoration == "NTamd64")));
+        assert!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(index.Entries.clone()), None, &win10).is_empty());
+        // 其他架构的 Models 节完整时仍可匹配及安装
+        let win10x86 = TargetOS { Arch: "NTx86".to_string(), ..win10.clone() };
+        assert_eq!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(index.Entries.clone()), None, &win10x86).len(), 1);
+        let infPath = drivePath.join("Lan").join("e1d.inf");
+        assert!(checkDriverFiles(&infPath, SignaturePolicy::Any, &win10).is_some());
+        assert!(checkDriverFiles(&infPath, SignaturePolicy::Any, &win10x86).is_none());
+        // 旧版索引未标记 Models 节时跳过整个驱动
+        let mut legacy = index.Entries;
+        legacy[0].DriverList.iter_mut().for_each(|driverID| driverID.Incomplete = false);
+        assert!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(legacy), None, &win10x86).is_empty());
+
+        // 补全文件后增量更新重新检查
+        std::fs::write(drivePath.join("Lan").join("x64").join("e1d.sys"), "sys").unwrap();
+        createIndex(&drivePath, None, &indexPath, None, true).unwrap();
+        let index = IndexFile::load(&indexPath).unwrap();
+        assert!(index.Entries[0].Missing.is_empty());
+        assert_eq!(getMatchInfo(&hwIdList, &HardwareIdIndex::new(index.Entries), None, &win10).len(), 1);
+        let _ = std::fs::remove_file(&packagePath);
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 编录文件测试（testDrive/catalog 为 generate.py 生成的驱动及其以测试证书链签名的编录文件）
+    #[test]
+    fn catalogTest() {
+        use crate::command::create_index::{createIndex, IndexFile, InfInfo};
+        use crate::utils::archiveBackend::ArchiveBackend;
+        use crate::utils::catalog::{Catalog, HashAlgorithm, HashKind, SignatureStatus};
+        use crate::utils::driverFiles::verifySignature;
+        use crate::utils::driverRank::SignatureTier;
+        use crate::utils::infParser::InfFile;
+        use crate::utils::nativeArchive::NativeArchive;
+
+        let fixturePath = Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("catalog");
+        let catalog = Catalog::open(&fixturePath.join("sample.cat")).unwrap();
+        // 每个文件各有 SHA-1 与 SHA-256 成员
+        assert_eq!(catalog.Members.len(), 4);
+        assert_eq!(catalog.Members.iter().filter(|member| member.Algorithm == HashAlgorithm::Sha256).count(), 2);
+        let sys = catalog.Members.iter().find(|member| member.Kind == Some(HashKind::Authenticode)).unwrap();
+        assert_eq!(sys.Tag, "0D9A7FC84ED2971E640EA946B7B90EAE5D4932B11AEF2A27ACA6C321405ED047");
+        assert_eq!(sys.attribute("File"), Some("sample.sys"));
+        assert_eq!(catalog.osAttr(), vec!["2:6.2,2:6.3,2:10.0"]);
+        assert!(catalog.attribute("OS").unwrap().contains("_v100_X64"));
+        assert_eq!(catalog.signer(), Some("CN=DriverIndexer Test Publisher, OU=Driver Signing, O=DriverIndexer, C=CN"));
+        assert_eq!(catalog.status(), SignatureStatus::Whql);
+
+        // PE 文件比较 Authenticode 映像哈希，其他文件比较整个文件的哈希
+        let sysContent = std::fs::read(fixturePath.join("sample.sys")).unwrap();
+        assert_eq!(catalog.findFile(&sysContent).len(), 2);
+        assert_eq!(catalog.findFile(&std::fs::read(fixturePath.join("sample.inf")).unwrap()).len(), 2);
+        assert!(catalog.findFile(b"sample").is_empty());
+        assert!(Catalog::parse(b"cat").is_err());
+
+        // 签名校验：签名内容或签名属性被修改视为被篡改
+        let catalogData = std::fs::read(fixturePath.join("sample.cat")).unwrap();
+        // 篡改位置：CTL 内容（成员属性名称）、签名属性（消息摘要）、签名值（位于文件末尾）
+        let find = |pattern: &[u8]| catalogData.windows(pattern.len()).position(|window| window == pattern).unwrap();
+        let osAttr: Vec<u8> = "OSAttr".encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let messageDigest = find(&[0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x04]) + 15;
+        for offset in [find(&osAttr), messageDigest, catalogData.len() - 10] {
+            let mut forged = catalogData.clone();
+            forged[offset] ^= 0xFF;
+            assert_eq!(Catalog::parse(&forged).unwrap().status(), SignatureStatus::Tampered);
+        }
+        // 签名者名称与 WHQL 用途相同，但证书链的根证书仅名称相同
+        let forged = Catalog::open(&fixturePath.join("forged.cat")).unwrap();
+        assert_eq!(forged.signer(), catalog.signer());
+        assert_eq!(forged.Members.len(), 4);
+        assert_eq!(forged.status(), SignatureStatus::Unverified);
+        assert_eq!(SignatureStatus::fromCode(SignatureStatus::Unverified as u8), SignatureStatus::Unverified);
+
+        // 驱动目录：签名状态写入INF信息并参与驱动等级
+        let drivePath = env::temp_dir().join("DriverIndexer-catalogTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        std::fs::create_dir_all(&drivePath).unwrap();
+        for name in ["sample.inf", "sample.cat", "sample.sys"] {
+            std::fs::copy(fixturePath.join(name), drivePath.join(name)).unwrap();
+        }
+        let infInfo = InfInfo::parsingInfFile(&drivePath, &drivePath.join("sample.inf"), true).unwrap();
+        assert_eq!(infInfo.Signature, SignatureStatus::Whql);
+        assert_eq!(infInfo.Signer, catalog.signer().unwrap());
+        assert_eq!(SignatureTier::fromInfInfo(&infInfo), SignatureTier::Whql);
+
+        // 压缩包：不解压驱动文件，仅校验INF本身
+        let packagePath = env::temp_dir().join("DriverIndexer-catalogTest.zip");
+        NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+        assert_eq!(InfInfo::parsingInfArchive(&packagePath, None).unwrap()[0].Signature, SignatureStatus::Whql);
+
+        // 压缩包内多个驱动目录：每个INF仅使用其引用的编录文件
+        let multiPath = env::temp_dir().join("DriverIndexer-catalogTest-Multi");
+        let multiPackagePath = env::temp_dir().join("DriverIndexer-catalogTest-Multi.zip");
+        let _ = std::fs::remove_dir_all(&multiPath);
+        for (dir, nameList) in [("A", vec!["sample.inf", "sample.cat", "sample.sys"]), ("B", vec!["sample.inf", "sample.sys"])] {
+            std::fs::create_dir_all(multiPath.join(dir)).unwrap();
+            for name in nameList {
+                std::fs::copy(fixturePath.join(name), multiPath.join(dir).join(name)).unwrap();
+            }
+        }
+        NativeArchive::new().create(&multiPath, &multiPackagePath).unwrap();
+        let mut infInfoList = InfInfo::parsingInfArchive(&multiPackagePath, None).unwrap();
+        infInfoList.sort_by(|a, b| a.Path.cmp(&b.Path));
+        assert_eq!(infInfoList.iter().map(|infInfo| infInfo.Signature).collect::<Vec<_>>(), vec![SignatureStatus::Whql, SignatureStatus::Unsigned]);
+        let indexPath = env::temp_dir().join("DriverIndexer-catalogTest-Multi.index");
+        createIndex(&multiPackagePath, None, &indexPath, None, false).unwrap();
+        let index = IndexFile::load(&indexPath).unwrap();
+        assert_eq!(index.Entries.iter().map(|infInfo| (infInfo.Path.as_str(), infInfo.Signature)).collect::<Vec<_>>(), vec![("A", SignatureStatus::Whql), ("B", SignatureStatus::Unsigned)]);
+        let _ = std::fs::remove_file(&indexPath);
+        let _ = std::fs::remove_file(&multiPackagePath);
+        let _ = std::fs::remove_dir_all(&multiPath);
+
+        // 多架构驱动：每个架构使用各自的编录文件，仅校验该架构的驱动文件
+        let multiArch = InfFile::parse("[Version]\nCatalogFile.NTamd64=sample.cat\nCatalogFile.NTx86=sample32.cat\n[SourceDisksNames]\n1=%Disk%\n[SourceDisksFiles]\nsample.sys=1\nsample32.sys=1\n[Manufacturer]\n%Mfg%=Mfg,NTamd64,NTx86\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_FFFF&DEV_0001\n[Mfg.NTx86]\n%Dev%=Install,PCI\\VEN_FFFF&DEV_0001\n[Install.NTamd64]\nCopyFiles=@sample.sys\n[Install.NTx86]\nCopyFiles=@sample32.sys\n");
+        std::fs::write(drivePath.join("sample32.sys"), "x86").unwrap();
+        let read = |file: &str| std::fs::read(drivePath.join(file)).ok();
+        assert_eq!(verifySignature(&multiArch, "sample.inf", Some("NTamd64"), &read).Status, SignatureStatus::Whql);
+        // 未指定架构时取最差的签名状态（缺少 x86 编录文件）
+        let signature = verifySignature(&multiArch, "sample.inf", None, &read);
+        assert_eq!(signature.Status, SignatureStatus::Unsigned);
+        assert!(signature.Mismatched.is_empty());
+        assert_eq!(signature.Signer, catalog.signer().unwrap());
+        std::fs::copy(fixturePath.join("sample.cat"), drivePath.join("sample32.cat")).unwrap();
+        let signature = verifySignature(&multiArch, "sample.inf", Some("NTx86"), &read);
+        assert_eq!(signature.Status, SignatureStatus::Tampered);
+        assert_eq!(signature.Mismatched, vec!["sample32.sys"]);
+        std::fs::remove_file(drivePath.join("sample32.sys")).unwrap();
+        std::fs::remove_file(drivePath.join("sample32.cat")).unwrap();
+
+        // 驱动文件被修改
+        let mut tampered = sysContent.clone();
+        tampered[0x1000] ^= 0xFF;
+        std::fs::write(drivePath.join("sample.sys"), tampered).unwrap();
+        let signature = verifySignature(&InfFile::open(&drivePath.join("sample.inf")).unwrap(), "sample.inf", None, &|file| std::fs::read(drivePath.join(file)).ok());
+        assert_eq!(signature.Status, SignatureStatus::Tampered);
+        assert_eq!(signature.Mismatched, vec!["sample.sys"]);
+        let infInfo = InfInfo::parsingInfFile(&drivePath, &drivePath.join("sample.inf"), true).unwrap();
+        assert_eq!(SignatureTier::fromInfInfo(&infInfo), SignatureTier::Unsigned);
+        // 仅校验INF本身时不读取驱动文件（由安装前的检查校验）
+        let infInfo = InfInfo::parsingInfFile(&drivePath, &drivePath.join("sample.inf"), false).unwrap();
+        assert_eq!(infInfo.Signature, SignatureStatus::Whql);
+
+        // 缺少编录文件
+        std::fs::remove_file(drivePath.join("sample.cat")).unwrap();
+        assert_eq!(InfInfo::parsingInfFile(&drivePath, &drivePath.join("sample.inf"), true).unwrap().Signature, SignatureStatus::Unsigned);
+        let _ = std::fs::remove_file(&packagePath);
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 签名策略测试（testDrive/catalog 中的 WHQL 编录文件适用于 Windows 8、8.1、10）
+    #[test]
+    fn signaturePolicyTest() {
+        use crate::command::create_index::InfInfo;
+        use crate::command::load_driver::{applySignaturePolicy, checkDriverFiles, installMatchDrivers, packageMatchGroup};
+        use crate::utils::catalog::SignatureStatus;
+        use crate::utils::devcon::HwID;
+        use crate::utils::devconParser::DeviceState;
+        use crate::utils::deviceBackend::{DeviceBackend, DeviceFilter};
+        use crate::utils::signaturePolicy::{osAttrCovers, PolicyViolation, SignaturePolicy};
+        use crate::utils::targetOS::TargetOS;
+        use std::collections::BTreeSet;
+        use std::sync::Arc;
+
+        let target = |major: u32, minor: u32| TargetOS { Arch: "NTamd64".to_string(), Major: major, Minor: minor, ProductType: 1, SuiteMask: 0, Build: 0 };
+        let (win7, win10) = (target(6, 1), target(10, 0));
+        assert_eq!(SignaturePolicy::fromName("WHQL"), Some(SignaturePolicy::Whql));
+        assert_eq!(SignaturePolicy::fromName("signed"), Some(SignaturePolicy::Signed));
+        assert_eq!(SignaturePolicy::fromName("test"), None);
+        assert_eq!(SignaturePolicy::default(), SignaturePolicy::Any);
+
+        // OSAttr：平台 2 为 Windows NT 系列
+        assert!(osAttrCovers("2:6.2,2:6.3,2:10.0", &win10));
+        assert!(!osAttrCovers("2:6.2,2:6.3,2:10.0", &win7));
+        assert!(osAttrCovers("2:5.00,2:6.1", &win7));
+        assert!(!osAttrCovers("1:10.0", &win10));
+        assert!(!osAttrCovers("", &win10));
+
+        // 各策略的检查规则
+        let signer = "CN=Contoso";
+        assert!(SignaturePolicy::Any.check(SignatureStatus::Unsigned, "", "", &win10).is_ok());
+        assert_eq!(SignaturePolicy::Signed.check(SignatureStatus::Unsigned, "", "", &win10), Err(PolicyViolation::Unsigned));
+        assert_eq!(SignaturePolicy::Signed.check(SignatureStatus::Tampered, signer, "", &win10), Err(PolicyViolation::Tampered));
+        assert!(SignaturePolicy::Signed.check(SignatureStatus::Signed, signer, "", &win10).is_ok());
+        assert!(SignaturePolicy::Signed.check(SignatureStatus::Whql, signer, "", &win10).is_ok());
+        assert_eq!(SignaturePolicy::Whql.check(SignatureStatus::Signed, signer, "", &win10), Err(PolicyViolation::NotWhql(signer.to_string())));
+        assert!(SignaturePolicy::Whql.check(SignatureStatus::Whql, signer, "2:10.0", &win10).is_ok());
+        assert_eq!(SignaturePolicy::Whql.check(SignatureStatus::Whql, signer, "2:10.0", &win7), Err(PolicyViolation::UnsupportedOS("2:10.0".to_string())));
+        // 签名或证书链无法验证的编录文件不视为已签名
+        assert_eq!(SignaturePolicy::Signed.check(SignatureStatus::Unverified, signer, "2:10.0", &win10), Err(PolicyViolation::Unverified(signer.to_string())));
+        assert_eq!(SignaturePolicy::Whql.check(SignatureStatus::Unverified, signer, "2:10.0", &win10), Err(PolicyViolation::Unverified(signer.to_string())));
+        assert!(SignaturePolicy::Any.check(SignatureStatus::Unverified, signer, "", &win10).is_ok());
+        // 签名状态未知（旧版索引）时留待安装前检查
+        assert!(SignaturePolicy::Whql.check(SignatureStatus::Unknown, "", "", &win10).is_ok());
+
+        // 驱动目录：WHQL 驱动与未签名驱动匹配同一设备
+        let fixturePath = Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("catalog");
+        let drivePath = env::temp_dir().join("DriverIndexer-signaturePolicyTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        std::fs::create_dir_all(drivePath.join("Sample")).unwrap();
+        std::fs::create_dir_all(drivePath.join("Generic")).unwrap();
+        for name in ["sample.inf", "sample.cat", "sample.sys"] {
+            std::fs::copy(fixturePath.join(name), drivePath.join("Sample").join(name)).unwrap();
+        }
+        let content = "[Version]\nClass=System\nDriverVer=06/01/2024,99.0.0.0\nCatalogFile=generic.cat\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_FFFF&DEV_0001\n";
+        std::fs::write(drivePath.join("Generic").join("generic.inf"), content).unwrap();
+        std::fs::write(drivePath.join("Generic").join("generic.cat"), "cat").unwrap();
+
+        let sample = InfInfo::parsingInfFile(&drivePath, &drivePath.join("Sample").join("sample.inf"), true).unwrap();
+        assert_eq!(sample.OSAttr, "2:6.2,2:6.3,2:10.0");
+        assert!(SignaturePolicy::Whql.checkInf(&sample, &win10).is_ok());
+        assert_eq!(SignaturePolicy::Whql.checkInf(&sample, &win7), Err(PolicyViolation::UnsupportedOS(sample.OSAttr.clone())));
+        let generic = InfInfo::parsingInfFile(&drivePath, &drivePath.join("Generic").join("generic.inf"), true).unwrap();
+        assert_eq!(SignaturePolicy::Signed.checkInf(&generic, &win10), Err(PolicyViolation::Unsigned));
+
+        // 排除不符合策略的驱动，没有候选驱动的设备不再匹配，每个驱动仅提示一次
+        let device = |path: &str| HwID {
+            DeviceInstancePath: path.to_string(),
+            Name: path.to_string(),
+            HardwareIDs: vec![r"PCI\VEN_FFFF&DEV_0001".to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        };
+        let matchList = vec![(device("A"), vec![sample.clone(), generic.clone()]), (device("B"), vec![generic.clone()])];
+        let mut rejectedList: BTreeSet<String> = BTreeSet::new();
+        let result = applySignaturePolicy(matchList.clone(), SignaturePolicy::Signed, &win10, &mut rejectedList);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].1, vec![sample.clone()]);
+        assert_eq!(rejectedList.len(), 1);
+        assert!(applySignaturePolicy(matchList.clone(), SignaturePolicy::Whql, &win7, &mut BTreeSet::new()).is_empty());
+        assert_eq!(applySignaturePolicy(matchList, SignaturePolicy::Any, &win7, &mut BTreeSet::new()).len(), 2);
+
+        // 安装前按解压后的驱动文件检查
+        let sampleInf = drivePath.join("Sample").join("sample.inf");
+        assert!(checkDriverFiles(&sampleInf, SignaturePolicy::Whql, &win10).is_none());
+        assert!(checkDriverFiles(&sampleInf, SignaturePolicy::Whql, &win7).is_some());
+        assert!(checkDriverFiles(&drivePath.join("Generic").join("generic.inf"), SignaturePolicy::Any, &win10).is_none());
+        assert!(checkDriverFiles(&drivePath.join("Generic").join("generic.inf"), SignaturePolicy::Signed, &win10).is_some());
+        // 签名者名称与 WHQL 相同但证书链不是 Microsoft 根证书的编录文件
+        let forgedPath = env::temp_dir().join("DriverIndexer-signaturePolicyTest-Forged");
+        let _ = std::fs::remove_dir_all(&forgedPath);
+        std::fs::create_dir_all(&forgedPath).unwrap();
+        for name in ["sample.inf", "sample.sys"] {
+            std::fs::copy(fixturePath.join(name), forgedPath.join(name)).unwrap();
+        }
+        std::fs::copy(fixturePath.join("forged.cat"), forgedPath.join("sample.cat")).unwrap();
+        let forged = InfInfo::parsingInfFile(&forgedPath, &forgedPath.join("sample.inf"), true).unwrap();
+        assert_eq!(SignaturePolicy::Whql.checkInf(&forged, &win10), Err(PolicyViolation::Unverified(sample.Signer.clone())));
+        assert!(checkDriverFiles(&forgedPath.join("sample.inf"), SignaturePolicy::Signed, &win10).is_some());
+        let _ = std::fs::remove_dir_all(&forgedPath);
+
+        // 安装：版本更高的未签名驱动不参与匹配，安装 WHQL 驱动
+        let group = packageMatchGroup(&drivePath, None, None, None).unwrap();
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\SAMPLE"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Whql, false, None).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed, vec![r"PCI\SAMPLE"]);
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 合并、拆分索引测试
+    #[test]
+    fn mergeIndexTest() {
+        use crate::command::create_index::{IndexFile, IndexFormat, InfInfo};
+        use crate::utils::infParser::InfFile;
+
+        let tempPath = env::temp_dir().join("DriverIndexer-mergeIndexTest");
+        std::fs::create_dir_all(&tempPath).unwrap();
+        let lanPath = tempPath.join("Lan.7z");
+        let wlanPath = tempPath.join("Wlan.7z");
+        std::fs::write(&lanPath, b"lan package").unwrap();
+        std::fs::write(&wlanPath, b"wlan package").unwrap();
+
+        let infInfo = |path: &str, id: &str| {
+            let inf = InfFile::parse(&format!("[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,{}\n", id));
+            InfInfo::fromInf(path.to_string(), "net.inf".to_string(), &inf)
+        };
+        let lan = IndexFile::new(vec![infInfo("Intel", r"PCI\VEN_8086&DEV_15B8")], Some(&lanPath), false).unwrap();
+        let wlan = IndexFile::new(vec![infInfo("Intel", r"PCI\VEN_8086&DEV_2723")], Some(&wlanPath), true).unwrap();
+
+        // 合并后INF驱动信息记录所属驱动包
+        let merged = IndexFile::merge(vec![lan.clone(), wlan.clone()]).unwrap();
+        assert!(merged.isMerged());
+        assert!(merged.PasswordRequired);
+        assert_eq!(merged.Packages.len(), 2);
+        let packages: Vec<&str> = merged.Entries.iter().map(|item| item.Package.as_str()).collect();
+        assert_eq!(packages, ["Lan.7z", "Wlan.7z"]);
+
+        // 按文件名校验成员驱动包
+        assert!(merged.verifyPackage(&lanPath).is_ok());
+        assert!(merged.verifyPackage(&tempPath.join("Other.7z")).is_err());
+
+        // 同名驱动包以后出现的索引为准
+        let newLan = IndexFile::new(vec![infInfo("Intel", r"PCI\VEN_8086&DEV_15BB"), infInfo("Realtek", r"PCI\VEN_10EC&DEV_8168")], Some(&lanPath), false).unwrap();
+        let remerged = IndexFile::merge(vec![merged.clone(), newLan]).unwrap();
+        assert_eq!(remerged.Packages.len(), 2);
+        assert_eq!(remerged.Entries.iter().filter(|item| item.Package == "Lan.7z").count(), 2);
+        assert!(!remerged.Entries.iter().any(|item| item.DriverList[0].Id == r"PCI\VEN_8086&DEV_15B8"));
+
+        // 二进制索引保留所属驱动包
+        let indexPath = tempPath.join("Drivers.index");
+        merged.save(&indexPath, IndexFormat::Binary).unwrap();
+        assert_eq!(IndexFile::load(&indexPath).unwrap(), merged);
+
+        // 拆分后还原各个驱动包的索引
+        let splitList = merged.split();
+        assert_eq!(splitList.len(), 2);
+        assert_eq!(splitList[0].Package, lan.Package);
+        assert_eq!(splitList[0].Entries, lan.Entries);
+        assert_eq!(splitList[1].Entries, wlan.Entries);
+
+        // 从驱动目录创建的索引无法合并
+        let directoryIndex = IndexFile::new(vec![infInfo("Intel", r"PCI\VEN_8086&DEV_15B8")], None, false).unwrap();
+        assert!(IndexFile::merge(vec![lan, directoryIndex]).is_err());
+        let _ = std::fs::remove_dir_all(&tempPath);
+    }
+
+    // 匹配报告测试
+    #[test]
+    fn matchReportTest() {
+        use crate::command::load_driver::packageMatchGroup;
+        use crate::command::match_driver::{matchReport, reportTable};
+        use crate::utils::devcon::HwID;
+        use crate::utils::targetOS::TargetOS;
+
+        let drivePath = env::temp_dir().join("DriverIndexer-matchReportTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        std::fs::create_dir_all(drivePath.join("Lan")).unwrap();
+        std::fs::create_dir_all(drivePath.join("Generic")).unwrap();
+        let infContent = |version: &str, id: &str| {
+            format!("[Version]\nClass=Net\nDriverVer=06/01/2020,{}\nCatalogFile=net.cat\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,{}\n", version, id)
+        };
+        std::fs::write(drivePath.join("Lan").join("e1d.inf"), infContent("12.19.1.37", r"PCI\VEN_8086&DEV_15B8")).unwrap();
+        std::fs::write(drivePath.join("Generic").join("net.inf"), infContent("1.0.0.0", r"PCI\CC_0200")).unwrap();
+        std::fs::write(drivePath.join("Lan").join("net.cat"), "cat").unwrap();
+        std::fs::write(drivePath.join("Generic").join("net.cat"), "cat").unwrap();
+
+        let group = packageMatchGroup(&drivePath, None, None, None).unwrap();
+        let hwIdList = vec![
+            HwID {
+                DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03\3&11583659&0&FE".to_string(),
+                Name: "Ethernet Controller".to_string(),
+                HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03".to_string(), r"PCI\VEN_8086&DEV_15B8".to_string()],
+                CompatibleIDs: vec![r"PCI\CC_0200".to_string()],
+                Status: None,
+            },
+            HwID {
+                DeviceInstancePath: r"USB\VID_0BDA&PID_8153\000001".to_string(),
+                Name: "USB Ethernet".to_string(),
+                HardwareIDs: vec![r"USB\VID_0BDA&PID_8153".to_string()],
+                CompatibleIDs: vec![],
+                Status: None,
+            },
+        ];
+        let win10 = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+        let report = matchReport(&[group], &hwIdList, None, &win10);
+
+        // 未匹配的设备不出现在报告中，候选驱动按驱动等级排列
+        assert_eq!(report.len(), 1);
+        let candidates = &report[0].Candidates;
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].Inf, std::path::Path::new("Lan").join("e1d.inf").to_string_lossy());
+        assert_eq!(candidates[0].MatchedID, r"PCI\VEN_8086&DEV_15B8");
+        assert_eq!(candidates[0].MatchType, "HardwareID");
+        // 编录文件无法解析，视为未签名
+        assert_eq!(candidates[0].Rank, "0x80FF0100");
+        assert_eq!(candidates[0].Version, "12.19.1.37");
+        assert_eq!(candidates[0].Package, drivePath.to_string_lossy());
+        assert_eq!(candidates[1].MatchType, "CompatibleID");
+
+        // 表格含表头，每个候选驱动一行
+        assert_eq!(reportTable(&report[0]).len(), 3);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json[0]["Candidates"][1]["MatchedID"], r"PCI\CC_0200");
+
+        // 压缩包内的索引直接从内存中加载，不解压到临时目录（索引仅包含 Lan 驱动）
+        use crate::command::create_index::{IndexFile, IndexFormat};
+        use crate::utils::archiveBackend::ArchiveBackend;
+        let infList: Vec<InfInfo> = InfInfo::parsingInfFileList(&drivePath, &getFileList(&drivePath, "*.inf").unwrap()).into_iter().filter(|item| item.Path == "Lan").collect();
+        for format in [IndexFormat::Json, IndexFormat::Binary] {
+            IndexFile::new(infList.clone(), None, false).unwrap().save(&drivePath.join("Drivers.index"), format).unwrap();
+            let packagePath = env::temp_dir().join("DriverIndexer-matchReportTest.zip");
+            let _ = std::fs::remove_file(&packagePath);
+            crate::utils::nativeArchive::NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+            let group = packageMatchGroup(&packagePath, None, None, None).unwrap();
+            let report = matchReport(&[group], &hwIdList, None, &win10);
+            assert_eq!(report[0].Candidates.len(), 1);
+            assert!(!crate::TEMP_PATH.join("DriverIndexer-matchReportTest").join("Drivers.index").exists());
+            let _ = std::fs::remove_file(&packagePath);
+        }
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 硬件清单测试
+    #[test]
+    fn inventoryTest() {
+        use crate::utils::devcon::HwID;
+        use crate::utils::devconParser::DeviceState;
+        use crate::utils::deviceBackend::openBackend;
+        use crate::utils::inventory::Inventory;
+        use crate::utils::targetOS::TargetOS;
+
+        let tempPath = env::temp_dir().join("DriverIndexer-inventoryTest");
+        std::fs::create_dir_all(&tempPath).unwrap();
+
+        let hwIdList = vec![
+            HwID {
+                DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03\3&11583659&0&FE".to_string(),
+                Name: "Ethernet Controller".to_string(),
+                HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03".to_string(), r"PCI\VEN_8086&DEV_15B8".to_string()],
+                CompatibleIDs: vec![r"PCI\CC_0200".to_string()],
+                Status: Some(DeviceState::Running),
+            },
+            HwID {
+                DeviceInstancePath: r"USB\VID_0BDA&PID_8153\000001".to_string(),
+                Name: "USB Ethernet".to_string(),
+                HardwareIDs: vec![r"USB\VID_0BDA&PID_8153".to_string()],
+                CompatibleIDs: vec![],
+                Status: Some(DeviceState::Problem(28)),
+            },
+        ];
+        let win7 = TargetOS { Arch: "NTx86".to_string(), Major: 6, Minor: 1, ProductType: 1, SuiteMask: 0, Build: 7601 };
+        let inventory = Inventory::new(&hwIdList, Some(win7.clone()));
+
+        // 保存后读取的清单与原清单一致
+        let inventoryPath = tempPath.join("inventory.json");
+        inventory.save(&inventoryPath).unwrap();
+        let loaded = Inventory::load(&inventoryPath).unwrap();
+        assert_eq!(loaded, inventory);
+
+        // 非精确匹配时仅包含有问题的设备
+        assert_eq!(loaded.deviceList(true), hwIdList);
+        assert_eq!(loaded.deviceList(false), vec![hwIdList[1].clone()]);
+
+        // 以清单中的设备、系统代替当前系统
+        let backend = openBackend(Some(&inventoryPath)).unwrap();
+        assert_eq!(backend.target(), Some(win7));
+        let statusList = backend.queryStatus(backend.devices().unwrap()).unwrap();
+        assert_eq!(statusList.iter().map(|hwID| hwID.problemCode()).collect::<Vec<_>>(), vec![None, Some(28)]);
+
+        // 未记录运行状态的清单仅能区分设备是否有问题
+        let mut legacy = inventory.clone();
+        legacy.Devices.iter_mut().for_each(|device| device.Status = None);
+        let legacyList = legacy.deviceList(true);
+        assert!(!legacyList[0].hasProblem());
+        assert!(legacyList[1].hasProblem());
+        assert_eq!(legacyList[1].problemCode(), None);
+
+        // 不支持的清单版本
+        let content = std::fs::read_to_string(&inventoryPath).unwrap().replace("\"FormatVersion\": 1", "\"FormatVersion\": 99");
+        std::fs::write(&inventoryPath, content).unwrap();
+        assert!(Inventory::load(&inventoryPath).is_err());
+        std::fs::write(&inventoryPath, "[]").unwrap();
+        assert!(Inventory::load(&inventoryPath).is_err());
+        let _ = std::fs::remove_dir_all(&tempPath);
+    }
+
+    // 模拟设备后端：安装驱动后设备正常运行，并按脚本出现新的设备（未安装驱动）
+    struct MockBackend {
+        state: std::sync::Mutex<MockState>,
+        /// 安装驱动后出现的设备（硬件id => 设备列表）
+        reveals: std::collections::HashMap<String, Vec<crate::utils::devcon::HwID>>,
+        /// 安装失败的硬件id
+        failing: Vec<String>,
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        /// 设备列表（设备，运行状态）
+        devices: Vec<(crate::utils::devcon::HwID, crate::utils::devconParser::DeviceState)>,
+        /// 安装成功的设备实例路径
+        installed: Vec<String>,
+        /// 安装失败的设备实例路径
+        failed: Vec<String>,
+        /// 扫描次数
+        rescans: usize,
+    }
+
+    impl MockBackend {
+        fn new(devices: Vec<(crate::utils::devcon::HwID, crate::utils::devconParser::DeviceState)>) -> MockBackend {
+            MockBackend {
+                state: std::sync::Mutex::new(MockState { devices, ..Default::default() }),
+                reveals: std::collections::HashMap::new(),
+                failing: Vec::new(),
+            }
+        }
+    }
+
+    impl crate::utils::deviceBackend::DeviceBackend for MockBackend {
+        fn devices(&self) -> Result<Vec<crate::utils::devcon::HwID>, Box<dyn std::error::Error>> {
+            Ok(self.state.lock().unwrap().devices.iter().map(|(hwID, _)| hwID.clone()).collect())
+        }
+
+        fn queryStatus(&self, hwIDList: Vec<crate::utils::devcon::HwID>) -> Result<Vec<crate::utils::devcon::HwID>, Box<dyn std::error::Error>> {
+            let state = self.state.lock().unwrap();
+            Ok(hwIDList
+                .into_iter()
+                .map(|mut hwID| {
+                    hwID.Status = state.devices.iter().find(|(device, _)| *device == hwID).map(|(_, status)| status.clone());
+                    hwID
+                })
+                .collect())
+        }
+
+        fn rescan(&self) -> bool {
+            self.state.lock().unwrap().rescans += 1;
+            true
+        }
+
+        fn installInf(&self, _infPath: &Path, hwId: &str) -> bool {
+            let mut state = self.state.lock().unwrap();
+            let Some(index) = state.devices.iter().position(|(device, _)| {
+                device.HardwareIDs.iter().chain(device.CompatibleIDs.iter()).any(|id| id.eq_ignore_ascii_case(hwId))
+            }) else {
+                return false;
+            };
+            let path = state.devices[index].0.DeviceInstancePath.clone();
+            if self.failing.iter().any(|id| id.eq_ignore_ascii_case(hwId)) {
+                state.failed.push(path);
+                return false;
+            }
+            state.devices[index].1 = crate::utils::devconParser::DeviceState::Running;
+            state.installed.push(path);
+            let revealed = self.reveals.get(&hwId.to_uppercase()).cloned().unwrap_or_default();
+            state.devices.extend(revealed.into_iter().map(|hwID| (hwID, crate::utils::devconParser::DeviceState::Problem(28))));
+            true
+        }
+
+        fn target(&self) -> Option<crate::utils::targetOS::TargetOS> {
+            Some(crate::utils::targetOS::TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 })
+        }
+    }
+
+    // 匹配安装流程测试（3次匹配、过滤已处理的设备、失败路径）
+    #[test]
+    fn installMatchDriversTest() {
+        use crate::command::load_driver::{installMatchDrivers, packageMatchGroup};
+        use crate::utils::devcon::HwID;
+        use crate::utils::devconParser::DeviceState;
+        use crate::utils::deviceBackend::{DeviceBackend, DeviceFilter};
+        use crate::utils::signaturePolicy::SignaturePolicy;
+        use std::sync::Arc;
+
+        let drivePath = env::temp_dir().join("DriverIndexer-installMatchDriversTest");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        let drivers = [
+            ("Chipset", "bus", r"PCI\VEN_8086&DEV_A0EF"),
+            ("Bluetooth", "bth", r"USB\VID_8087&PID_0026"),
+            ("HID", "hid", r"HID\VID_8087&PID_0A2B"),
+            ("HID", "hidchild", r"HID\VID_8087&PID_0A2C"),
+            ("Lan", "rt640x64", r"PCI\VEN_10EC&DEV_8168"),
+        ];
+        for (dir, name, id) in drivers {
+            std::fs::create_dir_all(drivePath.join(dir)).unwrap();
+            let content = format!("[Version]\nClass=System\nDriverVer=06/01/2020,1.0.0.0\nCatalogFile={}.cat\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,{}\n", name, id);
+            std::fs::write(drivePath.join(dir).join(format!("{}.inf", name)), content).unwrap();
+            std::fs::write(drivePath.join(dir).join(format!("{}.cat", name)), "cat").unwrap();
+        }
+        let group = packageMatchGroup(&drivePath, None, None, None).unwrap();
+        let device = |path: &str, id: &str| HwID {
+            DeviceInstancePath: path.to_string(),
+            Name: path.to_string(),
+            HardwareIDs: vec![id.to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        };
+
+        // 每次安装后出现下一级设备，最多匹配3次；安装失败的设备不重复安装
+        let mut mock = MockBackend::new(vec![
+            (device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28)),
+            (device(r"PCI\LAN", r"PCI\VEN_10EC&DEV_8168"), DeviceState::Problem(28)),
+            (device(r"ACPI\OK", r"ACPI\PNP0C0A"), DeviceState::Running),
+        ]);
+        mock.reveals.insert(r"PCI\VEN_8086&DEV_A0EF".to_string(), vec![device(r"USB\BTH", r"USB\VID_8087&PID_0026")]);
+        mock.reveals.insert(r"USB\VID_8087&PID_0026".to_string(), vec![device(r"HID\KBD", r"HID\VID_8087&PID_0A2B")]);
+        mock.reveals.insert(r"HID\VID_8087&PID_0A2B".to_string(), vec![device(r"HID\CHILD", r"HID\VID_8087&PID_0A2C")]);
+        mock.failing.push(r"PCI\VEN_10EC&DEV_8168".to_string());
+        let mock = Arc::new(mock);
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_ok());
+        {
+            let state = mock.state.lock().unwrap();
+            assert_eq!(state.installed, vec![r"PCI\BUS", r"USB\BTH", r"HID\KBD"]);
+            assert_eq!(state.failed, vec![r"PCI\LAN"]);
+            assert_eq!(state.rescans, 3);
+        }
+
+        // 首次匹配已全部安装，后续扫描没有新设备时正常结束
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed, vec![r"PCI\BUS"]);
+        assert_eq!(mock.state.lock().unwrap().rescans, 2);
+
+        // 仅解压时不安装驱动
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, true, None).is_ok());
+        assert!(mock.state.lock().unwrap().installed.is_empty());
+
+        // 没有匹配的驱动
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\GPU", r"PCI\VEN_10DE&DEV_1C82"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_ok());
+        assert!(mock.state.lock().unwrap().failed.is_empty());
+        assert_eq!(mock.state.lock().unwrap().rescans, 1);
+
+        // 没有设备、没有需要安装驱动的设备
+        let backend: Arc<dyn DeviceBackend> = Arc::new(MockBackend::new(vec![]));
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_err());
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Running)]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_err());
+        // 匹配所有设备时包括已安装驱动的设备
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::All, None, SignaturePolicy::Any, false, None).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed, vec![r"PCI\BUS"]);
+
+        // 仅匹配指定问题代码的设备（禁用的设备问题代码为 22）
+        let devices = vec![
+            (device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28)),
+            (device(r"PCI\LAN", r"PCI\VEN_10EC&DEV_8168"), DeviceState::Problem(10)),
+            (device(r"USB\BTH", r"USB\VID_8087&PID_0026"), DeviceState::Disabled),
+        ];
+        let mock = Arc::new(MockBackend::new(devices.clone()));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::ProblemCodes(vec![28]), None, SignaturePolicy::Any, false, None).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed, vec![r"PCI\BUS"]);
+        let mock = Arc::new(MockBackend::new(devices));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed.len(), 2);
+        assert!(!mock.state.lock().unwrap().installed.contains(&r"USB\BTH".to_string()));
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::ProblemCodes(vec![22]), None, SignaturePolicy::Any, false, None).is_ok());
+        assert!(mock.state.lock().unwrap().installed.contains(&r"USB\BTH".to_string()));
+
+        // 安装前检查驱动文件：索引后被删除的编录文件导致安装失败
+        std::fs::remove_file(drivePath.join("Chipset").join("bus.cat")).unwrap();
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, None).is_ok());
+        assert!(mock.state.lock().unwrap().installed.is_empty());
+        assert!(mock.state.lock().unwrap().failed.is_empty());
+        std::fs::write(drivePath.join("Chipset").join("bus.cat"), "cat").unwrap();
+
+        // 签名策略：编录文件无法解析的驱动视为未签名，不参与匹配
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Signed, false, None).is_ok());
+        assert!(mock.state.lock().unwrap().installed.is_empty());
+        assert_eq!(mock.state.lock().unwrap().rescans, 1);
+
+        // 驱动包使用解压缓存：首次解压到缓存，再次加载时复用缓存中的驱动目录
+        use crate::utils::archiveBackend::ArchiveBackend;
+        use crate::utils::extractCache::ExtractCache;
+        let packagePath = env::temp_dir().join("DriverIndexer-installMatchDriversTest.zip");
+        let cachePath = env::temp_dir().join("DriverIndexer-installMatchDriversTest-cache");
+        let _ = std::fs::remove_dir_all(&cachePath);
+        // 同一设备的旧版驱动（候选驱动中排在后面）
+        std::fs::create_dir_all(drivePath.join("ChipsetOld")).unwrap();
+        std::fs::write(drivePath.join("ChipsetOld").join("bus.inf"), "[Version]\nClass=System\nDriverVer=06/01/2019,0.9.0.0\nCatalogFile=bus.cat\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_A0EF\n").unwrap();
+        std::fs::write(drivePath.join("ChipsetOld").join("bus.cat"), "cat").unwrap();
+        crate::utils::nativeArchive::NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+        let group = packageMatchGroup(&packagePath, None, None, None).unwrap();
+        let mut cache = ExtractCache::open(&cachePath, 1024 * 1024).unwrap();
+        let backend: Arc<dyn DeviceBackend> = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, Some(&mut cache)).is_ok());
+        let hash = cache.packageHash(&packagePath, None).unwrap();
+        assert!(cache.packageDir(&hash).join("Chipset").join("bus.inf").exists());
+        assert_eq!(cache.Entries.len(), 1);
+        let mut cache = ExtractCache::open(&cachePath, 1024 * 1024).unwrap();
+        assert_eq!(cache.Entries[0].Path, "Chipset");
+        let mock = Arc::new(MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]));
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, Some(&mut cache)).is_ok());
+        assert_eq!(mock.state.lock().unwrap().installed, vec![r"PCI\BUS"]);
+        assert!(cache.lookup(&hash, "Chipset"));
+
+        // 仅解压每个设备的首选驱动，首选驱动安装失败时再解压其余候选驱动
+        assert!(!cache.packageDir(&hash).join("ChipsetOld").exists());
+        let mut mock = MockBackend::new(vec![(device(r"PCI\BUS", r"PCI\VEN_8086&DEV_A0EF"), DeviceState::Problem(28))]);
+        mock.failing.push(r"PCI\VEN_8086&DEV_A0EF".to_string());
+        let mock = Arc::new(mock);
+        let backend: Arc<dyn DeviceBackend> = mock.clone();
+        assert!(installMatchDrivers(&backend, &group, None, &DeviceFilter::Problem, None, SignaturePolicy::Any, false, Some(&mut cache)).is_ok());
+        assert!(cache.packageDir(&hash).join("ChipsetOld").join("bus.inf").exists());
+        assert_eq!(mock.state.lock().unwrap().failed, vec![r"PCI\BUS", r"PCI\BUS"]);
+        let _ = std::fs::remove_dir_all(&cachePath);
+        let _ = std::fs::remove_file(&packagePath);
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // 导入驱动时匹配设备测试（压缩包按文件列表检查驱动文件，仅解压匹配的驱动目录）
+    #[test]
+    fn importMatchDeviceTest() {
+        use crate::command::import_driver::matchDeviceDrivers;
+        use crate::utils::archiveBackend::ArchiveBackend;
+        use crate::utils::devcon::HwID;
+        use crate::utils::devconParser::DeviceState;
+        use crate::utils::signaturePolicy::SignaturePolicy;
+        use crate::utils::targetOS::TargetOS;
+
+        let drivePath = env::temp_dir().join("DriverIndexer-importMatchDeviceTest");
+        let packagePath = env::temp_dir().join("DriverIndexer-importMatchDeviceTest.zip");
+        let extractPath = env::temp_dir().join("DriverIndexer-importMatchDeviceTest-extract");
+        let _ = std::fs::remove_dir_all(&drivePath);
+        let _ = std::fs::remove_dir_all(&extractPath);
+        for (dir, name, id) in [("Chipset", "bus", r"PCI\VEN_8086&DEV_A0EF"), ("Lan", "rt640x64", r"PCI\VEN_10EC&DEV_8168")] {
+            std::fs::create_dir_all(drivePath.join(dir)).unwrap();
+            let content = format!("[Version]\nClass=System\nDriverVer=06/01/2020,1.0.0.0\nCatalogFile={0}.cat\n[SourceDisksNames]\n1=%Disk%\n[SourceDisksFiles]\n{0}.sys=1\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,{1}\n[Install.NTamd64]\nCopyFiles=@{0}.sys\n", name, id);
+            std::fs::write(drivePath.join(dir).join(format!("{}.inf", name)), content).unwrap();
+            std::fs::write(drivePath.join(dir).join(format!("{}.cat", name)), "cat").unwrap();
+            std::fs::write(drivePath.join(dir).join(format!("{}.sys", name)), "sys").unwrap();
+        }
+        let _ = std::fs::remove_file(&packagePath);
+        crate::utils::nativeArchive::NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+        let device = HwID {
+            DeviceInstancePath: r"PCI\BUS".to_string(),
+            Name: r"PCI\BUS".to_string(),
+            HardwareIDs: vec![r"PCI\VEN_8086&DEV_A0EF".to_string()],
+            CompatibleIDs: vec![],
+            Status: None,
+        };
+        let backend = MockBackend::new(vec![(device, DeviceState::Problem(28))]);
+        let target = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+
+        // 驱动文件未解压时仍视为完整，仅解压匹配的驱动目录
+        let infList = matchDeviceDrivers(&backend, &packagePath, None, &extractPath, SignaturePolicy::Any, &target).unwrap();
+        assert_eq!(infList, vec![extractPath.join("Chipset").join("bus.inf")]);
+        assert!(extractPath.join("Chipset").join("bus.sys").exists());
+        assert!(!extractPath.join("Lan").exists());
+
+        // 驱动目录与压缩包的匹配结果一致
+        let infList = matchDeviceDrivers(&backend, &drivePath, None, &drivePath, SignaturePolicy::Any, &target).unwrap();
+        assert_eq!(infList, vec![drivePath.join("Chipset").join("bus.inf")]);
+
+        // 压缩包中缺少驱动文件的驱动不参与匹配
+        std::fs::remove_file(drivePath.join("Chipset").join("bus.sys")).unwrap();
+        std::fs::remove_file(&packagePath).unwrap();
+        crate::utils::nativeArchive::NativeArchive::new().create(&drivePath, &packagePath).unwrap();
+        assert!(matchDeviceDrivers(&backend, &packagePath, None, &extractPath, SignaturePolicy::Any, &target).is_err());
+        let _ = std::fs::remove_file(&packagePath);
+        let _ = std::fs::remove_dir_all(&extractPath);
+        let _ = std::fs::remove_dir_all(&drivePath);
+    }
+
+    // devcon 输出解析测试（testDrive/devcon 下的输出及对应的 JSON 解析结果）
+    #[test]
+    fn devconParserTest() {
+        use crate::utils::devconParser::{decodeOutput, parseHwids, parseStatus};
+
+        let fixturePath = Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("devcon");
+        let mut count = 0;
+        for entry in std::fs::read_dir(&fixturePath).unwrap() {
+            let outputPath = entry.unwrap().path();
+            if outputPath.extension().unwrap_or_default() != "txt" {
+                continue;
+            }
+            let content = decodeOutput(&std::fs::read(&outputPath).unwrap());
+            let expected: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(outputPath.with_extension("json")).unwrap()).unwrap();
+            let name = outputPath.file_name().unwrap().to_string_lossy().to_string();
+            // 换行符不影响解析结果
+            for content in [content.clone(), content.replace("\r\n", "\n")] {
+                let parsed = if name.starts_with("hwids") {
+                    serde_json::to_value(parseHwids(&content)).unwrap()
+                } else {
+                    serde_json::to_value(parseStatus(&content)).unwrap()
+                };
+                assert_eq!(parsed, expected, "{}", name);
+            }
+            count += 1;
+        }
+        assert!(count > 0);
+
+        // 问题代码
+        let statusList = parseStatus(&std::fs::read_to_string(fixturePath.join("status-en.txt")).unwrap());
+        let problemList: Vec<Option<u32>> = statusList.iter().map(|status| status.problemCode()).collect();
+        assert_eq!(problemList, vec![None, Some(28), Some(10), Some(1), Some(22), None, None, None]);
+        assert_eq!(statusList.iter().filter(|status| status.hasProblem()).count(), 4);
+
+        // 本地化版本的 GBK（代码页 936）输出
+        let output = std::fs::read(fixturePath.join("status-gbk.txt")).unwrap();
+        assert!(std::str::from_utf8(&output).is_err());
+        let content = decodeOutput(&output);
+        assert!(content.contains("名称: 标准 VGA 图形适配器") && content.contains("找到 3 个匹配的设备。"));
+        let nameList: Vec<String> = parseStatus(&content).into_iter().map(|status| status.Name).collect();
+        assert_eq!(nameList, vec!["标准 VGA 图形适配器", "高清晰度音频设备", "通用串行总线网络适配器"]);
+    }
+
+    // 离线注册表测试（testDrive/hive/SYSTEM 为当前控制集是 ControlSet002 的示例配置单元）
+    #[test]
+    fn registryHiveTest() {
+        use crate::utils::devconParser::DeviceState;
+        use crate::utils::deviceBackend::{DeviceBackend, DeviceFilter};
+        use crate::utils::offlineRegistry::OfflineRegistry;
+        use crate::utils::registryHive::{RegistryHive, RegistryValue};
+
+        let hivePath = Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("hive").join("SYSTEM");
+        let hive = RegistryHive::open(&hivePath).unwrap();
+        let root = hive.root();
+
+        // 项、值（不区分大小写）
+        assert_eq!(root.subkeys().iter().map(|key| key.name()).collect::<Vec<_>>(), vec!["ControlSet001", "ControlSet002", "Select"]);
+        assert_eq!(root.openKey("select").unwrap().getDword("CURRENT"), Some(2));
+        assert_eq!(root.openKey("Select").unwrap().getString(""), Some("default".to_string()));
+        assert!(root.openKey(r"ControlSet002\Services").is_none());
+        let control = root.openKey(r"\ControlSet002\Control\").unwrap();
+        assert_eq!(control.getString("Path"), Some(r"%SystemRoot%\System32".to_string()));
+        assert_eq!(control.value("Qword"), Some(RegistryValue::Qword(0x0123456789ABCDEF)));
+        // 分段保存的大数据
+        let Some(RegistryValue::Binary(data)) = control.value("BigData") else { panic!() };
+        assert_eq!(data, (0..40000).map(|index| (index % 251) as u8).collect::<Vec<u8>>());
+
+        // 当前控制集中的设备（ri、li 子项列表，UTF-16 名称）
+        let deviceList = OfflineRegistry::enumDevices(&hive).unwrap();
+        let pathList: Vec<&str> = deviceList.iter().map(|device| device.Device.DeviceInstancePath.as_str()).collect();
+        assert_eq!(
+            pathList,
+            vec![
+                r"PCI\VEN_8086&DEV_15B8&SUBSYS_86721043&REV_31\3&11583659&0&FE",
+                r"PCI\VEN_10EC&DEV_8168&SUBSYS_86771043&REV_15\4&2b1a2c3d&0&00E4",
+                r"PCI\VEN_8086&DEV_A170&SUBSYS_86C71043&REV_31\3&11583659&0&FB",
+                r"PCI\VEN_10DE&DEV_1C82&SUBSYS_85B61043&REV_A1\4&1d4e5e5d&0&0008",
+                r"USB\VID_0BDA&PID_8153\000001",
+                r"ROOT\LEGACY_BEEP\0000",
+            ]
+        );
+        let intel = &deviceList[0];
+        assert_eq!(intel.Device.Name, "Intel(R) Ethernet Connection (2) I219-V");
+        assert_eq!(intel.Device.HardwareIDs, vec![r"PCI\VEN_8086&DEV_15B8&SUBSYS_86721043&REV_31", r"PCI\VEN_8086&DEV_15B8&SUBSYS_86721043"]);
+        assert_eq!(intel.Device.CompatibleIDs.len(), 4);
+        assert_eq!(intel.Driver.as_deref(), Some(r"{4d36e972-e325-11ce-bfc1-08002be10318}\0001"));
+        assert_eq!(deviceList[1].Device.Name, "Realtek PCIe GbE");
+        assert_eq!(deviceList[4].ConfigFlags, 0x40);
+        assert!(deviceList[5].Device.HardwareIDs.is_empty());
+        let stateList: Vec<DeviceState> = deviceList.iter().map(|device| device.state()).collect();
+        assert_eq!(
+            stateList,
+            vec![
+                DeviceState::Stopped,
+                DeviceState::Disabled,
+                DeviceState::Problem(28),
+                DeviceState::Problem(28),
+                DeviceState::Problem(28),
+                DeviceState::Problem(28),
+            ]
+        );
+
+        // 作为设备后端
+        let backend = OfflineRegistry { Devices: deviceList, Target: None };
+        let hwIDList = backend.queryStatus(backend.devices().unwrap()).unwrap();
+        assert_eq!(hwIDList.iter().filter(|hwID| DeviceFilter::Problem.isMatch(hwID)).count(), 4);
+        assert_eq!(hwIDList.iter().filter(|hwID| DeviceFilter::ProblemCodes(vec![22]).isMatch(hwID)).count(), 1);
+
+        // 损坏的配置单元
+        let data = std::fs::read(&hivePath).unwrap();
+        assert!(RegistryHive::fromBytes(data[..0x800].to_vec()).is_err());
+        let mut invalidRoot = data.clone();
+        invalidRoot[0x24..0x28].copy_from_slice(&0x7FFF_0000u32.to_le_bytes());
+        assert!(RegistryHive::fromBytes(invalidRoot).is_err());
+        assert!(RegistryHive::fromBytes(data[..data.len() / 2].to_vec()).is_err());
+        // 大数据的值大小超出各分段的总大小
+        let mut invalidSize = data.clone();
+        let nameOffset = invalidSize.windows(7).position(|name| name == b"BigData").unwrap();
+        invalidSize[nameOffset - 16..nameOffset - 12].copy_from_slice(&0x7FFF_0000u32.to_le_bytes());
+        let invalidHive = RegistryHive::fromBytes(invalidSize).unwrap();
+        assert_eq!(invalidHive.root().openKey(r"ControlSet002\Control").unwrap().value("BigData"), None);
+        assert!(OfflineRegistry::open(&env::temp_dir().join("DriverIndexer-registryHiveTest")).is_err());
+    }
+
+    // 驱动数据库测试（testDrive/hive 下的 DRIVERS 与 SYSTEM 配置单元）
+    #[test]
+    fn driverDatabaseTest() {
+        use crate::utils::driverDatabase::DriverDatabase;
+        use crate::utils::registryHive::RegistryHive;
+
+        let hivePath = Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("hive");
+        let drivers = RegistryHive::open(&hivePath.join("DRIVERS")).unwrap();
+        let system = RegistryHive::open(&hivePath.join("SYSTEM")).unwrap();
+        let database = DriverDatabase::fromHives(&drivers, &system).unwrap();
+
+        let names: Vec<&str> = database.Packages.iter().map(|package| package.PublishedName.as_str()).collect();
+        assert_eq!(names, vec!["oem7.inf", "oem9.inf", "oem15.inf", "oem20.inf", "hdaudio.inf", "oem30.inf", "oem31.inf", "oem32.inf", "oem33.inf"]);
+        let rtl = &database.Packages[1];
+        assert_eq!(rtl.PackageId, "rt640x64.inf_amd64_1a2b3c4d5e6f7a8b");
+        assert_eq!(rtl.Provider, "Realtek");
+        assert_eq!(rtl.Class, "{4d36e972-e325-11ce-bfc1-08002be10318}");
+        // 多级子项组成的硬件id
+        assert_eq!(rtl.HardwareIDs, vec![r"PCI\VEN_10EC&DEV_8168"]);
+        assert_eq!(database.Packages[3].HardwareIDs, vec![r"PCI\VEN_10EC&DEV_8168"]);
+        // 已禁用的设备仍在使用驱动
+        assert_eq!(rtl.Devices, vec![r"PCI\VEN_10EC&DEV_8168&SUBSYS_86771043&REV_15\4&2b1a2c3d&0&00E4"]);
+
+        assert!(database.isInUse("OEM7.INF"));
+        assert!(database.isInUse("hdaudio.inf"));
+        assert!(!database.isInUse("oem15.inf"));
+        assert!(!database.isInUse("machine.inf"));
+        // 扩展INF不记录在设备的驱动项中，支持设备的硬件id即被该设备使用
+        assert_eq!(database.Packages[5].Devices, rtl.Devices);
+        assert!(database.isInUse("oem30.inf"));
+        assert!(!database.isInUse("oem31.inf"));
+        // 系统自带驱动、打印机驱动、不支持硬件id的驱动不会被视为未使用的驱动
+        let unused: Vec<&str> = database.unusedPackages().iter().map(|package| package.PublishedName.as_str()).collect();
+        assert_eq!(unused, vec!["oem15.inf", "oem20.inf", "oem31.inf"]);
+
+        // SYSTEM 配置单元不是驱动数据库
+        assert!(DriverDatabase::fromHives(&system, &system).is_none());
+        assert!(DriverDatabase::open(&env::temp_dir().join("DriverIndexer-driverDatabaseTest")).is_err());
+    }
+
+    // 7-zip 列表解析测试
+    #[test]
+    fn sevenZipParserTest() {
+        use crate::utils::archiveBackend::{ArchiveEntry, FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_READONLY};
+        use crate::utils::sevenZipParser::parseTechnicalListing;
+
+        let content = std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("testDrive").join("7z").join("list-slt.txt")).unwrap();
+        // 换行符不影响解析结果
+        for content in [content.clone(), content.replace("\r\n", "\n")] {
+            let entryList = parseTechnicalListing(&content);
+            let pathList: Vec<&str> = entryList.iter().map(|entry| entry.Path.as_str()).collect();
+            assert_eq!(pathList, vec!["Intel", "Intel/LAN/e1d.inf", "Intel/LAN/readme.inform.txt", "Realtek/rt640x64.inf"]);
+            assert_eq!(
+                entryList[1],
+                ArchiveEntry {
+                    Path: "Intel/LAN/e1d.inf".to_string(),
+                    Size: 84126,
+                    IsDir: false,
+                    Modified: Some(1683878400),
+                    Crc: Some(0x4A17B156),
+                    Attributes: FILE_ATTRIBUTE_ARCHIVE,
+                    Encrypted: true,
+                    Block: Some(0),
+                }
+            );
+            assert!(entryList[0].IsDir && entryList[0].Crc.is_none() && entryList[0].Block.is_none());
+            assert_eq!(entryList[0].Attributes, FILE_ATTRIBUTE_DIRECTORY);
+            assert_eq!(entryList[2].Attributes, FILE_ATTRIBUTE_READONLY | FILE_ATTRIBUTE_ARCHIVE);
+            assert_eq!(entryList[3].Block, Some(1));
+
+            // 仅扩展名为 .inf 的文件为INF
+            let infList: Vec<&str> = entryList.iter().filter(|entry| entry.isInf()).map(|entry| entry.Path.as_str()).collect();
+            assert_eq!(infList, vec!["Intel/LAN/e1d.inf", "Realtek/rt640x64.inf"]);
+        }
+        assert!(parseTechnicalListing("Can't open as archive: 1").is_empty());
+    }
+
+    // 内置压缩包测试
+    #[test]
+    fn nativeArchiveTest() {
+        use crate::command::create_index::{createIndex, IndexFile, IndexFormat};
+        use crate::utils::archiveBackend::{entryOutPath, matchPattern, ArchiveBackend, ArchiveFormat};
+        use crate::utils::cabinet::{Cabinet, COMPRESS_LZX, COMPRESS_MSZIP, COMPRESS_NONE};
+        use crate::utils::nativeArchive::NativeArchive;
+        use crate::utils::util::isArchive;
+        use std::io::{Read, Write};
+
+        let tempPath = env::temp_dir().join("DriverIndexer-nativeArchiveTest");
+        std::fs::remove_dir_all(&tempPath).ok();
+        let driverPath = tempPath.join("Drivers");
+        std::fs::create_dir_all(driverPath.join("Intel").join("LAN")).unwrap();
+        std::fs::create_dir_all(driverPath.join("Realtek")).unwrap();
+        std::fs::write(driverPath.join("Intel").join("LAN").join("e1d.inf"), "[Version]\nClass=Net\n").unwrap();
+        std::fs::write(driverPath.join("Intel").join("LAN").join("e1d.sys"), vec![7u8; 4096]).unwrap();
+        std::fs::write(driverPath.join("Realtek").join("rt640x64.inf"), "[Version]\nClass=Net\n").unwrap();
+        std::fs::write(driverPath.join("Drivers.index"), "[]").unwrap();
+
+        // 通配符
+        assert!(matchPattern("Intel/LAN/e1d.inf", "*.INF"));
+        assert!(matchPattern("Intel/LAN/e1d.sys", "*"));
+        assert!(matchPattern("Intel/LAN/e1d.sys", r"intel\lan"));
+        assert!(matchPattern("Intel/LAN/e1d.sys", "LAN"));
+        assert!(!matchPattern("Intel/LAN/e1d.sys", "LAN/e1d.sys"));
+        assert!(!matchPattern("Intel/LAN/e1d.sys", "*.inf"));
+        assert_eq!(entryOutPath(&tempPath, "Intel/./e1d.inf"), Some(tempPath.join("Intel").join("e1d.inf")));
+        assert_eq!(entryOutPath(&tempPath, "../e1d.inf"), None);
+
+        // 创建、识别、列出、解压（ZIP、TAR、TAR.GZ、TAR.XZ）
+        let native = NativeArchive::new();
+        let tarPath = tempPath.join("Drivers.tar");
+        native.create(&driverPath, &tarPath).unwrap();
+        let xzPath = tempPath.join("Drivers.bin");
+        let mut writer = lzma_rust2::XzWriter::new(File::create(&xzPath).unwrap(), lzma_rust2::XzOptions::default()).unwrap();
+        std::io::copy(&mut File::open(&tarPath).unwrap(), &mut writer).unwrap();
+        writer.finish().unwrap();
+        for (name, format) in [("Drivers.zip", ArchiveFormat::Zip), ("Drivers.tar", ArchiveFormat::Tar), ("Drivers.tgz", ArchiveFormat::TarGz), ("Drivers.bin", ArchiveFormat::TarXz)] {
+            let archivePath = tempPath.join(name);
+            if !archivePath.exists() {
+                assert!(native.create(&driverPath, &archivePath).unwrap());
+            }
+            assert_eq!(ArchiveFormat::detect(&archivePath), Some(format));
+            assert!(native.isDriverPackage(&archivePath).unwrap());
+            let mut pathList: Vec<String> = native.list(&archivePath, None).unwrap().into_iter().filter(|entry| !entry.IsDir).map(|entry| entry.Path).collect();
+            pathList.sort();
+            assert_eq!(pathList, vec!["Drivers.index", "Intel/LAN/e1d.inf", "Intel/LAN/e1d.sys", "Realtek/rt640x64.inf"]);
+
+            let outPath = tempPath.join(format!("{}-out", name));
+            assert!(native.extractPattern(&archivePath, None, "*.inf", &outPath).unwrap());
+            assert!(outPath.join("Intel").join("LAN").join("e1d.inf").exists() && outPath.join("Realtek").join("rt640x64.inf").exists());
+            assert!(!outPath.join("Intel").join("LAN").join("e1d.sys").exists());
+            assert!(native.extractPattern(&archivePath, None, "Intel/LAN", &outPath).unwrap());
+            assert_eq!(std::fs::read(outPath.join("Intel").join("LAN").join("e1d.sys")).unwrap(), vec![7u8; 4096]);
+            assert!(native.extractEntry(&archivePath, None, "Drivers.index", &outPath).unwrap());
+            assert!(outPath.join("Drivers.index").exists());
+            assert!(!native.extractPattern(&archivePath, None, "*.cat", &outPath).unwrap());
+
+            // 批量解压多个驱动目录
+            let batchPath = tempPath.join(format!("{}-batch", name));
+            assert!(native.extractPatterns(&archivePath, None, &["Intel\\LAN".to_string(), "Realtek".to_string(), "Missing".to_string()], &batchPath).unwrap());
+            assert!(batchPath.join("Intel").join("LAN").join("e1d.sys").exists() && batchPath.join("Realtek").join("rt640x64.inf").exists());
+            assert!(!batchPath.join("Drivers.index").exists());
+            assert!(!native.extractPatterns(&archivePath, None, &["*.cat".to_string()], &batchPath).unwrap());
+        }
+        assert!(native.create(&driverPath, &tempPath.join("Drivers.7z")).is_err());
+
+        // 文件信息、读取文件内容
+        let zipPath = tempPath.join("Drivers.zip");
+        let entry = native.list(&zipPath, None).unwrap().into_iter().find(|entry| entry.Path == "Intel/LAN/e1d.sys").unwrap();
+        assert_eq!(entry.Crc, Some({
+            let mut crc = flate2::Crc::new();
+            crc.update(&[7u8; 4096]);
+            crc.sum()
+        }));
+        assert!(entry.Modified.is_some() && !entry.Encrypted);
+        let fileList = native.readFiles(&zipPath, None, "*.inf").unwrap();
+        let infList: Vec<(&str, &[u8])> = fileList.iter().map(|(entry, content)| (entry.Path.as_str(), content.as_slice())).collect();
+        assert_eq!(infList, vec![("Intel/LAN/e1d.inf", &b"[Version]\nClass=Net\n"[..]), ("Realtek/rt640x64.inf", &b"[Version]\nClass=Net\n"[..])]);
+
+        // 从压缩包创建索引（直接读取压缩包中的INF）
+        std::fs::write(driverPath.join("Intel").join("LAN").join("e1d.inf"), "[Version]\nClass=Net\n[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n%Dev%=Install,PCI\\VEN_8086&DEV_15B8\n").unwrap();
+        let packagePath = tempPath.join("Lan.zip");
+        native.create(&driverPath, &packagePath).unwrap();
+        createIndex(&packagePath, None, Path::new("Lan.index"), Some(IndexFormat::Json), false).unwrap();
+        let index = IndexFile::load(&tempPath.join("Lan.index")).unwrap();
+        assert_eq!(index.Entries.len(), 1);
+        assert_eq!(index.Entries[0].Path, format!("Intel{}LAN", std::path::MAIN_SEPARATOR));
+        assert_eq!(index.Entries[0].DriverList[0].Id, r"PCI\VEN_8086&DEV_15B8");
+        assert!(index.Entries[0].Size > 0 && index.Entries[0].Modified > 0);
+        assert!(index.verifyPackage(&packagePath).is_ok());
+        // 即时建立索引（流式读取，解析后不保留内容）
+        let mut visited = Vec::new();
+        native.visitFiles(&packagePath, None, "*.inf", &mut |entry, content| {
+            visited.push((entry.Path, content.len()));
+            Ok(())
+        }).unwrap();
+        assert_eq!(visited.len(), 2);
+        assert!(native.visitFiles(&packagePath, None, "*.inf", &mut |_, _| Err("stop".into())).is_err());
+        let infInfoList = InfInfo::parsingInfArchive(&packagePath, None).unwrap();
+        assert_eq!(infInfoList.len(), 2);
+        assert!(infInfoList.iter().any(|item| item.Inf == "e1d.inf" && item.DriverList.len() == 1));
+
+        // 扩展名不代表格式
+        let fakePath = tempPath.join("fake.zip");
+        std::fs::write(&fakePath, "not an archive").unwrap();
+        assert!(!isArchive(&fakePath));
+        assert!(isArchive(&tempPath.join("Drivers.bin")));
+
+        // 内置实现仅支持未压缩或 Deflate 压缩、ZipCrypto 加密的 ZIP，其余交由 7-zip；解压失败时返回错误
+        // 创建 ZIP 后修改中央目录中的 通用标志（偏移 8）或压缩方法（偏移 10）
+        let writeZip = |name: &str, options: zip::write::FullFileOptions, offset: usize, value: u16| {
+            let path = tempPath.join(name);
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+            writer.start_file("Intel/e1d.inf", options.compression_method(zip::CompressionMethod::Stored)).unwrap();
+            writer.write_all(b"[Version]\r\nClass=Net\r\n").unwrap();
+            let mut content = writer.finish().unwrap().into_inner();
+            let central = content.windows(4).position(|window| window == b"PK\x01\x02").unwrap();
+            content[central + offset..central + offset + 2].copy_from_slice(&value.to_le_bytes());
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+        let cryptPath = writeZip("crypt.zip", Default::default(), 8, 0x0001);
+        assert!(NativeArchive::supports(&cryptPath, ArchiveFormat::Zip));
+        assert!(native.extractPattern(&cryptPath, None, "*.inf", &tempPath.join("crypt")).is_err());
+        // AES 扩展字段：AE-2、AES-256、Deflate
+        let mut options = zip::write::FullFileOptions::default();
+        options.add_extra_data(0x9901, Box::new([0x02, 0x00, 0x41, 0x45, 0x03, 0x08, 0x00]), false).unwrap();
+        assert!(!NativeArchive::supports(&writeZip("aes.zip", options, 8, 0x0001), ArchiveFormat::Zip));
+        assert!(!NativeArchive::supports(&writeZip("bzip2.zip", Default::default(), 10, 12), ArchiveFormat::Zip));
+        assert!(NativeArchive::supports(&zipPath, ArchiveFormat::Zip));
+
+        // CAB：文件夹 0 未压缩，文件夹 1 为 MSZIP 压缩的两个数据块，第二块引用第一块的内容
+        let text: Vec<u8> = (0..300).map(|index| b"0123456789ABCDEF"[index % 16] ^ (index / 16) as u8).collect();
+        let mut bits: Vec<bool> = Vec::new();
+        let mut push = |value: u32, count: u32, msbFirst: bool| {
+            for index in 0..count {
+                bits.push((value >> if msbFirst { count - 1 - index } else { index }) & 1 == 1);
+            }
+        };
+        // 固定哈夫曼块：长度 258（285）、距离 300（16 + 7 位 43）、块结束
+        push(1, 1, false);
+        push(1, 2, false);
+        push(0b1100_0101, 8, true);
+        push(16, 5, true);
+        push(300 - 257, 7, false);
+        push(0, 7, true);
+        let referenceBlock: Vec<u8> = bits.chunks(8).map(|chunk| chunk.iter().enumerate().map(|(index, bit)| (*bit as u8) << index).sum::<u8>()).collect();
+        let mszip = |data: Vec<u8>| [b"CK".to_vec(), data].concat();
+        let inf = b"[Version]\r\nClass=Net\r\n".to_vec();
+        // 文件夹为 压缩类型、数据块列表（压缩数据、解压大小）
+        type FolderList = Vec<(u16, Vec<(Vec<u8>, usize)>)>;
+        let folderList: FolderList = vec![
+            (COMPRESS_NONE, vec![(inf.clone(), inf.len())]),
+            (COMPRESS_MSZIP, vec![(mszip(miniz_oxide::deflate::compress_to_vec(&text, 6)), 300), (mszip(referenceBlock), 258)]),
+        ];
+        let fileList: Vec<(&str, u32, u16, u32)> = vec![(r"Intel\e1d.inf", inf.len() as u32, 0, 0), (r"Intel\e1d.sys", 400, 1, 100), ("readme.txt", 100, 1, 0)];
+        let buildCab = |folderList: &FolderList| {
+            let mut cab = b"MSCF".to_vec();
+            cab.resize(36, 0);
+            let filesOffset = 36 + folderList.len() * 8;
+            cab[16..20].copy_from_slice(&(filesOffset as u32).to_le_bytes());
+            cab[24..26].copy_from_slice(&[3, 1]);
+            cab[26..28].copy_from_slice(&(folderList.len() as u16).to_le_bytes());
+            cab[28..30].copy_from_slice(&(fileList.len() as u16).to_le_bytes());
+            let mut dataOffset = filesOffset + fileList.iter().map(|(name, ..)| 17 + name.len()).sum::<usize>();
+            let mut dataList: Vec<u8> = Vec::new();
+            for (compression, blockList) in folderList {
+                cab.extend((dataOffset as u32).to_le_bytes());
+                cab.extend((blockList.len() as u16).to_le_bytes());
+                cab.extend(compression.to_le_bytes());
+                for (block, size) in blockList {
+                    dataList.extend([0u8; 4]);
+                    dataList.extend((block.len() as u16).to_le_bytes());
+                    dataList.extend((*size as u16).to_le_bytes());
+                    dataList.extend(block);
+                    dataOffset += 8 + block.len();
+                }
+            }
+            for (name, size, folder, offset) in &fileList {
+                cab.extend(size.to_le_bytes());
+                cab.extend(offset.to_le_bytes());
+                cab.extend(folder.to_le_bytes());
+                cab.extend([0u8; 6]);
+                cab.extend(name.as_bytes());
+                cab.push(0);
+            }
+            [cab, dataList].concat()
+        };
+
+        let cabPath = tempPath.join("Drivers.cab");
+        std::fs::write(&cabPath, buildCab(&folderList)).unwrap();
+        assert_eq!(ArchiveFormat::detect(&cabPath), Some(ArchiveFormat::Cab));
+        let mut cabinet = Cabinet::open(&cabPath).unwrap();
+        assert!(cabinet.isSupported());
+        let mut content = Vec::new();
+        cabinet.readFolder(1).unwrap().read_to_end(&mut content).unwrap();
+        assert_eq!(&content[..300], &text[..]);
+        assert_eq!(&content[300..], &text[..258]);
+        let pathList: Vec<String> = native.list(&cabPath, None).unwrap().into_iter().map(|entry| entry.Path).collect();
+        assert_eq!(pathList, vec!["Intel/e1d.inf", "Intel/e1d.sys", "readme.txt"]);
+        let outPath = tempPath.join("cab-out");
+        assert!(native.extractPattern(&cabPath, None, "intel", &outPath).unwrap());
+        assert_eq!(std::fs::read(outPath.join("Intel").join("e1d.inf")).unwrap(), inf);
+        assert_eq!(std::fs::read(outPath.join("Intel").join("e1d.sys")).unwrap(), content[100..500].to_vec());
+        assert!(!outPath.join("readme.txt").exists());
+
+        // LZX 压缩的 CAB 使用 7-zip
+        let mut lzxFolderList = folderList.clone();
+        lzxFolderList[1].0 = COMPRESS_LZX | 0x1500;
+        std::fs::write(&cabPath, buildCab(&lzxFolderList)).unwrap();
+        assert!(!Cabinet::open(&cabPath).unwrap().isSupported());
+
+        std::fs::remove_dir_all(&tempPath).ok();
+    }
+
+    // 驱动库读取测试
+    #[test]
+    fn driverRepositoryTest() {
+        use crate::command::export_driver::{driverTable, formatSize};
+        use crate::utils::driverRepository::scanDriverStore;
+
+        let systemDrive = env::temp_dir().join("DriverIndexer-driverRepositoryTest");
+        std::fs::remove_dir_all(&systemDrive).ok();
+        let infPath = systemDrive.join("Windows").join("INF");
+        let repositoryPath = systemDrive.join("Windows").join("System32").join("DriverStore").join("FileRepository");
+        std::fs::create_dir_all(&infPath).unwrap();
+
+        let newPackage = |folder: &str, inf: &str, content: &str| {
+            let folderPath = repositoryPath.join(folder);
+            std::fs::create_dir_all(&folderPath).unwrap();
+            std::fs::write(folderPath.join(inf), content).unwrap();
+            std::fs::write(folderPath.join("driver.sys"), vec![0u8; 2048]).unwrap();
+        };
+        let rtl = "[Version]\nClass=Net\nProvider=%Realtek%\nDriverVer=06/01/2023,10.68.815.2023\n[Strings]\nRealtek=\"Realtek\"\n";
+        let e1d = "[Version]\nClass=Net\nProvider=%Intel%\nDriverVer=03/02/2021,12.19.1.37\n[Strings]\nIntel=\"Intel\"\n";
+        newPackage("rt640x64.inf_amd64_1a2b3c4d5e6f7a8b", "rt640x64.inf", rtl);
+        newPackage("e1d.inf_amd64_0f1e2d3c4b5a6978", "e1d.inf", e1d);
+        newPackage("machine.inf_amd64_b12c03e4d2a4f1a2", "machine.inf", "[Version]\nClass=System\nProvider=%MSFT%\nDriverVer=06/21/2006,10.0.19041.1\n[Strings]\nMSFT=\"Microsoft\"\n");
+        // 目录名称与INF文件名不符
+        newPackage("ocdrv.inf_x86_0000000000000000", "readme.inf", "[Version]\nClass=Printer\n");
+        std::fs::write(infPath.join("oem7.inf"), rtl).unwrap();
+        std::fs::write(infPath.join("OEM12.INF"), e1d).unwrap();
+        std::fs::write(infPath.join("machine.inf"), "[Version]\nClass=System\n").unwrap();
+
+        let packageList = scanDriverStore(&systemDrive).unwrap();
+        let names: Vec<(&str, &str, bool)> = packageList.iter().map(|package| (package.PublishedName.as_str(), package.Inf.as_str(), package.Inbox)).collect();
+        assert_eq!(names, vec![("oem7.inf", "rt640x64.inf", false), ("OEM12.INF", "e1d.inf", false), ("machine.inf", "machine.inf", true), ("readme.inf", "readme.inf", true)]);
+        let rtlPackage = &packageList[0];
+        assert_eq!((rtlPackage.Class.as_str(), rtlPackage.Provider.as_str()), ("Net", "Realtek"));
+        assert_eq!((rtlPackage.Date.as_str(), rtlPackage.Version.as_str()), ("06/01/2023", "10.68.815.2023"));
+        assert_eq!(rtlPackage.Size, rtl.len() as u64 + 2048);
+        assert_eq!(rtlPackage.infPath(), repositoryPath.join("rt640x64.inf_amd64_1a2b3c4d5e6f7a8b").join("rt640x64.inf"));
+        assert!(packageList[1].isNamed("oem12.inf") && packageList[1].isNamed("E1D.inf"));
+
+        // 表格（含表头）
+        let table = driverTable(&packageList);
+        assert_eq!(table.len(), 5);
+        assert_eq!(table[1].split_whitespace().take(3).collect::<Vec<_>>(), vec!["oem7.inf", "rt640x64.inf", "Net"]);
+        assert_eq!(formatSize(512), "512 B");
+        assert_eq!(formatSize(1536), "1.5 KB");
+        assert_eq!(formatSize(5 * 1024 * 1024), "5.0 MB");
+
+        // 驱动库不存在
+        assert!(scanDriverStore(&systemDrive.join("missing")).is_err());
+
+        std::fs::remove_dir_all(&systemDrive).ok();
+    }
+
+    // 解压缓存测试（校验、按最近使用时间删除、清空）
+    #[test]
+    fn extractCacheTest() {
+        use crate::utils::extractCache::ExtractCache;
+
+        let cachePath = env::temp_dir().join("DriverIndexer-extractCacheTest");
+        let _ = std::fs::remove_dir_all(&cachePath);
+        let packagePath = Path::new("Drivers.7z");
+        let (first, second) = ("a".repeat(64), "b".repeat(64));
+        let write = |cache: &ExtractCache, hash: &str, path: &str, size: usize| {
+            let dir = cache.packageDir(hash).join(path);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("driver.sys"), vec![0u8; size]).unwrap();
+        };
+
+        // 记录后可复用，文件被修改后视为无效
+        let mut cache = ExtractCache::open(&cachePath, 1000).unwrap();
+        write(&cache, &first, "Intel", 300);
+        write(&cache, &first, "Realtek", 300);
+        cache.record(packagePath, &first, &["Intel".to_string(), "Realtek".to_string()]);
+        cache.save().unwrap();
+        let mut cache = ExtractCache::open(&cachePath, 1000).unwrap();
+        assert_eq!(cache.totalSize(), 600);
+        assert!(cache.lookup(&first, "intel"));
+        assert!(!cache.lookup(&second, "Intel"));
+        std::fs::write(cache.packageDir(&first).join("Realtek").join("driver.sys"), b"changed").unwrap();
+        assert!(!cache.lookup(&first, "Realtek"));
+        assert_eq!(cache.Entries.len(), 1);
+        assert!(!cache.packageDir(&first).join("Realtek").exists());
+
+        // 超出大小上限时删除最久未使用的驱动目录，本次使用的驱动目录保留
+        let mut cache = ExtractCache::open(&cachePath, 1000).unwrap();
+        cache.Entries[0].LastUsed = 1;
+        write(&cache, &second, "Nvidia", 800);
+        cache.record(packagePath, &second, &["Nvidia".to_string()]);
+        assert_eq!(cache.Entries.len(), 1);
+        assert_eq!(cache.Entries[0].Hash, second);
+        assert!(!cache.packageDir(&first).exists());
+        write(&cache, &second, "Nvidia/Audio", 800);
+        cache.record(packagePath, &second, &["Nvidia/Audio".to_string()]);
+        assert_eq!(cache.Entries.len(), 2);
+
+        // 清空缓存（包括清单中未记录的解压目录，其他目录保留）
+        write(&cache, &"c".repeat(64), "Orphan", 10);
+        std::fs::create_dir_all(cachePath.join("Other")).unwrap();
+        cache.purge().unwrap();
+        assert!(cache.Entries.is_empty() && !cache.packageDir(&second).exists());
+        assert!(!cache.packageDir(&"c".repeat(64)).exists() && cachePath.join("Other").exists());
+        assert!(ExtractCache::open(&cachePath, 1000).unwrap().Entries.is_empty());
+        let _ = std::fs::remove_dir_all(&cachePath);
+    }
+
+    // 二进制索引测试
+    #[test]
+    fn binaryIndexTest() {
+        use crate::command::create_index::{IndexFile, IndexFormat, InfInfo};
+        use crate::utils::binaryIndex::BinaryIndex;
+        use crate::utils::catalog::SignatureStatus;
+        use crate::utils::infParser::InfFile;
+
+        let tempPath = env::temp_dir().join("DriverIndexer-binaryIndexTest");
+        std::fs::create_dir_all(&tempPath).unwrap();
+
+        let newInf = |name: &str, models: &str| {
+            let content = format!("[Version]\nClass=Net\nDriverVer=06/01/2020,1.0.0.0\nCatalogFile={}.cat\n[Manufacturer]\n%Mfg%=Mfg,NTamd64,NTarm64\n[Mfg.NTamd64]\n{}\n[Mfg.NTarm64]\n{}\n", name, models, models);
+            InfInfo::fromInf(format!("Net\\{}", name), format!("{}.inf", name), &InfFile::parse(&content))
+        };
+        let mut entries = vec![
+            newInf("e1d", "%Dev%=Install,PCI\\VEN_8086&DEV_15B8,PCI\\CC_020000"),
+            newInf("rt640x64", "%Dev%=Install,PCI\\VEN_10EC&DEV_8168"),
+            newInf("netwtw", "%Dev%=Install,PCI\\VEN_8086&DEV_2723\n%Dev2%=Install,PCI\\VEN_8086&DEV_15B8"),
+        ];
+        // 缺少的文件及签名
+        entries[1].Missing = vec!["rt640x64.sys".to_string(), "x64/rt640x64.dll".to_string()];
+        entries[1].Signature = SignatureStatus::Whql;
+        entries[1].Signer = "CN=Microsoft Windows Hardware Compatibility Publisher".to_string();
+        entries[1].OSAttr = "2:6.3,2:10.0".to_string();
+        let index = IndexFile::new(entries.clone(), None, false).unwrap();
+        let indexPath = tempPath.join("Drivers.index");
+        index.save(&indexPath, IndexFormat::Binary).unwrap();
+
+        assert!(BinaryIndex::isBinaryIndex(&indexPath));
+        let binary = BinaryIndex::open(&indexPath).unwrap();
+        assert_eq!(binary.len(), 3);
+        assert_eq!(binary.entry(1), entries[1]);
+        // 硬件id查询（不区分大小写）
+        assert_eq!(binary.lookup(r"pci\ven_8086&dev_15b8"), vec![0, 2]);
+        assert_eq!(binary.lookup(r"PCI\CC_020000"), vec![0]);
+        assert!(binary.lookup(r"PCI\VEN_FFFF").is_empty());
+        // 完整读取与自动识别
+        assert_eq!(binary.toIndexFile().unwrap(), index);
+        assert_eq!(IndexFile::load(&indexPath).unwrap(), index);
+
+        // 损坏的索引
+        let mut data = BinaryIndex::encode(&index).unwrap();
+        data.truncate(data.len() / 2);
+        std::fs::write(&indexPath, &data).unwrap();
+        assert!(BinaryIndex::open(&indexPath).is_err());
+        // 表大小超出范围（32 位系统上乘积会溢出）
+        for field in [2, 5, 7, 9, 11] {
+            let mut data = BinaryIndex::encode(&index).unwrap();
+            data[12 + field * 4..16 + field * 4].copy_from_slice(&u32::MAX.to_le_bytes());
+            std::fs::write(&indexPath, &data).unwrap();
+            assert!(BinaryIndex::open(&indexPath).is_err());
+        }
+
+        std::fs::remove_dir_all(&tempPath).ok();
+    }
+
+    // 驱动等级测试
+    #[test]
+    fn driverRankTest() {
+        use crate::command::create_index::InfInfo;
+        use crate::command::load_driver::getMatchInfo;
+        use crate::utils::devcon::HwID;
+        use crate::utils::driverRank::{computeRank, formatRank, SignatureTier};
+        use crate::utils::infParser::InfFile;
+        use crate::utils::targetOS::TargetOS;
+
+        assert_eq!(computeRank(SignatureTier::Whql, 0xFF, false, 0, 0), 0x00FF0000);
+        assert_eq!(computeRank(SignatureTier::Signed, 0xFF, false, 1, 0), 0x0DFF0100);
+        assert_eq!(computeRank(SignatureTier::Signed, 0x80, true, 2, 3), 0x0D803202);
+        assert_eq!(formatRank(computeRank(SignatureTier::Unsigned, 0xFF, false, 0, 1)), "0x80FF1000");
+
+        let newInf = |name: &str, driverVer: &str, catalog: bool, models: &str, featureScore: Option<&str>| {
+            let mut content = format!("[Version]\nClass=Net\nDriverVer={}\n", driverVer);
+            if catalog {
+                content.push_str("CatalogFile.NTamd64=drv.cat\n");
+            }
+            content.push_str("[Manufacturer]\n%Mfg%=Mfg,NTamd64\n[Mfg.NTamd64]\n");
+            content.push_str(models);
+            if let Some(score) = featureScore {
+                content.push_str(&format!("\n[Install.NT]\nFeatureScore={}\n", score));
+            }
+            InfInfo::fromInf(name.to_string(), format!("{}.inf", name), &InfFile::parse(&content))
+        };
+        let hwIdList = vec![HwID {
+            DeviceInstancePath: r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086\3&11583659&0&FE".to_string(),
+            Name: "Ethernet Controller".to_string(),
+            HardwareIDs: vec![r"PCI\VEN_8086&DEV_15B8&SUBSYS_00008086".to_string(), r"PCI\VEN_8086&DEV_15B8".to_string()],
+            CompatibleIDs: vec![r"PCI\CC_020000".to_string()],
+            Status: None,
+        }];
+        let win10 = TargetOS { Arch: "NTamd64".to_string(), Major: 10, Minor: 0, ProductType: 1, SuiteMask: 0, Build: 19045 };
+
+        let infInfoList = vec![
+            // 兼容id匹配，版本最高
+            newInf("Class", "01/01/2024,30.0.0.0", true, "%Dev%=Install,PCI\\CC_020000", None),
+            // 第二个硬件id匹配
+            newInf("Generic", "06/01/2020,20.0.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8", None),
+            // 第一个硬件id匹配
+            newInf("Oem", "06/01/2019,10.0.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8&SUBSYS_00008086", None),
+            // 第一个硬件id匹配，但未签名
+            newInf("Unsigned", "06/01/2023,40.0.0.0", false, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8&SUBSYS_00008086", None),
+            // 第二个硬件id匹配，功能分数更优
+            newInf("Feature", "06/01/2018,1.0.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8", Some("0x80")),
+            // INF兼容id匹配设备硬件id
+            newInf("InfCompat", "06/01/2022,1.0.0.0", true, "%Dev%=Install,PCI\\VEN_FFFF,PCI\\VEN_8086&DEV_15B8&SUBSYS_00008086", None),
+        ];
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &win10);
+        let ranked: Vec<(&str, u32)> = matchInfo[0].1.iter().map(|item| (item.Path.as_str(), item.Rank)).collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("Feature", 0x0D800100),
+                ("Oem", 0x0DFF0000),
+                ("Generic", 0x0DFF0100),
+                ("InfCompat", 0x0DFF1000),
+                ("Class", 0x0DFF2000),
+                ("Unsigned", 0x80FF0000),
+            ]
+        );
+
+        // 等级相同时：新日期优先，其次高版本优先
+        let infInfoList = vec![
+            newInf("Old", "06/01/2019,10.0.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8", None),
+            newInf("New", "06/01/2020,9.0.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8", None),
+            newInf("NewHigh", "06/01/2020,9.1.0.0", true, "%Dev%=Install,PCI\\VEN_8086&DEV_15B8", None),
+        ];
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList.clone()), None, &win10);
+        let ranked: Vec<&str> = matchInfo[0].1.iter().map(|item| item.Path.as_str()).collect();
+        assert_eq!(ranked, vec!["NewHigh", "New", "Old"]);
+    }
+
     // 正则表达式测试
     #[test]
     fn reTest() {
@@ -148,7 +2006,7 @@ mod Tests {
 
         // 匹配硬件id
         let time1 = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-        let matchInfo = getMatchInfo(&hwIdList, &infInfoList, None);
+        let matchInfo = getMatchInfo(&hwIdList, &HardwareIdIndex::new(infInfoList), None, &crate::utils::targetOS::TargetOS::current());
         let time2 = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
 
         println!("{:#?}", matchInfo);
@@ -182,7 +2040,7 @@ mod Tests {
         use crate::command::classify_driver::classify_driver;
 
         let basePath = PathBuf::from(r"D:\UserData\Desktop\万能网卡驱动-驱动精灵");
-        classify_driver(&basePath);
+        classify_driver(&basePath, &basePath.join("output"), false);
     }
 
     // 版本号对比测试
@@ -413,6 +2271,12 @@ mod Tests {
         println!("{:?}", crate::utils::util::getArchCode(Path::new(r"C:\")));
     }
 
+    #[test]
+    fn getOfflineTargetOSTest() {
+        println!("{:?}", crate::utils::util::getSystemVersion(Path::new(r"C:\")));
+        println!("{:?}", crate::utils::targetOS::TargetOS::offline(Path::new(r"C:\")));
+    }
+
     #[test]
     fn loadOfflineDriverTest() {
         let mut candidates = Vec::new();
@@ -437,6 +2301,6 @@ mod Tests {
 
     #[test]
     fn guidTest() {
-        unsafe { println!("{:?}", get_class_description("4D36E972-E325-11CE-BFC1-08002BE10318")); }
+        unsafe { println!("{:?}", crate::utils::setupAPI::get_class_description_str("{4D36E972-E325-11CE-BFC1-08002BE10318}")); }
     }
 }
@@ -0,0 +1,261 @@
+use crate::utils::nativeArchive::NativeArchive;
+use crate::utils::sevenZIP::sevenZip;
+use chrono::NaiveDate;
+use flate2::read::GzDecoder;
+use glob::{MatchOptions, Pattern};
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// 只读（FILE_ATTRIBUTE_READONLY）
+pub const FILE_ATTRIBUTE_READONLY: u32 = 0x0001;
+/// 隐藏（FILE_ATTRIBUTE_HIDDEN）
+pub const FILE_ATTRIBUTE_HIDDEN: u32 = 0x0002;
+/// 系统（FILE_ATTRIBUTE_SYSTEM）
+pub const FILE_ATTRIBUTE_SYSTEM: u32 = 0x0004;
+/// 目录（FILE_ATTRIBUTE_DIRECTORY）
+pub const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x0010;
+/// 存档（FILE_ATTRIBUTE_ARCHIVE）
+pub const FILE_ATTRIBUTE_ARCHIVE: u32 = 0x0020;
+
+/// 压缩包内的文件
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ArchiveEntry {
+    /// 文件路径（以 `/` 分隔）
+    pub(crate) Path: String,
+    /// 文件大小（字节）
+    pub(crate) Size: u64,
+    /// 是否为目录
+    pub(crate) IsDir: bool,
+    /// 修改时间（Unix 时间戳，压缩包中未记录时为 None）
+    pub(crate) Modified: Option<u64>,
+    /// CRC32 校验值（压缩包中未记录时为 None）
+    pub(crate) Crc: Option<u32>,
+    /// 文件属性（FILE_ATTRIBUTE_*）
+    pub(crate) Attributes: u32,
+    /// 是否已加密
+    pub(crate) Encrypted: bool,
+    /// 所在的固实块（非固实压缩时为 None）
+    pub(crate) Block: Option<u64>,
+}
+
+/// 压缩包内的文件及其内容
+pub type ArchiveFile = (ArchiveEntry, Vec<u8>);
+
+/// 压缩包内文件内容的处理函数
+pub type FileVisitor<'a> = dyn FnMut(ArchiveEntry, Vec<u8>) -> Result<(), Box<dyn Error>> + 'a;
+
+impl ArchiveEntry {
+    /// 是否为INF文件
+    pub fn isInf(&self) -> bool {
+        !self.IsDir && Path::new(&self.Path).extension().is_some_and(|extension| extension.eq_ignore_ascii_case("inf"))
+    }
+}
+
+/// 压缩包后端
+/// 列出、解压及创建压缩包，驱动包的解压流程通过该接口访问压缩包
+pub trait ArchiveBackend: Send + Sync {
+    /// 列出压缩包内的文件
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    fn list(&self, archivePath: &Path, password: Option<&str>) -> Result<Vec<ArchiveEntry>, Box<dyn Error>>;
+
+    /// 按通配符解压文件（保留目录结构，递归子目录，不覆盖已存在的文件）
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 通配符（如 `*.inf`、驱动目录，解压全部文件为 `*`）
+    /// 4. 输出路径
+    fn extractPattern(&self, archivePath: &Path, password: Option<&str>, pattern: &str, outPath: &Path) -> Result<bool, Box<dyn Error>>;
+
+    /// 按多个通配符一次性解压文件（规则同 `extractPattern`）
+    /// 固实压缩包只需解压一遍，匹配任一通配符的文件均会解压
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 通配符列表
+    /// 4. 输出路径
+    fn extractPatterns(&self, archivePath: &Path, password: Option<&str>, patterns: &[String], outPath: &Path) -> Result<bool, Box<dyn Error>>;
+
+    /// 解压指定文件（保留目录结构，不覆盖已存在的文件）
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 压缩包内文件路径
+    /// 4. 输出路径
+    fn extractEntry(&self, archivePath: &Path, password: Option<&str>, entryPath: &str, outPath: &Path) -> Result<bool, Box<dyn Error>>;
+
+    /// 创建压缩包
+    /// # 参数
+    /// 1. 待压缩的目录
+    /// 2. 压缩包路径
+    fn create(&self, inputPath: &Path, outPath: &Path) -> Result<bool, Box<dyn Error>>;
+
+    /// 逐个读取匹配的文件内容（不写入磁盘，处理完的内容即可释放）
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 通配符（规则同 `extractPattern`）
+    /// 4. 文件内容处理函数（按压缩包中的顺序调用，返回错误时停止读取）
+    fn visitFiles(&self, archivePath: &Path, password: Option<&str>, pattern: &str, visit: &mut FileVisitor) -> Result<(), Box<dyn Error>>;
+
+    /// 读取匹配的文件内容（不写入磁盘）
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 通配符（规则同 `extractPattern`）
+    fn readFiles(&self, archivePath: &Path, password: Option<&str>, pattern: &str) -> Result<Vec<ArchiveFile>, Box<dyn Error>> {
+        let mut fileList = Vec::new();
+        self.visitFiles(archivePath, password, pattern, &mut |entry, content| {
+            fileList.push((entry, content));
+            Ok(())
+        })?;
+        Ok(fileList)
+    }
+
+    /// 是否为驱动包（包含驱动INF文件）
+    /// # 参数
+    /// 1. 压缩包路径
+    fn isDriverPackage(&self, archivePath: &Path) -> Result<bool, Box<dyn Error>> {
+        Ok(self.list(archivePath, None)?.iter().any(ArchiveEntry::isInf))
+    }
+}
+
+/// 压缩包格式（按文件头识别）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarXz,
+    Cab,
+    SevenZip,
+    Rar,
+    Wim,
+}
+
+impl ArchiveFormat {
+    /// 识别压缩包格式
+    /// # 参数
+    /// 1. 文件路径
+    /// # 规则
+    /// 1. 按文件头的特征字节识别，不依赖扩展名
+    /// 2. gzip 需解压首个块确认其中为 tar，xz 视为 tar
+    pub fn detect(path: &Path) -> Option<ArchiveFormat> {
+        let mut header = Vec::with_capacity(512);
+        File::open(path).ok()?.take(512).read_to_end(&mut header).ok()?;
+
+        let format = if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") || header.starts_with(b"PK\x07\x08") {
+            ArchiveFormat::Zip
+        } else if header.starts_with(b"7z\xBC\xAF\x27\x1C") {
+            ArchiveFormat::SevenZip
+        } else if header.starts_with(b"Rar!\x1A\x07") {
+            ArchiveFormat::Rar
+        } else if header.starts_with(b"MSCF") {
+            ArchiveFormat::Cab
+        } else if header.starts_with(b"MSWIM\0\0\0") {
+            ArchiveFormat::Wim
+        } else if header.starts_with(b"\xFD7zXZ\0") {
+            ArchiveFormat::TarXz
+        } else if header.starts_with(b"\x1F\x8B") {
+            let mut block = Vec::with_capacity(512);
+            GzDecoder::new(File::open(path).ok()?).take(512).read_to_end(&mut block).ok()?;
+            if !isTarHeader(&block) {
+                return None;
+            }
+            ArchiveFormat::TarGz
+        } else if isTarHeader(&header) {
+            ArchiveFormat::Tar
+        } else {
+            return None;
+        };
+        Some(format)
+    }
+}
+
+/// 打开压缩包后端
+/// # 参数
+/// 1. 压缩包路径
+/// # 规则
+/// 1. TAR(.gz/.xz)、仅使用 Deflate 压缩或未压缩的 ZIP（可为 ZipCrypto 加密）及仅使用 MSZIP 压缩或未压缩的 CAB 使用内置实现
+/// 2. 其余格式（7z、RAR、WIM、使用其他压缩方法或 AES 加密的 ZIP、LZX 压缩的 CAB、自解压程序等）使用 7-zip
+pub fn openArchive(archivePath: &Path) -> Result<Box<dyn ArchiveBackend>, Box<dyn Error>> {
+    let native = ArchiveFormat::detect(archivePath).is_some_and(|format| NativeArchive::supports(archivePath, format));
+    Ok(if native { Box::new(NativeArchive::new()) } else { Box::new(sevenZip::new()?) })
+}
+
+/// 文件是否匹配通配符（不区分大小写）
+/// # 参数
+/// 1. 压缩包内文件路径
+/// 2. 通配符
+/// # 规则
+/// 1. 通配符为空或 `*` 时匹配全部文件
+/// 2. 不含路径分隔符的通配符匹配任意层级的文件或目录名称（如 `*.inf`）
+/// 3. 含路径分隔符的通配符从压缩包根目录开始匹配（如 `Intel/LAN`）
+/// 4. 匹配目录时包括该目录下的全部文件
+pub fn matchPattern(entryPath: &str, pattern: &str) -> bool {
+    let pattern = pattern.replace('\\', "/");
+    let pattern = pattern.trim_matches('/');
+    if pattern.is_empty() || pattern == "*" {
+        return true;
+    }
+    let options = MatchOptions { case_sensitive: false, require_literal_separator: true, require_literal_leading_dot: false };
+    let Ok(patternList) = pattern.split('/').map(Pattern::new).collect::<Result<Vec<Pattern>, _>>() else { return false };
+    let entryPath = entryPath.replace('\\', "/");
+    let nameList: Vec<&str> = entryPath.split('/').filter(|name| !name.is_empty()).collect();
+    if nameList.len() < patternList.len() {
+        return false;
+    }
+
+    let isMatch = |start: usize| patternList.iter().zip(&nameList[start..]).all(|(pattern, name)| pattern.matches_with(name, options));
+    if pattern.contains('/') {
+        isMatch(0)
+    } else {
+        (0..=nameList.len() - patternList.len()).any(isMatch)
+    }
+}
+
+/// 获取文件的解压路径
+/// 包含上级目录（`..`）或绝对路径的文件返回 None，避免写入输出路径之外
+/// # 参数
+/// 1. 输出路径
+/// 2. 压缩包内文件路径
+pub fn entryOutPath(outPath: &Path, entryPath: &str) -> Option<PathBuf> {
+    let mut path = outPath.to_path_buf();
+    for component in Path::new(&entryPath.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(name) => path.push(name),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+/// 转换 DOS 日期时间（ZIP、CAB 中的修改时间）为 Unix 时间戳
+/// # 参数
+/// 1. DOS 日期（年 7 位，自 1980 年起；月 4 位；日 5 位）
+/// 2. DOS 时间（时 5 位；分 6 位；秒 5 位，以 2 秒为单位）
+pub fn dosTimestamp(date: u16, time: u16) -> Option<u64> {
+    let date = NaiveDate::from_ymd_opt(1980 + (date >> 9) as i32, ((date >> 5) & 0x0F) as u32, (date & 0x1F) as u32)?;
+    let time = date.and_hms_opt((time >> 11) as u32, ((time >> 5) & 0x3F) as u32, ((time & 0x1F) * 2) as u32)?;
+    u64::try_from(time.and_utc().timestamp()).ok()
+}
+
+/// 是否为 tar 文件头（ustar 标识或校验和正确的 v7 格式）
+/// # 参数
+/// 1. 首个 512 字节块
+fn isTarHeader(block: &[u8]) -> bool {
+    if block.len() < 512 {
+        return false;
+    }
+    if &block[257..262] == b"ustar" {
+        return true;
+    }
+    // 校验和为八进制文本，计算时校验和字段视为空格
+    let Some(checksum) = std::str::from_utf8(&block[148..156]).ok().and_then(|text| u32::from_str_radix(text.trim_matches(|c: char| c == '\0' || c == ' '), 8).ok()) else { return false };
+    let sum: u32 = block.iter().enumerate().map(|(index, byte)| if (148..156).contains(&index) { b' ' as u32 } else { *byte as u32 }).sum();
+    block[0] != 0 && checksum == sum
+}
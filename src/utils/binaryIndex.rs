@@ -0,0 +1,439 @@
+// 二进制索引格式（小端序）
+// [文件头]       标识、版本及各表的位置
+// [元数据]       JSON 格式的索引文件头（不含INF信息）
+// [字符串表]     去重后的字符串，偏移数组（数量 + 1）+ UTF-8 数据
+// [INF记录表]    每条 84 字节：Path、Inf、Class、Date、Version、Catalog、架构起始、架构数量、硬件id起始、硬件id数量、Size(u64)、Modified(u64)、Package、Missing（以 `|` 连接）、Signer、Signature(u8)、保留(3 字节)、OSAttr、FilesStamp(u64)
+// [架构引用表]   字符串编号数组
+// [硬件id记录表] 每条 20 字节：Id、Models、Decoration、所属INF、Position(u16)、FeatureScore(u8)、标志(u8，位 0 为 Incomplete)
+// [哈希桶]       桶偏移数组（数量 + 1），指向槽位表
+// [槽位表]       按桶排列的硬件id记录编号
+
+use crate::command::create_index::{DriverID, IndexFile, InfInfo};
+use crate::utils::catalog::SignatureStatus;
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+
+/// 二进制索引文件标识
+const MAGIC: &[u8; 8] = b"DRVIDXB\0";
+/// 二进制索引格式版本
+const BINARY_VERSION: u32 = 7;
+/// 文件头大小
+const HEADER_SIZE: usize = 72;
+/// INF记录大小
+const ENTRY_SIZE: usize = 84;
+/// 硬件id记录大小
+const ID_SIZE: usize = 20;
+
+/// 文件头
+#[derive(Debug, Clone, Copy, Default)]
+struct Header {
+    metaOffset: usize,
+    metaLen: usize,
+    stringCount: usize,
+    stringOffsets: usize,
+    stringData: usize,
+    entryCount: usize,
+    entries: usize,
+    archRefCount: usize,
+    archRefs: usize,
+    idCount: usize,
+    ids: usize,
+    bucketCount: usize,
+    buckets: usize,
+    slots: usize,
+}
+
+/// 索引内容
+enum IndexData {
+    /// 内存映射的索引文件
+    Mapped(Mmap),
+    /// 已读取到内存中的索引（如压缩包内的索引）
+    Memory(Vec<u8>),
+}
+
+impl Deref for IndexData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            IndexData::Mapped(mmap) => mmap,
+            IndexData::Memory(content) => content,
+        }
+    }
+}
+
+/// 二进制索引（内存映射的索引文件或内存中的索引内容）
+/// 仅在查询时读取所需的记录，无需反序列化整个索引
+pub struct BinaryIndex {
+    data: IndexData,
+    header: Header,
+}
+
+impl BinaryIndex {
+    /// 是否为二进制索引文件
+    /// # 参数
+    /// 1. 索引文件路径
+    pub fn isBinaryIndex(indexPath: &Path) -> bool {
+        let mut magic = [0u8; 8];
+        File::open(indexPath).and_then(|mut file| file.read_exact(&mut magic)).is_ok() && &magic == MAGIC
+    }
+
+    /// 是否为二进制索引内容
+    /// # 参数
+    /// 1. 索引内容
+    pub fn isBinaryContent(content: &[u8]) -> bool {
+        content.starts_with(MAGIC)
+    }
+
+    /// 打开二进制索引
+    /// # 参数
+    /// 1. 索引文件路径
+    pub fn open(indexPath: &Path) -> Result<BinaryIndex, Box<dyn Error>> {
+        let file = File::open(indexPath)?;
+        BinaryIndex::parse(IndexData::Mapped(unsafe { Mmap::map(&file)? }))
+    }
+
+    /// 从内存中的内容打开二进制索引
+    /// # 参数
+    /// 1. 索引内容
+    pub fn fromBytes(content: Vec<u8>) -> Result<BinaryIndex, Box<dyn Error>> {
+        BinaryIndex::parse(IndexData::Memory(content))
+    }
+
+    /// 解析并校验文件头
+    /// # 参数
+    /// 1. 索引内容
+    fn parse(data: IndexData) -> Result<BinaryIndex, Box<dyn Error>> {
+        if data.len() < HEADER_SIZE || &data[..8] != MAGIC {
+            return Err("Invalid binary index".into());
+        }
+        if readU32(&data, 8) != BINARY_VERSION {
+            return Err("Unsupported binary index version".into());
+        }
+
+        let field = |index: usize| readU32(&data, 12 + index * 4) as usize;
+        let header = Header {
+            metaOffset: field(0),
+            metaLen: field(1),
+            stringCount: field(2),
+            stringOffsets: field(3),
+            stringData: field(4),
+            entryCount: field(5),
+            entries: field(6),
+            archRefCount: field(7),
+            archRefs: field(8),
+            idCount: field(9),
+            ids: field(10),
+            bucketCount: field(11),
+            buckets: field(12),
+            slots: field(13),
+        };
+
+        // 校验各表均位于文件范围内，之后的读取无需再检查（表大小溢出时视为无效索引）
+        let fileLen = data.len();
+        let fits = |offset: usize, len: Option<usize>| len.and_then(|len| offset.checked_add(len)).is_some_and(|end| end <= fileLen);
+        let table = |count: usize, size: usize| count.checked_mul(size);
+        let valid = fits(header.metaOffset, Some(header.metaLen))
+            && fits(header.stringOffsets, header.stringCount.checked_add(1).and_then(|count| table(count, 4)))
+            && fits(header.entries, table(header.entryCount, ENTRY_SIZE))
+            && fits(header.archRefs, table(header.archRefCount, 4))
+            && fits(header.ids, table(header.idCount, ID_SIZE))
+            && header.bucketCount.is_power_of_two()
+            && fits(header.buckets, header.bucketCount.checked_add(1).and_then(|count| table(count, 4)))
+            && fits(header.slots, table(header.idCount, 4));
+        if !valid {
+            return Err("Invalid binary index".into());
+        }
+        let index = BinaryIndex { data, header };
+        let stringDataLen = index.u32At(header.stringOffsets + header.stringCount * 4) as usize;
+        if !fits(header.stringData, Some(stringDataLen)) {
+            return Err("Invalid binary index".into());
+        }
+        Ok(index)
+    }
+
+    /// 读取索引文件头（不含INF信息）
+    pub fn header(&self) -> Result<IndexFile, Box<dyn Error>> {
+        let meta = &self.data[self.header.metaOffset..self.header.metaOffset + self.header.metaLen];
+        Ok(serde_json::from_slice(meta)?)
+    }
+
+    /// INF记录数量
+    pub fn len(&self) -> usize {
+        self.header.entryCount
+    }
+
+    /// 是否没有INF记录
+    pub fn is_empty(&self) -> bool {
+        self.header.entryCount == 0
+    }
+
+    /// 读取指定的INF记录
+    /// # 参数
+    /// 1. INF记录编号
+    pub fn entry(&self, index: usize) -> InfInfo {
+        let offset = self.header.entries + index * ENTRY_SIZE;
+        let field = |index: usize| self.u32At(offset + index * 4);
+        let (archStart, archCount) = (field(6) as usize, field(7) as usize);
+        let (idStart, idCount) = (field(8) as usize, field(9) as usize);
+
+        InfInfo {
+            Path: self.string(field(0)),
+            Inf: self.string(field(1)),
+            Class: self.string(field(2)),
+            Arch: (archStart..archStart + archCount)
+                .filter(|&item| item < self.header.archRefCount)
+                .map(|item| self.string(self.u32At(self.header.archRefs + item * 4)))
+                .collect(),
+            Date: self.string(field(3)),
+            Version: self.string(field(4)),
+            Catalog: self.string(field(5)),
+            Size: self.u64At(offset + 40),
+            Modified: self.u64At(offset + 48),
+            FilesStamp: self.u64At(offset + 76),
+            Package: self.string(self.u32At(offset + 56)),
+            Missing: self.str(self.u32At(offset + 60)).split('|').filter(|item| !item.is_empty()).map(String::from).collect(),
+            Signature: SignatureStatus::fromCode(self.data[offset + 68]),
+            Signer: self.string(self.u32At(offset + 64)),
+            OSAttr: self.string(self.u32At(offset + 72)),
+            DriverList: (idStart..idStart + idCount).filter(|&item| item < self.header.idCount).map(|item| self.driverID(item)).collect(),
+            Rank: 0,
+        }
+    }
+
+    /// 查找包含指定硬件id的INF记录
+    /// # 参数
+    /// 1. 硬件id（不区分大小写）
+    /// # 返回
+    /// INF记录编号列表
+    pub fn lookup(&self, id: &str) -> Vec<usize> {
+        let id = id.to_uppercase();
+        let bucket = (hashID(&id) as usize) & (self.header.bucketCount - 1);
+        let start = self.u32At(self.header.buckets + bucket * 4) as usize;
+        let end = (self.u32At(self.header.buckets + (bucket + 1) * 4) as usize).min(self.header.idCount);
+
+        let mut result: Vec<usize> = Vec::new();
+        for slot in start..end {
+            let record = self.u32At(self.header.slots + slot * 4) as usize;
+            if record >= self.header.idCount {
+                continue;
+            }
+            let offset = self.header.ids + record * ID_SIZE;
+            if self.str(self.u32At(offset)) == id {
+                let entry = self.u32At(offset + 12) as usize;
+                if entry < self.header.entryCount && !result.contains(&entry) {
+                    result.push(entry);
+                }
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// 读取完整的索引文件
+    pub fn toIndexFile(&self) -> Result<IndexFile, Box<dyn Error>> {
+        let mut index = self.header()?;
+        index.Entries = (0..self.len()).map(|item| self.entry(item)).collect();
+        Ok(index)
+    }
+
+    /// 保存为二进制索引
+    /// # 参数
+    /// 1. 索引文件
+    /// 2. 索引文件保存路径
+    pub fn save(index: &IndexFile, savePath: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(savePath, BinaryIndex::encode(index)?)?;
+        Ok(())
+    }
+
+    /// 编码为二进制索引
+    /// # 参数
+    /// 1. 索引文件
+    pub fn encode(index: &IndexFile) -> Result<Vec<u8>, Box<dyn Error>> {
+        // 元数据（不含INF信息）
+        let meta = serde_json::to_vec(&IndexFile {
+            FormatVersion: index.FormatVersion,
+            GeneratorVersion: index.GeneratorVersion.clone(),
+            CreatedAt: index.CreatedAt.clone(),
+            Package: index.Package.clone(),
+            Packages: index.Packages.clone(),
+            PasswordRequired: index.PasswordRequired,
+            Entries: Vec::new(),
+        })?;
+
+        let mut strings = StringTable::default();
+        let mut entries: Vec<u8> = Vec::with_capacity(index.Entries.len() * ENTRY_SIZE);
+        let mut archRefs: Vec<u32> = Vec::new();
+        let mut ids: Vec<u8> = Vec::new();
+        // (哈希值, 硬件id记录编号)
+        let mut hashes: Vec<(u32, u32)> = Vec::new();
+
+        for (entryIndex, infInfo) in index.Entries.iter().enumerate() {
+            let idStart = hashes.len() as u32;
+            for fieldValue in [&infInfo.Path, &infInfo.Inf, &infInfo.Class, &infInfo.Date, &infInfo.Version, &infInfo.Catalog] {
+                entries.extend_from_slice(&strings.intern(fieldValue).to_le_bytes());
+            }
+            entries.extend_from_slice(&(archRefs.len() as u32).to_le_bytes());
+            entries.extend_from_slice(&(infInfo.Arch.len() as u32).to_le_bytes());
+            entries.extend_from_slice(&idStart.to_le_bytes());
+            entries.extend_from_slice(&(infInfo.DriverList.len() as u32).to_le_bytes());
+            entries.extend_from_slice(&infInfo.Size.to_le_bytes());
+            entries.extend_from_slice(&infInfo.Modified.to_le_bytes());
+            entries.extend_from_slice(&strings.intern(&infInfo.Package).to_le_bytes());
+            entries.extend_from_slice(&strings.intern(&infInfo.Missing.join("|")).to_le_bytes());
+            entries.extend_from_slice(&strings.intern(&infInfo.Signer).to_le_bytes());
+            entries.extend_from_slice(&[infInfo.Signature as u8, 0, 0, 0]);
+            entries.extend_from_slice(&strings.intern(&infInfo.OSAttr).to_le_bytes());
+            entries.extend_from_slice(&infInfo.FilesStamp.to_le_bytes());
+            for arch in infInfo.Arch.iter() {
+                archRefs.push(strings.intern(arch));
+            }
+
+            for driverID in infInfo.DriverList.iter() {
+                let id = driverID.Id.to_uppercase();
+                hashes.push((hashID(&id), hashes.len() as u32));
+                ids.extend_from_slice(&strings.intern(&id).to_le_bytes());
+                ids.extend_from_slice(&strings.intern(&driverID.Models).to_le_bytes());
+                ids.extend_from_slice(&strings.intern(&driverID.Decoration).to_le_bytes());
+                ids.extend_from_slice(&(entryIndex as u32).to_le_bytes());
+                ids.extend_from_slice(&(driverID.Position.min(u16::MAX as usize) as u16).to_le_bytes());
+                ids.push(driverID.FeatureScore);
+                ids.push(driverID.Incomplete as u8);
+            }
+        }
+
+        // 哈希表：桶数量为不小于硬件id数量的 2 的幂
+        let bucketCount = hashes.len().max(1).next_power_of_two();
+        hashes.sort_by_key(|(hash, record)| ((*hash as usize) & (bucketCount - 1), *record));
+        let mut buckets: Vec<u32> = vec![0; bucketCount + 1];
+        for (hash, _) in hashes.iter() {
+            buckets[((*hash as usize) & (bucketCount - 1)) + 1] += 1;
+        }
+        for item in 1..buckets.len() {
+            buckets[item] += buckets[item - 1];
+        }
+
+        // 写入
+        let mut data: Vec<u8> = vec![0; HEADER_SIZE];
+        let mut fields: Vec<u32> = Vec::new();
+        let append = |data: &mut Vec<u8>, bytes: &[u8]| -> u32 {
+            // 各表按 4 字节对齐
+            data.resize(data.len().next_multiple_of(4), 0);
+            let offset = data.len() as u32;
+            data.extend_from_slice(bytes);
+            offset
+        };
+        let toBytes = |list: &[u32]| -> Vec<u8> { list.iter().flat_map(|item| item.to_le_bytes()).collect() };
+
+        fields.push(append(&mut data, &meta));
+        fields.push(meta.len() as u32);
+        fields.push(strings.offsets.len() as u32 - 1);
+        fields.push(append(&mut data, &toBytes(&strings.offsets)));
+        fields.push(append(&mut data, &strings.data));
+        fields.push(index.Entries.len() as u32);
+        fields.push(append(&mut data, &entries));
+        fields.push(archRefs.len() as u32);
+        fields.push(append(&mut data, &toBytes(&archRefs)));
+        fields.push(hashes.len() as u32);
+        fields.push(append(&mut data, &ids));
+        fields.push(bucketCount as u32);
+        fields.push(append(&mut data, &toBytes(&buckets)));
+        let slots: Vec<u32> = hashes.iter().map(|(_, record)| *record).collect();
+        fields.push(append(&mut data, &toBytes(&slots)));
+
+        if data.len() > u32::MAX as usize {
+            return Err("Binary index is too large".into());
+        }
+        data[..8].copy_from_slice(MAGIC);
+        data[8..12].copy_from_slice(&BINARY_VERSION.to_le_bytes());
+        for (index, value) in fields.iter().enumerate() {
+            data[12 + index * 4..16 + index * 4].copy_from_slice(&value.to_le_bytes());
+        }
+        Ok(data)
+    }
+
+    /// 读取指定的硬件id记录
+    fn driverID(&self, record: usize) -> DriverID {
+        let offset = self.header.ids + record * ID_SIZE;
+        DriverID {
+            Id: self.string(self.u32At(offset)),
+            Models: self.string(self.u32At(offset + 4)),
+            Decoration: self.string(self.u32At(offset + 8)),
+            Position: u16::from_le_bytes([self.data[offset + 16], self.data[offset + 17]]) as usize,
+            FeatureScore: self.data[offset + 18],
+            Incomplete: self.data[offset + 19] & 1 != 0,
+        }
+    }
+
+    /// 读取字符串（编号无效时返回空字符串）
+    fn str(&self, id: u32) -> &str {
+        let id = id as usize;
+        if id >= self.header.stringCount {
+            return "";
+        }
+        let start = self.u32At(self.header.stringOffsets + id * 4) as usize;
+        let end = self.u32At(self.header.stringOffsets + (id + 1) * 4) as usize;
+        self.data
+            .get(self.header.stringData + start..self.header.stringData + end)
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn string(&self, id: u32) -> String {
+        self.str(id).to_string()
+    }
+
+    fn u32At(&self, offset: usize) -> u32 {
+        readU32(&self.data, offset)
+    }
+
+    fn u64At(&self, offset: usize) -> u64 {
+        self.data.get(offset..offset + 8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap())).unwrap_or(0)
+    }
+}
+
+/// 字符串表
+struct StringTable {
+    /// 字符串 => 编号
+    index: HashMap<String, u32>,
+    /// 偏移数组（数量 + 1）
+    offsets: Vec<u32>,
+    /// UTF-8 数据
+    data: Vec<u8>,
+}
+
+impl Default for StringTable {
+    fn default() -> StringTable {
+        StringTable { index: HashMap::new(), offsets: vec![0], data: Vec::new() }
+    }
+}
+
+impl StringTable {
+    /// 获取字符串编号，不存在则添加
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(&id) = self.index.get(value) {
+            return id;
+        }
+        let id = self.offsets.len() as u32 - 1;
+        self.data.extend_from_slice(value.as_bytes());
+        self.offsets.push(self.data.len() as u32);
+        self.index.insert(value.to_string(), id);
+        id
+    }
+}
+
+/// 硬件id哈希（FNV-1a）
+fn hashID(id: &str) -> u32 {
+    id.bytes().fold(0x811C9DC5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
+
+/// 读取小端序 u32（越界时返回 0）
+fn readU32(data: &[u8], offset: usize) -> u32 {
+    data.get(offset..offset + 4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap())).unwrap_or(0)
+}
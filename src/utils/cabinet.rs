@@ -0,0 +1,280 @@
+use crate::i18n::getLocaleText;
+use miniz_oxide::inflate::core::{decompress, inflate_flags, DecompressorOxide};
+use miniz_oxide::inflate::TINFLStatus;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// 未压缩
+pub const COMPRESS_NONE: u16 = 0;
+/// MSZIP 压缩（deflate）
+pub const COMPRESS_MSZIP: u16 = 1;
+/// Quantum 压缩
+pub const COMPRESS_QUANTUM: u16 = 2;
+/// LZX 压缩
+pub const COMPRESS_LZX: u16 = 3;
+
+/// 文件头中包含上一个分卷信息（cfhdrPREV_CABINET）
+const FLAG_PREV_CABINET: u16 = 0x0001;
+/// 文件头中包含下一个分卷信息（cfhdrNEXT_CABINET）
+const FLAG_NEXT_CABINET: u16 = 0x0002;
+/// 文件头、文件夹、数据块中包含保留区域（cfhdrRESERVE_PRESENT）
+const FLAG_RESERVE_PRESENT: u16 = 0x0004;
+/// 文件名为 UTF-8 编码（_A_NAME_IS_UTF）
+const ATTRIBUTE_NAME_IS_UTF: u16 = 0x0080;
+/// 文件属性中的 Windows 属性位（只读、隐藏、系统、存档）
+const ATTRIBUTE_MASK: u16 = 0x0027;
+/// 文件所属文件夹从上一个分卷延续（ifoldCONTINUED_FROM_PREV 及以上）
+const FOLDER_CONTINUED: u16 = 0xFFFD;
+/// 文件头固定部分大小
+const HEADER_SIZE: usize = 36;
+/// MSZIP 数据块的最大解压大小（即历史窗口大小）
+const MSZIP_BLOCK_SIZE: usize = 32768;
+/// 数据块头大小（校验和、压缩大小、解压大小）
+const DATA_HEADER_SIZE: usize = 8;
+
+/// CAB 文件夹（一组连续压缩的数据块）
+#[derive(Debug, Clone, PartialEq)]
+pub struct CabFolder {
+    /// 首个数据块的偏移
+    pub(crate) DataOffset: u64,
+    /// 数据块数量
+    pub(crate) DataCount: u16,
+    /// 压缩类型（低 4 位为压缩算法）
+    pub(crate) Compression: u16,
+}
+
+/// CAB 中的文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct CabFile {
+    /// 文件路径（以 `\` 分隔）
+    pub(crate) Name: String,
+    /// 文件大小（字节）
+    pub(crate) Size: u32,
+    /// 所属文件夹
+    pub(crate) Folder: u16,
+    /// 在文件夹解压数据中的偏移
+    pub(crate) Offset: u32,
+    /// 修改日期（DOS 日期）
+    pub(crate) Date: u16,
+    /// 修改时间（DOS 时间）
+    pub(crate) Time: u16,
+    /// 文件属性（FILE_ATTRIBUTE_*，不含 _A_NAME_IS_UTF 等 CAB 专用标志）
+    pub(crate) Attributes: u16,
+}
+
+/// CAB 压缩包（Microsoft Cabinet）
+/// 只读解析，支持未压缩及 MSZIP 压缩的文件夹，不支持分卷
+/// 打开时仅读取文件头、文件夹及文件列表，数据块在解压时按偏移读取
+pub struct Cabinet {
+    file: BufReader<File>,
+    reserveSize: usize,
+    pub(crate) Folders: Vec<CabFolder>,
+    pub(crate) Files: Vec<CabFile>,
+}
+
+impl Cabinet {
+    /// 读取 CAB 压缩包
+    /// # 参数
+    /// 1. 压缩包路径
+    pub fn open(path: &Path) -> Result<Cabinet, Box<dyn Error>> {
+        match Cabinet::readHeader(BufReader::new(File::open(path)?)) {
+            Ok(Some(cabinet)) => Ok(cabinet),
+            Ok(None) | Err(_) => {
+                let arg = hash_map!("path".to_string() => path.display().to_string().into());
+                Err(getLocaleText("archive-parsing-failed", Some(&arg)).into())
+            }
+        }
+    }
+
+    /// 解析文件头、文件夹及文件列表
+    /// # 参数
+    /// 1. 压缩包文件
+    /// # 返回
+    /// 不是 CAB 时为 None
+    fn readHeader(mut file: BufReader<File>) -> io::Result<Option<Cabinet>> {
+        let mut header = [0u8; HEADER_SIZE];
+        file.read_exact(&mut header)?;
+        if !header.starts_with(b"MSCF") {
+            return Ok(None);
+        }
+        let filesOffset = readU32(&header, 16);
+        let folderCount = readU16(&header, 26);
+        let fileCount = readU16(&header, 28);
+        let flags = readU16(&header, 30);
+
+        // 保留区域大小
+        let (mut folderReserve, mut reserveSize) = (0, 0);
+        if flags & FLAG_RESERVE_PRESENT != 0 {
+            let mut reserve = [0u8; 4];
+            file.read_exact(&mut reserve)?;
+            folderReserve = reserve[2] as i64;
+            reserveSize = reserve[3] as usize;
+            file.seek_relative(readU16(&reserve, 0) as i64)?;
+        }
+        // 跳过分卷的文件名、磁盘名
+        for flag in [FLAG_PREV_CABINET, FLAG_NEXT_CABINET] {
+            if flags & flag != 0 {
+                for _ in 0..2 {
+                    readString(&mut file)?;
+                }
+            }
+        }
+
+        let mut Folders = Vec::new();
+        for _ in 0..folderCount {
+            let mut folder = [0u8; 8];
+            file.read_exact(&mut folder)?;
+            Folders.push(CabFolder { DataOffset: readU32(&folder, 0) as u64, DataCount: readU16(&folder, 4), Compression: readU16(&folder, 6) });
+            file.seek_relative(folderReserve)?;
+        }
+
+        let mut Files = Vec::new();
+        file.seek(SeekFrom::Start(filesOffset as u64))?;
+        for _ in 0..fileCount {
+            let mut entry = [0u8; 16];
+            file.read_exact(&mut entry)?;
+            let nameBytes = readString(&mut file)?;
+            // 非 UTF-8 文件名为系统代码页，按 Latin-1 读取
+            let attributes = readU16(&entry, 14);
+            let Name = if attributes & ATTRIBUTE_NAME_IS_UTF != 0 {
+                String::from_utf8_lossy(&nameBytes).to_string()
+            } else {
+                nameBytes.iter().map(|byte| *byte as char).collect()
+            };
+            Files.push(CabFile {
+                Name,
+                Size: readU32(&entry, 0),
+                Offset: readU32(&entry, 4),
+                Folder: readU16(&entry, 8),
+                Date: readU16(&entry, 10),
+                Time: readU16(&entry, 12),
+                Attributes: attributes & ATTRIBUTE_MASK,
+            });
+        }
+        Ok(Some(Cabinet { file, reserveSize, Folders, Files }))
+    }
+
+    /// 是否支持解压全部文件（未压缩或 MSZIP 压缩，且不跨分卷）
+    pub fn isSupported(&self) -> bool {
+        self.Folders.iter().all(|folder| matches!(folder.Compression & 0x0F, COMPRESS_NONE | COMPRESS_MSZIP))
+            && self.Files.iter().all(|file| file.Folder < FOLDER_CONTINUED && (file.Folder as usize) < self.Folders.len())
+    }
+
+    /// 读取文件夹的解压数据
+    /// # 参数
+    /// 1. 文件夹序号
+    /// # 返回
+    /// 按数据块逐个解压的数据流，文件夹不存在或压缩类型不支持时为 None
+    pub fn readFolder(&mut self, index: usize) -> Option<FolderReader<'_>> {
+        let folder = self.Folders.get(index)?;
+        let compression = folder.Compression & 0x0F;
+        if !matches!(compression, COMPRESS_NONE | COMPRESS_MSZIP) {
+            return None;
+        }
+        Some(FolderReader {
+            file: &mut self.file,
+            reserveSize: self.reserveSize,
+            compression,
+            offset: folder.DataOffset,
+            remaining: folder.DataCount,
+            buffer: Vec::new(),
+            position: 0,
+        })
+    }
+}
+
+/// 文件夹的解压数据流
+/// # 规则
+/// 1. 每次读取一个数据块，数据块头为 校验和、压缩大小、解压大小，之后为保留区域及压缩数据
+/// 2. MSZIP 数据块以 “CK” 开头，各块为独立的 deflate 数据，但可引用之前数据块解压的内容（32KB 窗口），因此保留最近 32KB 的解压数据
+pub struct FolderReader<'a> {
+    file: &'a mut BufReader<File>,
+    reserveSize: usize,
+    compression: u16,
+    /// 下一个数据块的偏移
+    offset: u64,
+    /// 剩余数据块数量
+    remaining: u16,
+    /// 历史窗口及当前数据块的解压数据
+    buffer: Vec<u8>,
+    /// 当前数据块中已读取的位置
+    position: usize,
+}
+
+impl FolderReader<'_> {
+    /// 解压下一个数据块，没有剩余数据块时返回 false
+    fn nextBlock(&mut self) -> io::Result<bool> {
+        if self.remaining == 0 {
+            return Ok(false);
+        }
+        self.remaining -= 1;
+        let invalidData = || io::Error::new(io::ErrorKind::InvalidData, "Invalid cabinet data block");
+
+        let mut header = [0u8; DATA_HEADER_SIZE];
+        self.file.seek(SeekFrom::Start(self.offset))?;
+        self.file.read_exact(&mut header)?;
+        let compressedSize = readU16(&header, 4) as usize;
+        let size = readU16(&header, 6) as usize;
+        let mut block = vec![0u8; compressedSize];
+        self.file.seek_relative(self.reserveSize as i64)?;
+        self.file.read_exact(&mut block)?;
+        self.offset += (DATA_HEADER_SIZE + self.reserveSize + compressedSize) as u64;
+
+        // 仅保留最近 32KB 作为历史窗口
+        let expired = self.buffer.len().saturating_sub(MSZIP_BLOCK_SIZE);
+        self.buffer.drain(..expired);
+        let start = self.buffer.len();
+        if self.compression == COMPRESS_NONE {
+            self.buffer.extend_from_slice(block.get(..size).ok_or_else(invalidData)?);
+        } else {
+            if size > MSZIP_BLOCK_SIZE || !block.starts_with(b"CK") {
+                return Err(invalidData());
+            }
+            // 输出缓冲区不循环使用，历史窗口即为之前解压的内容
+            self.buffer.resize(start + size, 0);
+            let mut decompressor = DecompressorOxide::new();
+            let (status, _, written) = decompress(&mut decompressor, &block[2..], &mut self.buffer, start, inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF);
+            if status != TINFLStatus::Done || written != size {
+                return Err(invalidData());
+            }
+        }
+        self.position = start;
+        Ok(true)
+    }
+}
+
+impl Read for FolderReader<'_> {
+    fn read(&mut self, output: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            if !self.nextBlock()? {
+                return Ok(0);
+            }
+        }
+        let count = output.len().min(self.buffer.len() - self.position);
+        output[..count].copy_from_slice(&self.buffer[self.position..self.position + count]);
+        self.position += count;
+        Ok(count)
+    }
+}
+
+/// 读取以空字符结尾的字符串（不含空字符）
+fn readString(file: &mut BufReader<File>) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    file.read_until(0, &mut bytes)?;
+    if bytes.pop() != Some(0) {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// 读取小端序 16 位整数
+fn readU16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+/// 读取小端序 32 位整数
+fn readU32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
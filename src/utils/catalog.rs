@@ -0,0 +1,794 @@
+// [编录文件](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/catalog-files)
+// 编录文件（.cat）为 PKCS#7 SignedData，签名内容为证书信任列表（CTL），DER 编码
+// SignedData    { 版本, 摘要算法, 内容 { szOID_CTL, [0] CTL }, [0] 证书, [1] CRL, 签名者信息 }
+// CTL           { 用途, [标识], [序号], 更新时间, [下次更新时间], 成员算法, 成员列表, [0] 扩展 }
+// 成员          { 标签（文件哈希）, 属性集 { 名称-值（File、OSAttr）, SPC_INDIRECT_DATA（哈希类型及哈希值）... } }
+// 扩展          编录属性（名称-值，如 OS）
+// 成员的哈希为 Authenticode PE 映像哈希（PE 文件）或整个文件的哈希（其他文件）
+// 签名者信息 { 版本, 签名者标识, 摘要算法, [0] 签名属性 { 消息摘要（CTL 的摘要）... }, 签名算法, 签名值 }，签名为对签名属性的 RSA 签名
+
+use crate::utils::cryptAPI::{certificateTrust, CertificateTrust};
+use rsa::{BigUint, Pkcs1v15Sign, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// PKCS#7 SignedData
+const OID_SIGNED_DATA: &str = "1.2.840.113549.1.7.2";
+/// 证书信任列表（szOID_CTL）
+const OID_CTL: &str = "1.3.6.1.4.1.311.10.1";
+/// 编录名称-值属性（CAT_NAMEVALUE_OBJID）
+const OID_NAME_VALUE: &str = "1.3.6.1.4.1.311.12.2.1";
+/// Authenticode 间接数据（SPC_INDIRECT_DATA_OBJID）
+const OID_INDIRECT_DATA: &str = "1.3.6.1.4.1.311.2.1.4";
+/// PE 映像数据（SPC_PE_IMAGE_DATA_OBJID）
+const OID_PE_IMAGE_DATA: &str = "1.3.6.1.4.1.311.2.1.15";
+/// SHA-1 页哈希（SPC_PE_IMAGE_PAGE_HASHES_V1_OBJID）的 DER 编码
+const PAGE_HASHES_V1: &[u8] = &[0x06, 0x0A, 0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x02, 0x03, 0x01];
+/// SHA-256 页哈希（SPC_PE_IMAGE_PAGE_HASHES_V2_OBJID）的 DER 编码
+const PAGE_HASHES_V2: &[u8] = &[0x06, 0x0A, 0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x02, 0x03, 0x02];
+/// SHA-1
+const OID_SHA1: &str = "1.3.14.3.2.26";
+/// SHA-256
+const OID_SHA256: &str = "2.16.840.1.101.3.4.2.1";
+/// SHA-384
+const OID_SHA384: &str = "2.16.840.1.101.3.4.2.2";
+/// SHA-512
+const OID_SHA512: &str = "2.16.840.1.101.3.4.2.3";
+/// RSA 公钥（rsaEncryption）
+const OID_RSA: &str = "1.2.840.113549.1.1.1";
+/// 消息摘要属性（messageDigest）
+const OID_MESSAGE_DIGEST: &str = "1.2.840.113549.1.9.4";
+/// 增强型密钥用法扩展
+const OID_EXTENDED_KEY_USAGE: &str = "2.5.29.37";
+/// WHQL 驱动签名用途（Windows Hardware Driver Verification）
+const OID_WHQL_CRYPTO: &str = "1.3.6.1.4.1.311.10.3.5";
+/// Windows 系统组件签名用途（Windows System Component Verification）
+const OID_NT5_CRYPTO: &str = "1.3.6.1.4.1.311.10.3.6";
+/// RSA 公钥的最大长度（位）
+const RSA_MAX_SIZE: usize = 16384;
+
+lazy_static! {
+    /// 签名证书（DER 编码） => 系统验证的证书链信任状态
+    static ref TRUST_CACHE: Mutex<HashMap<Vec<u8>, CertificateTrust>> = Mutex::new(HashMap::new());
+}
+
+/// Microsoft 根证书的 RSA 公钥（模数，公钥指数为 65537）
+/// Microsoft Root Certificate Authority 2010：WHQL 签名（Microsoft Windows Third Party Component CA 2012 等）的根证书
+/// 其他根证书（如 Microsoft Root Authority）由系统验证
+const MICROSOFT_ROOTS: &[&[u8]] = &[&[
+    0xB9, 0x08, 0x9E, 0x28, 0xE4, 0xE4, 0xEC, 0x06, 0x4E, 0x50, 0x68, 0xB3, 0x41, 0xC5, 0x7B, 0xEB,
+    0xAE, 0xB6, 0x8E, 0xAF, 0x81, 0xBA, 0x22, 0x44, 0x1F, 0x65, 0x34, 0x69, 0x4C, 0xBE, 0x70, 0x40,
+    0x17, 0xF2, 0x16, 0x7B, 0xE2, 0x79, 0xFD, 0x86, 0xED, 0x0D, 0x39, 0xF4, 0x1B, 0xA8, 0xAD, 0x92,
+    0x90, 0x1E, 0xCB, 0x3D, 0x76, 0x8F, 0x5A, 0xD9, 0xB5, 0x91, 0x10, 0x2E, 0x3C, 0x05, 0x8D, 0x8A,
+    0x6D, 0x24, 0x54, 0xE7, 0x1F, 0xED, 0x56, 0xAD, 0x83, 0xB4, 0x50, 0x9C, 0x15, 0xA5, 0x17, 0x74,
+    0x88, 0x59, 0x20, 0xFC, 0x08, 0xC5, 0x84, 0x76, 0xD3, 0x68, 0xD4, 0x6F, 0x28, 0x78, 0xCE, 0x5C,
+    0xB8, 0xF3, 0x50, 0x90, 0x44, 0xFF, 0xE3, 0x63, 0x5F, 0xBE, 0xA1, 0x9A, 0x2C, 0x96, 0x15, 0x04,
+    0xD6, 0x07, 0xFE, 0x1E, 0x84, 0x21, 0xE0, 0x42, 0x31, 0x11, 0xC4, 0x28, 0x36, 0x94, 0xCF, 0x50,
+    0xA4, 0x62, 0x9E, 0xC9, 0xD6, 0xAB, 0x71, 0x00, 0xB2, 0x5B, 0x0C, 0xE6, 0x96, 0xD4, 0x0A, 0x24,
+    0x96, 0xF5, 0xFF, 0xC6, 0xD5, 0xB7, 0x1B, 0xD7, 0xCB, 0xB7, 0x21, 0x62, 0xAF, 0x12, 0xDC, 0xA1,
+    0x5D, 0x37, 0xE3, 0x1A, 0xFB, 0x1A, 0x46, 0x98, 0xC0, 0x9B, 0xC0, 0xE7, 0x63, 0x1F, 0x2A, 0x08,
+    0x93, 0x02, 0x7E, 0x1E, 0x6A, 0x8E, 0xF2, 0x9F, 0x18, 0x89, 0xE4, 0x22, 0x85, 0xA2, 0xB1, 0x84,
+    0x57, 0x40, 0xFF, 0xF5, 0x0E, 0xD8, 0x6F, 0x9C, 0xED, 0xE2, 0x45, 0x31, 0x01, 0xCD, 0x17, 0xE9,
+    0x7F, 0xB0, 0x81, 0x45, 0xE3, 0xAA, 0x21, 0x40, 0x26, 0xA1, 0x72, 0xAA, 0xA7, 0x4F, 0x3C, 0x01,
+    0x05, 0x7E, 0xEE, 0x83, 0x58, 0xB1, 0x5E, 0x06, 0x63, 0x99, 0x62, 0x91, 0x78, 0x82, 0xB7, 0x0D,
+    0x93, 0x0C, 0x24, 0x6A, 0xB4, 0x1B, 0xDB, 0x27, 0xEC, 0x5F, 0x95, 0x04, 0x3F, 0x93, 0x4A, 0x30,
+    0xF5, 0x97, 0x18, 0xB3, 0xA7, 0xF9, 0x19, 0xA7, 0x93, 0x33, 0x1D, 0x01, 0xC8, 0xDB, 0x22, 0x52,
+    0x5C, 0xD7, 0x25, 0xC9, 0x46, 0xF9, 0xA2, 0xFB, 0x87, 0x59, 0x43, 0xBE, 0x9B, 0x62, 0xB1, 0x8D,
+    0x2D, 0x86, 0x44, 0x1A, 0x46, 0xAC, 0x78, 0x61, 0x7E, 0x30, 0x09, 0xFA, 0xAE, 0x89, 0xC4, 0x41,
+    0x2A, 0x22, 0x66, 0x03, 0x91, 0x39, 0x45, 0x9C, 0xC7, 0x8B, 0x0C, 0xA8, 0xCA, 0x0D, 0x2F, 0xFB,
+    0x52, 0xEA, 0x0C, 0xF7, 0x63, 0x33, 0x23, 0x9D, 0xFE, 0xB0, 0x1F, 0xAD, 0x67, 0xD6, 0xA7, 0x50,
+    0x03, 0xC6, 0x04, 0x70, 0x63, 0xB5, 0x2C, 0xB1, 0x86, 0x5A, 0x43, 0xB7, 0xFB, 0xAE, 0xF9, 0x6E,
+    0x29, 0x6E, 0x21, 0x21, 0x41, 0x26, 0x06, 0x8C, 0xC9, 0xC3, 0xEE, 0xB0, 0xC2, 0x85, 0x93, 0xA1,
+    0xB9, 0x85, 0xD9, 0xE6, 0x32, 0x6C, 0x4B, 0x4C, 0x3F, 0xD6, 0x5D, 0xA3, 0xE5, 0xB5, 0x9D, 0x77,
+    0xC3, 0x9C, 0xC0, 0x55, 0xB7, 0x74, 0x00, 0xE3, 0xB8, 0x38, 0xAB, 0x83, 0x97, 0x50, 0xE1, 0x9A,
+    0x42, 0x24, 0x1D, 0xC6, 0xC0, 0xA3, 0x30, 0xD1, 0x1A, 0x5A, 0xC8, 0x52, 0x34, 0xF7, 0x73, 0xF1,
+    0xC7, 0x18, 0x1F, 0x33, 0xAD, 0x7A, 0xEC, 0xCB, 0x41, 0x60, 0xF3, 0x23, 0x94, 0x20, 0xC2, 0x48,
+    0x45, 0xAC, 0x5C, 0x51, 0xC6, 0x2E, 0x80, 0xC2, 0xE2, 0x77, 0x15, 0xBD, 0x85, 0x87, 0xED, 0x36,
+    0x9D, 0x96, 0x91, 0xEE, 0x00, 0xB5, 0xA3, 0x70, 0xEC, 0x9F, 0xE3, 0x8D, 0x80, 0x68, 0x83, 0x76,
+    0xBA, 0xAF, 0x5D, 0x70, 0x52, 0x22, 0x16, 0xE2, 0x66, 0xFB, 0xBA, 0xB3, 0xC5, 0xC2, 0xF7, 0x3E,
+    0x2F, 0x77, 0xA6, 0xCA, 0xDE, 0xC1, 0xA6, 0xC6, 0x48, 0x4C, 0xC3, 0x37, 0x51, 0x23, 0xD3, 0x27,
+    0xD7, 0xB8, 0x4E, 0x70, 0x96, 0xF0, 0xA1, 0x44, 0x76, 0xAF, 0x78, 0xCF, 0x9A, 0xE1, 0x66, 0x13,
+]];
+
+/// 测试根证书的 RSA 公钥（testDrive/catalog/generate.py 生成），仅测试时与 Microsoft 根证书同等对待
+#[cfg(test)]
+const TEST_ROOTS: &[&[u8]] = &[include_bytes!("../../testDrive/catalog/root.modulus")];
+#[cfg(not(test))]
+const TEST_ROOTS: &[&[u8]] = &[];
+
+/// 驱动签名状态
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureStatus {
+    /// 未检查（旧版索引或未读取驱动文件）
+    #[default]
+    Unknown = 0,
+    /// Microsoft 签名（WHQL、系统内置）
+    Whql = 1,
+    /// 第三方签名（证书链到受信任的根证书）
+    Signed = 2,
+    /// 未签名（未引用编录文件，或编录文件缺失、无法解析）
+    Unsigned = 3,
+    /// 驱动文件与编录文件不一致，或编录文件的签名无效
+    Tampered = 4,
+    /// 签名有效，但未能确认证书链的根证书（不受信任的根证书、测试签名、不支持的算法）
+    Unverified = 5,
+}
+
+impl SignatureStatus {
+    /// 由编号获取签名状态（用于二进制索引）
+    pub fn fromCode(code: u8) -> SignatureStatus {
+        match code {
+            1 => SignatureStatus::Whql,
+            2 => SignatureStatus::Signed,
+            3 => SignatureStatus::Unsigned,
+            4 => SignatureStatus::Tampered,
+            5 => SignatureStatus::Unverified,
+            _ => SignatureStatus::Unknown,
+        }
+    }
+}
+
+/// 哈希算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// 计算多段数据的哈希
+    fn digest(self, parts: &[&[u8]]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Sha1 => {
+                let mut hasher = Sha1::new();
+                parts.iter().for_each(|part| hasher.update(part));
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                parts.iter().for_each(|part| hasher.update(part));
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Sha384 => {
+                let mut hasher = Sha384::new();
+                parts.iter().for_each(|part| hasher.update(part));
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                parts.iter().for_each(|part| hasher.update(part));
+                hasher.finalize().to_vec()
+            }
+        }
+    }
+
+    /// PKCS#1 v1.5 签名中 DigestInfo { 算法, 哈希值 } 在哈希值之前的编码
+    fn digestInfoPrefix(self) -> &'static [u8] {
+        match self {
+            HashAlgorithm::Sha1 => &[0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04, 0x14],
+            HashAlgorithm::Sha256 => &[0x30, 0x31, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20],
+            HashAlgorithm::Sha384 => &[0x30, 0x41, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02, 0x05, 0x00, 0x04, 0x30],
+            HashAlgorithm::Sha512 => &[0x30, 0x51, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03, 0x05, 0x00, 0x04, 0x40],
+        }
+    }
+}
+
+/// 成员哈希类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashKind {
+    /// Authenticode PE 映像哈希
+    Authenticode,
+    /// 整个文件的哈希
+    Flat,
+}
+
+/// 编录成员
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogMember {
+    /// 标签（哈希值的十六进制大写形式，非哈希标签为原文）
+    pub(crate) Tag: String,
+    /// 哈希算法
+    pub(crate) Algorithm: HashAlgorithm,
+    /// 哈希值（非哈希标签为空）
+    pub(crate) Hash: Vec<u8>,
+    /// 哈希类型（成员未记录 SPC_INDIRECT_DATA 时为 None）
+    pub(crate) Kind: Option<HashKind>,
+    /// 页哈希的算法（未记录页哈希时为 None）
+    pub(crate) PageHashes: Option<HashAlgorithm>,
+    /// 名称-值属性（如 File、OSAttr）
+    pub(crate) Attributes: Vec<(String, String)>,
+}
+
+impl CatalogMember {
+    /// 获取指定名称的属性（不区分大小写）
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.Attributes.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+/// 编录文件
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Catalog {
+    /// 成员列表
+    pub(crate) Members: Vec<CatalogMember>,
+    /// 编录属性（如 OS）
+    pub(crate) Attributes: Vec<(String, String)>,
+    /// 签名者证书主题（如 `CN=..., O=..., C=...`）
+    pub(crate) Signers: Vec<String>,
+    /// 签名状态（校验首个签名者的签名及证书链）
+    pub(crate) Status: SignatureStatus,
+}
+
+impl Catalog {
+    /// 读取编录文件
+    /// # 参数
+    /// 1. 编录文件路径
+    pub fn open(catalogPath: &Path) -> Result<Catalog, Box<dyn Error>> {
+        Catalog::parse(&fs::read(catalogPath)?)
+    }
+
+    /// 解析编录文件
+    /// # 参数
+    /// 1. 编录文件内容
+    pub fn parse(data: &[u8]) -> Result<Catalog, Box<dyn Error>> {
+        let (root, _) = Der::read(data)?;
+        let contentInfo = root.children()?;
+        if contentInfo.len() < 2 || contentInfo[0].oid() != OID_SIGNED_DATA {
+            return Err("Invalid catalog".into());
+        }
+        let signedData = contentInfo[1].first()?.children()?;
+
+        // 签名内容（CTL），内容可能包装在 OCTET STRING 中，此时摘要按 OCTET STRING 的内容计算
+        let encapContent = signedData.get(2).ok_or("Invalid catalog")?.children()?;
+        if encapContent.len() < 2 || encapContent[0].oid() != OID_CTL {
+            return Err("Invalid catalog".into());
+        }
+        let mut ctl = encapContent[1].first()?;
+        let content = ctl.content;
+        if ctl.tag == TAG_OCTET_STRING {
+            ctl = Der::read(ctl.content)?.0;
+        }
+        let mut catalog = Catalog::default();
+        catalog.parseCtl(&ctl)?;
+
+        // 证书及签名者信息
+        let certificates: Vec<Certificate> = signedData.iter().find(|item| item.tag == 0xA0).map(|item| item.children()).transpose()?.unwrap_or_default().iter().filter_map(|item| Certificate::parse(item).ok()).collect();
+        let mut signerList: Vec<(Der, usize)> = Vec::new();
+        if let Some(signerInfos) = signedData.iter().skip(3).find(|item| item.tag == TAG_SET) {
+            for signerInfo in signerInfos.children()? {
+                // 签名者标识：IssuerAndSerialNumber
+                let Some(sid) = signerInfo.children()?.get(1).copied() else { continue };
+                if sid.tag != TAG_SEQUENCE {
+                    continue;
+                }
+                let sid = sid.children()?;
+                let (Some(issuer), Some(serial)) = (sid.first(), sid.get(1)) else { continue };
+                if let Some(index) = certificates.iter().position(|item| item.issuer == issuer.raw && item.serial == serial.content) {
+                    catalog.Signers.push(certificates[index].subject.clone());
+                    signerList.push((signerInfo, index));
+                }
+            }
+        }
+        catalog.Status = match signerList.first() {
+            Some((signerInfo, index)) => verifySigner(signerInfo, *index, &certificates, content)?,
+            None => SignatureStatus::Unsigned,
+        };
+        Ok(catalog)
+    }
+
+    /// 解析证书信任列表
+    fn parseCtl(&mut self, ctl: &Der) -> Result<(), Box<dyn Error>> {
+        // 跳过用途、标识、序号、更新时间与成员算法
+        let items = ctl.children()?;
+        let mut rest = items.iter().skip(1).skip_while(|item| item.tag == TAG_OCTET_STRING || item.tag == TAG_INTEGER).skip_while(|item| item.tag == TAG_UTC_TIME || item.tag == TAG_GENERALIZED_TIME).skip(1).peekable();
+
+        if let Some(subjects) = rest.next_if(|item| item.tag == TAG_SEQUENCE) {
+            for subject in subjects.children()? {
+                if let Ok(member) = parseMember(&subject) {
+                    self.Members.push(member);
+                }
+            }
+        }
+        if let Some(extensions) = rest.next_if(|item| item.tag == 0xA0) {
+            // 扩展 { OID, [critical], OCTET STRING { 名称-值 } }
+            for extension in extensions.first()?.children()? {
+                let fields = extension.children()?;
+                if fields.first().is_some_and(|item| item.oid() == OID_NAME_VALUE) {
+                    if let Some(value) = fields.last() {
+                        if let Ok(attribute) = parseNameValue(&Der::read(value.content)?.0) {
+                            self.Attributes.push(attribute);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 获取指定名称的编录属性（不区分大小写）
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.Attributes.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+
+    /// 各成员的 OSAttr（去重，按出现顺序）
+    pub fn osAttr(&self) -> Vec<String> {
+        let mut osAttrList: Vec<String> = Vec::new();
+        for value in self.Members.iter().filter_map(|member| member.attribute("OSAttr")) {
+            if !osAttrList.iter().any(|item| item == value) {
+                osAttrList.push(value.to_string());
+            }
+        }
+        osAttrList
+    }
+
+    /// 首个签名者的证书主题
+    pub fn signer(&self) -> Option<&str> {
+        self.Signers.first().map(String::as_str)
+    }
+
+    /// 签名状态
+    pub fn status(&self) -> SignatureStatus {
+        self.Status
+    }
+
+    /// 查找与文件内容匹配的成员（PE 文件同时比较 Authenticode 映像哈希与整个文件的哈希）
+    /// # 参数
+    /// 1. 文件内容
+    pub fn findFile(&self, content: &[u8]) -> Vec<&CatalogMember> {
+        let image = authenticodeRanges(content);
+        let mut memberList: Vec<&CatalogMember> = Vec::new();
+        for algorithm in [HashAlgorithm::Sha1, HashAlgorithm::Sha256] {
+            if !self.Members.iter().any(|member| member.Algorithm == algorithm && !member.Hash.is_empty()) {
+                continue;
+            }
+            let flat = algorithm.digest(&[content]);
+            let imageHash = image.as_ref().map(|parts| algorithm.digest(parts));
+            memberList.extend(self.Members.iter().filter(|member| member.Algorithm == algorithm && (member.Hash == flat || imageHash.as_ref() == Some(&member.Hash))));
+        }
+        memberList
+    }
+}
+
+/// 解析编录成员 { 标签, 属性集 }
+fn parseMember(subject: &Der) -> Result<CatalogMember, Box<dyn Error>> {
+    let fields = subject.children()?;
+    let tag = fields.first().ok_or("Invalid catalog member")?.content;
+    let mut member = CatalogMember {
+        Tag: String::new(),
+        Algorithm: HashAlgorithm::Sha1,
+        Hash: Vec::new(),
+        Kind: None,
+        PageHashes: None,
+        Attributes: Vec::new(),
+    };
+
+    for attribute in fields.get(1).map(|item| item.children()).transpose()?.unwrap_or_default() {
+        let attribute = attribute.children()?;
+        let (Some(oid), Some(values)) = (attribute.first(), attribute.get(1)) else { continue };
+        let Ok(value) = values.first() else { continue };
+        match oid.oid().as_str() {
+            OID_NAME_VALUE => member.Attributes.push(parseNameValue(&value)?),
+            OID_INDIRECT_DATA => {
+                // SpcIndirectDataContent { 数据 { 类型, 值 }, 摘要 { 算法, 哈希值 } }
+                let indirect = value.children()?;
+                let (Some(data), Some(digest)) = (indirect.first(), indirect.get(1)) else { continue };
+                let digest = digest.children()?;
+                let (Some(algorithm), Some(hash)) = (digest.first(), digest.get(1)) else { continue };
+                let Some(algorithm) = hashAlgorithm(&algorithm.first()?.oid()) else { continue };
+                member.Algorithm = algorithm;
+                member.Hash = hash.content.to_vec();
+                member.Kind = Some(if data.first()?.oid() == OID_PE_IMAGE_DATA { HashKind::Authenticode } else { HashKind::Flat });
+                member.PageHashes = if contains(data.raw, PAGE_HASHES_V2) {
+                    Some(HashAlgorithm::Sha256)
+                } else if contains(data.raw, PAGE_HASHES_V1) {
+                    Some(HashAlgorithm::Sha1)
+                } else {
+                    None
+                };
+            }
+            _ => {}
+        }
+    }
+
+    // 未记录 SPC_INDIRECT_DATA 的成员以标签为哈希值（二进制或 UTF-16 十六进制字符串）
+    if member.Hash.is_empty() {
+        let text = utf16(tag);
+        if let Some(hash) = [20, 32].contains(&tag.len()).then(|| tag.to_vec()).or_else(|| decodeHex(&text)) {
+            member.Algorithm = if hash.len() == 20 { HashAlgorithm::Sha1 } else { HashAlgorithm::Sha256 };
+            member.Hash = hash;
+        } else {
+            member.Tag = text;
+        }
+    }
+    if !member.Hash.is_empty() {
+        member.Tag = member.Hash.iter().map(|byte| format!("{:02X}", byte)).collect();
+    }
+    Ok(member)
+}
+
+/// 解析名称-值 { BMPString 名称, INTEGER 标志, OCTET STRING 值（UTF-16LE） }
+fn parseNameValue(value: &Der) -> Result<(String, String), Box<dyn Error>> {
+    let fields = value.children()?;
+    let (Some(name), Some(data)) = (fields.first(), fields.get(2)) else { return Err("Invalid catalog attribute".into()) };
+    let name: Vec<u16> = name.content.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+    Ok((String::from_utf16_lossy(&name), utf16(data.content)))
+}
+
+/// 校验签名者的签名及证书链
+/// # 参数
+/// 1. 签名者信息
+/// 2. 签名者证书在证书列表中的下标
+/// 3. 证书列表
+/// 4. 签名内容（CTL 不含标签与长度，包装在 OCTET STRING 中时为 OCTET STRING 的内容）
+/// # 规则
+/// 1. 签名属性中的消息摘要须与签名内容的摘要一致，且签名能以签名者证书的公钥验证，否则视为编录文件被篡改
+/// 2. 由签名者证书沿颁发者构建证书链，每个证书须能以颁发者证书的公钥验证
+/// 3. 证书链顶端的证书由内置的 Microsoft 根证书签名，或由系统验证链到 Microsoft 根证书，且签名者证书具有 WHQL 或 Windows 系统组件签名用途的为 WHQL
+/// 4. 由系统验证链到受信任的根证书的为第三方签名，否则为未验证（含不支持的签名算法）
+fn verifySigner(signerInfo: &Der, signer: usize, certificates: &[Certificate], content: &[u8]) -> Result<SignatureStatus, Box<dyn Error>> {
+    let fields = signerInfo.children()?;
+    let Some(digestAlgorithm) = fields.get(2).and_then(|item| item.first().ok()).and_then(|item| hashAlgorithm(&item.oid())) else { return Ok(SignatureStatus::Unverified) };
+    let signedAttributes = fields.get(3).filter(|item| item.tag == 0xA0);
+    let rest: Vec<&Der> = fields.iter().skip(if signedAttributes.is_some() { 4 } else { 3 }).collect();
+    let (Some(signatureAlgorithm), Some(signature)) = (rest.first(), rest.get(1)) else { return Err("Invalid signer".into()) };
+    let signatureAlgorithm = signatureAlgorithm.first()?.oid();
+    if signatureAlgorithm != OID_RSA && signatureHash(&signatureAlgorithm).is_none() {
+        return Ok(SignatureStatus::Unverified);
+    }
+
+    // 有签名属性时签名的是签名属性（以 SET 标签编码），否则为签名内容
+    let digest = digestAlgorithm.digest(&[content]);
+    let signed = match signedAttributes {
+        Some(attributes) => {
+            let mut messageDigest = None;
+            for attribute in attributes.children()? {
+                let attribute = attribute.children()?;
+                if attribute.first().is_some_and(|oid| oid.oid() == OID_MESSAGE_DIGEST) {
+                    messageDigest = attribute.get(1).map(|values| values.first()).transpose()?;
+                }
+            }
+            if messageDigest.is_none_or(|value| value.content != digest) {
+                return Ok(SignatureStatus::Tampered);
+            }
+            [&[TAG_SET], &attributes.raw[1..]].concat()
+        }
+        None => content.to_vec(),
+    };
+    match certificates[signer].verify(Some(digestAlgorithm), &signed, signature.content) {
+        None => return Ok(SignatureStatus::Unverified),
+        Some(false) => return Ok(SignatureStatus::Tampered),
+        Some(true) => {}
+    }
+
+    // 证书链：颁发者不在编录文件中（或为自签名证书）时到达顶端
+    let mut chain = vec![signer];
+    loop {
+        let current = &certificates[*chain.last().unwrap()];
+        let issuer = (0..certificates.len()).find(|index| {
+            !chain.contains(index) && certificates[*index].subjectName == current.issuer && certificates[*index].verify(current.signatureHash, &current.tbs, &current.signature) == Some(true)
+        });
+        match issuer {
+            Some(index) => chain.push(index),
+            None => break,
+        }
+    }
+    let top = &certificates[*chain.last().unwrap()];
+    let trust = if top.signedByMicrosoftRoot() {
+        CertificateTrust::MicrosoftRoot
+    } else {
+        // 同一签名证书只构建一次证书链（驱动包中的编录文件通常使用同一签名证书）
+        let mut trustCache = TRUST_CACHE.lock().unwrap();
+        *trustCache.entry(certificates[signer].raw.clone()).or_insert_with(|| {
+            let encodedList: Vec<&[u8]> = certificates.iter().map(|certificate| certificate.raw.as_slice()).collect();
+            unsafe { certificateTrust(&encodedList, signer) }
+        })
+    };
+    let whqlUsage = certificates[signer].usages.iter().any(|usage| usage == OID_WHQL_CRYPTO || usage == OID_NT5_CRYPTO);
+    Ok(match trust {
+        CertificateTrust::MicrosoftRoot if whqlUsage => SignatureStatus::Whql,
+        CertificateTrust::MicrosoftRoot | CertificateTrust::Trusted => SignatureStatus::Signed,
+        CertificateTrust::Untrusted => SignatureStatus::Unverified,
+    })
+}
+
+/// 哈希算法 OID
+fn hashAlgorithm(oid: &str) -> Option<HashAlgorithm> {
+    match oid {
+        OID_SHA1 => Some(HashAlgorithm::Sha1),
+        OID_SHA256 => Some(HashAlgorithm::Sha256),
+        OID_SHA384 => Some(HashAlgorithm::Sha384),
+        OID_SHA512 => Some(HashAlgorithm::Sha512),
+        _ => None,
+    }
+}
+
+/// RSA 签名算法 OID（sha*WithRSAEncryption）对应的哈希算法
+fn signatureHash(oid: &str) -> Option<HashAlgorithm> {
+    match oid {
+        "1.2.840.113549.1.1.5" => Some(HashAlgorithm::Sha1),
+        "1.2.840.113549.1.1.11" => Some(HashAlgorithm::Sha256),
+        "1.2.840.113549.1.1.12" => Some(HashAlgorithm::Sha384),
+        "1.2.840.113549.1.1.13" => Some(HashAlgorithm::Sha512),
+        _ => None,
+    }
+}
+
+/// 验证 PKCS#1 v1.5 RSA 签名
+/// # 参数
+/// 1. RSA 公钥
+/// 2. 哈希算法
+/// 3. 签名的数据
+/// 4. 签名值
+fn verifyRsa(publicKey: &RsaPublicKey, algorithm: HashAlgorithm, data: &[u8], signature: &[u8]) -> bool {
+    let hash = algorithm.digest(&[data]);
+    let scheme = Pkcs1v15Sign { hash_len: Some(hash.len()), prefix: algorithm.digestInfoPrefix().into() };
+    publicKey.verify(scheme, &hash, signature).is_ok()
+}
+
+/// Authenticode PE 映像哈希的数据范围
+/// 排除校验和、证书表目录项及证书表，非 PE 文件为 None
+/// # 参数
+/// 1. 文件内容
+fn authenticodeRanges(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let u32At = |offset: usize| data.get(offset..offset + 4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()));
+    if data.get(..2) != Some(b"MZ") {
+        return None;
+    }
+    let peOffset = u32At(0x3C)? as usize;
+    if data.get(peOffset..peOffset + 4) != Some(b"PE\0\0") {
+        return None;
+    }
+    // 可选头：PE32（0x10B）的数据目录位于偏移 96，PE32+（0x20B）位于偏移 112
+    let optional = peOffset + 24;
+    let directories = match data.get(optional..optional + 2)? {
+        [0x0B, 0x01] => optional + 96,
+        [0x0B, 0x02] => optional + 112,
+        _ => return None,
+    };
+    let checksum = optional + 64;
+    let certEntry = directories + 4 * 8;
+    if certEntry + 8 > data.len() {
+        return None;
+    }
+
+    let (certOffset, certSize) = (u32At(certEntry)? as usize, u32At(certEntry + 4)? as usize);
+    let mut ranges = vec![&data[..checksum], &data[checksum + 4..certEntry]];
+    if certSize > 0 && certOffset >= certEntry + 8 && certOffset.checked_add(certSize).is_some_and(|end| end <= data.len()) {
+        ranges.push(&data[certEntry + 8..certOffset]);
+        ranges.push(&data[certOffset + certSize..]);
+    } else {
+        ranges.push(&data[certEntry + 8..]);
+    }
+    Some(ranges)
+}
+
+/// X.509 证书（仅保留匹配签名者、验证签名所需的字段）
+struct Certificate {
+    /// 证书（DER 编码）
+    raw: Vec<u8>,
+    /// 待签名部分 TBSCertificate（DER 编码）
+    tbs: Vec<u8>,
+    /// 颁发者（DER 编码）
+    issuer: Vec<u8>,
+    /// 序列号
+    serial: Vec<u8>,
+    /// 主题（DER 编码）
+    subjectName: Vec<u8>,
+    /// 主题
+    subject: String,
+    /// RSA 公钥（非 RSA 公钥为 None）
+    publicKey: Option<RsaPublicKey>,
+    /// 证书签名的哈希算法（非 RSA 签名为 None）
+    signatureHash: Option<HashAlgorithm>,
+    /// 证书签名值
+    signature: Vec<u8>,
+    /// 增强型密钥用法（OID）
+    usages: Vec<String>,
+}
+
+impl Certificate {
+    /// 解析证书 { TBSCertificate { [0] 版本, 序列号, 签名算法, 颁发者, 有效期, 主题, 公钥, ..., [3] 扩展 }, 签名算法, 签名值 }
+    fn parse(certificate: &Der) -> Result<Certificate, Box<dyn Error>> {
+        let parts = certificate.children()?;
+        let (Some(tbs), Some(algorithm), Some(signature)) = (parts.first(), parts.get(1), parts.get(2)) else { return Err("Invalid certificate".into()) };
+        let tbsFields = tbs.children()?;
+        let fields: Vec<&Der> = tbsFields.iter().skip_while(|item| item.tag == 0xA0).collect();
+        if fields.len() < 6 {
+            return Err("Invalid certificate".into());
+        }
+
+        // 公钥 { 算法, BIT STRING { 模数, 公钥指数 } }
+        let keyInfo = fields[5].children()?;
+        let publicKey = match (keyInfo.first(), keyInfo.get(1)) {
+            (Some(keyAlgorithm), Some(key)) if keyAlgorithm.first()?.oid() == OID_RSA && !key.content.is_empty() => {
+                let numbers = Der::read(&key.content[1..])?.0.children()?;
+                let (Some(modulus), Some(exponent)) = (numbers.first(), numbers.get(1)) else { return Err("Invalid certificate".into()) };
+                RsaPublicKey::new_with_max_size(BigUint::from_bytes_be(modulus.content), BigUint::from_bytes_be(exponent.content), RSA_MAX_SIZE).ok()
+            }
+            _ => None,
+        };
+
+        // 扩展 { OID, [critical], OCTET STRING }，增强型密钥用法为 OID 列表
+        let mut usages = Vec::new();
+        if let Some(extensions) = fields.iter().find(|item| item.tag == 0xA3) {
+            for extension in extensions.first()?.children()? {
+                let extension = extension.children()?;
+                if extension.first().is_some_and(|oid| oid.oid() == OID_EXTENDED_KEY_USAGE) {
+                    if let Some(value) = extension.last() {
+                        usages.extend(Der::read(value.content)?.0.children()?.iter().map(|oid| oid.oid()));
+                    }
+                }
+            }
+        }
+
+        Ok(Certificate {
+            raw: certificate.raw.to_vec(),
+            tbs: tbs.raw.to_vec(),
+            issuer: fields[2].raw.to_vec(),
+            serial: fields[0].content.to_vec(),
+            subjectName: fields[4].raw.to_vec(),
+            subject: formatName(fields[4])?,
+            publicKey,
+            signatureHash: signatureHash(&algorithm.first()?.oid()),
+            signature: signature.content.get(1..).unwrap_or_default().to_vec(),
+            usages,
+        })
+    }
+
+    /// 以该证书的公钥验证签名
+    /// # 参数
+    /// 1. 哈希算法
+    /// 2. 签名的数据
+    /// 3. 签名值
+    /// # 返回
+    /// 公钥或签名算法不支持时为 None
+    fn verify(&self, algorithm: Option<HashAlgorithm>, data: &[u8], signature: &[u8]) -> Option<bool> {
+        Some(verifyRsa(self.publicKey.as_ref()?, algorithm?, data, signature))
+    }
+
+    /// 证书是否由内置的 Microsoft 根证书签名（根证书本身为自签名，同样适用）
+    fn signedByMicrosoftRoot(&self) -> bool {
+        let Some(algorithm) = self.signatureHash else { return false };
+        MICROSOFT_ROOTS.iter().chain(TEST_ROOTS).any(|modulus| {
+            RsaPublicKey::new_with_max_size(BigUint::from_bytes_be(modulus), BigUint::from(65537u32), RSA_MAX_SIZE).is_ok_and(|publicKey| verifyRsa(&publicKey, algorithm, &self.tbs, &self.signature))
+        })
+    }
+}
+
+/// 证书名称的显示形式（与 Windows 一致，从最后一个 RDN 开始，如 `CN=..., O=..., C=US`）
+fn formatName(name: &Der) -> Result<String, Box<dyn Error>> {
+    let mut parts: Vec<String> = Vec::new();
+    for rdn in name.children()? {
+        for attribute in rdn.children()? {
+            let fields = attribute.children()?;
+            let (Some(oid), Some(value)) = (fields.first(), fields.get(1)) else { continue };
+            let oid = oid.oid();
+            let key = match oid.as_str() {
+                "2.5.4.3" => "CN",
+                "2.5.4.6" => "C",
+                "2.5.4.7" => "L",
+                "2.5.4.8" => "S",
+                "2.5.4.10" => "O",
+                "2.5.4.11" => "OU",
+                "1.2.840.113549.1.9.1" => "E",
+                _ => oid.as_str(),
+            };
+            let text = if value.tag == TAG_BMP_STRING {
+                let units: Vec<u16> = value.content.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+                String::from_utf16_lossy(&units)
+            } else {
+                String::from_utf8_lossy(value.content).to_string()
+            };
+            parts.push(format!("{}={}", key, text));
+        }
+    }
+    parts.reverse();
+    Ok(parts.join(", "))
+}
+
+/// UTF-16LE 字符串（去除末尾的空字符）
+fn utf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units).trim_end_matches('\0').to_string()
+}
+
+/// 解析 SHA-1、SHA-256 哈希的十六进制字符串
+fn decodeHex(text: &str) -> Option<Vec<u8>> {
+    if (text.len() != 40 && text.len() != 64) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len()).step_by(2).map(|index| u8::from_str_radix(&text[index..index + 2], 16).ok()).collect()
+}
+
+/// 是否包含指定的字节序列
+fn contains(data: &[u8], pattern: &[u8]) -> bool {
+    data.windows(pattern.len()).any(|window| window == pattern)
+}
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_BMP_STRING: u8 = 0x1E;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SET: u8 = 0x31;
+
+/// DER 元素（仅支持单字节标签及定长编码）
+#[derive(Debug, Clone, Copy)]
+struct Der<'a> {
+    /// 标签
+    tag: u8,
+    /// 内容
+    content: &'a [u8],
+    /// 完整编码（标签、长度及内容）
+    raw: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    /// 读取一个元素
+    /// # 返回
+    /// (元素, 剩余数据)
+    fn read(data: &'a [u8]) -> Result<(Der<'a>, &'a [u8]), Box<dyn Error>> {
+        let (&tag, rest) = data.split_first().ok_or("Invalid DER")?;
+        let (&first, rest) = rest.split_first().ok_or("Invalid DER")?;
+        let (length, rest) = if first < 0x80 {
+            (first as usize, rest)
+        } else {
+            // 长格式，不支持不定长编码
+            let count = (first & 0x7F) as usize;
+            if count == 0 || count > 4 || rest.len() < count {
+                return Err("Invalid DER".into());
+            }
+            (rest[..count].iter().fold(0usize, |length, &byte| (length << 8) | byte as usize), &rest[count..])
+        };
+        if rest.len() < length {
+            return Err("Invalid DER".into());
+        }
+        let headerLen = data.len() - rest.len();
+        Ok((Der { tag, content: &rest[..length], raw: &data[..headerLen + length] }, &rest[length..]))
+    }
+
+    /// 子元素
+    fn children(&self) -> Result<Vec<Der<'a>>, Box<dyn Error>> {
+        let mut children = Vec::new();
+        let mut rest = self.content;
+        while !rest.is_empty() {
+            let (child, next) = Der::read(rest)?;
+            children.push(child);
+            rest = next;
+        }
+        Ok(children)
+    }
+
+    /// 第一个子元素
+    fn first(&self) -> Result<Der<'a>, Box<dyn Error>> {
+        Ok(Der::read(self.content)?.0)
+    }
+
+    /// OID 的点分形式（非 OID 时为空）
+    fn oid(&self) -> String {
+        if self.tag != TAG_OID || self.content.is_empty() {
+            return String::new();
+        }
+        let mut parts = vec![(self.content[0] / 40) as u64, (self.content[0] % 40) as u64];
+        let mut value = 0u64;
+        for &byte in &self.content[1..] {
+            value = (value << 7) | (byte & 0x7F) as u64;
+            if byte & 0x80 == 0 {
+                parts.push(value);
+                value = 0;
+            }
+        }
+        parts.iter().map(u64::to_string).collect::<Vec<String>>().join(".")
+    }
+}
+
@@ -4,6 +4,10 @@ use crate::utils::util::writeLogFile;
 use crate::LOG_PATH;
 use chrono::Local;
 use console::style;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 静默模式：仅将警告与错误输出到标准错误，使标准输出只包含机器可读的内容
+static QUIET: AtomicBool = AtomicBool::new(false);
 
 pub enum ConsoleType {
     Info,
@@ -12,6 +16,11 @@ pub enum ConsoleType {
     Err,
 }
 
+/// 设置静默模式
+pub fn setQuiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
 pub fn writeConsole(consoleType: ConsoleType, message: &str) {
     let title = match &consoleType {
         ConsoleType::Info => style(getLocaleText("Info", None)).cyan(),
@@ -19,10 +28,36 @@ pub fn writeConsole(consoleType: ConsoleType, message: &str) {
         ConsoleType::Warning => style(getLocaleText("Warning", None)).yellow(),
         ConsoleType::Err => style(getLocaleText("Err", None)).red().on_black().bold(),
     };
-    println!("  {}      {}", &title, message);
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("  {}      {}", &title, message);
+    } else if matches!(consoleType, ConsoleType::Warning | ConsoleType::Err) {
+        eprintln!("  {}      {}", &title, message);
+    }
     if isDebug() {
         let time = Local::now().format("%T").to_string();
         // let tieme = SystemTime::now().duration_since(UNIX_EPOCH);
         writeLogFile(&LOG_PATH, &format!("{} {}  {}", time, console::strip_ansi_codes(&title.to_string()), message)).ok();
     }
 }
+
+/// 按显示宽度对齐表格各列
+/// # 参数
+/// 1. 表格各行（含表头）
+/// # 返回
+/// 对齐后的各行
+pub fn formatTable(rows: &[Vec<String>]) -> Vec<String> {
+    let widths: Vec<usize> = (0..rows.first().map(Vec::len).unwrap_or(0))
+        .map(|column| rows.iter().map(|row| console::measure_text_width(&row[column])).max().unwrap_or(0))
+        .collect();
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - console::measure_text_width(cell))))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
@@ -0,0 +1,59 @@
+use std::ptr::null_mut;
+use windows::core::PCSTR;
+use windows::Win32::Security::Cryptography::{
+    CertAddEncodedCertificateToStore, CertCloseStore, CertFreeCertificateChain, CertFreeCertificateContext, CertGetCertificateChain, CertOpenStore, CertVerifyCertificateChainPolicy, CERT_CHAIN_CACHE_ONLY_URL_RETRIEVAL, CERT_CHAIN_CONTEXT, CERT_CHAIN_DISABLE_AUTH_ROOT_AUTO_UPDATE, CERT_CHAIN_PARA, CERT_CHAIN_POLICY_BASE,
+    CERT_CHAIN_POLICY_IGNORE_ALL_NOT_TIME_VALID_FLAGS, CERT_CHAIN_POLICY_MICROSOFT_ROOT, CERT_CHAIN_POLICY_PARA, CERT_CHAIN_POLICY_STATUS, CERT_CONTEXT, CERT_OPEN_STORE_FLAGS, CERT_QUERY_ENCODING_TYPE, CERT_STORE_ADD_ALWAYS, CERT_STORE_PROV_MEMORY,
+    PKCS_7_ASN_ENCODING, X509_ASN_ENCODING,
+};
+
+/// 证书链的信任状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificateTrust {
+    /// 未链到受信任的根证书（或无法验证）
+    Untrusted,
+    /// 链到受信任的根证书
+    Trusted,
+    /// 链到 Microsoft 根证书
+    MicrosoftRoot,
+}
+
+/// 由系统验证证书链
+/// [参考资料](https://learn.microsoft.com/zh-cn/windows/win32/api/wincrypt/nf-wincrypt-certverifycertificatechainpolicy)
+/// # 参数
+/// 1. 证书列表（DER 编码）
+/// 2. 终端证书在列表中的下标
+/// # 规则
+/// 1. 列表中的证书作为附加证书参与构建证书链，根证书须在系统的受信任根证书中
+/// 2. 不检查吊销状态，忽略有效期（编录文件的签名带有时间戳，签名证书通常已过期）
+/// 3. 不联网获取证书及更新根证书（仅使用本地缓存），以免离线环境中长时间等待
+pub unsafe fn certificateTrust(certificates: &[&[u8]], leaf: usize) -> CertificateTrust {
+    let Ok(store) = CertOpenStore(CERT_STORE_PROV_MEMORY, CERT_QUERY_ENCODING_TYPE(0), None, CERT_OPEN_STORE_FLAGS(0), None) else {
+        return CertificateTrust::Untrusted;
+    };
+    let encoding = CERT_QUERY_ENCODING_TYPE(X509_ASN_ENCODING.0 | PKCS_7_ASN_ENCODING.0);
+    let mut leafContext: *mut CERT_CONTEXT = null_mut();
+    for (index, certificate) in certificates.iter().enumerate() {
+        let context = (index == leaf).then_some(&mut leafContext as *mut *mut CERT_CONTEXT);
+        CertAddEncodedCertificateToStore(Some(store), encoding, certificate, CERT_STORE_ADD_ALWAYS, context).ok();
+    }
+
+    let mut trust = CertificateTrust::Untrusted;
+    if !leafContext.is_null() {
+        let chainPara = CERT_CHAIN_PARA { cbSize: size_of::<CERT_CHAIN_PARA>() as u32, ..Default::default() };
+        let mut chain: *mut CERT_CHAIN_CONTEXT = null_mut();
+        if CertGetCertificateChain(None, leafContext, None, Some(store), &chainPara, CERT_CHAIN_CACHE_ONLY_URL_RETRIEVAL | CERT_CHAIN_DISABLE_AUTH_ROOT_AUTO_UPDATE, None, &mut chain).is_ok() && !chain.is_null() {
+            let policyPara = CERT_CHAIN_POLICY_PARA { cbSize: size_of::<CERT_CHAIN_POLICY_PARA>() as u32, dwFlags: CERT_CHAIN_POLICY_IGNORE_ALL_NOT_TIME_VALID_FLAGS, ..Default::default() };
+            let verify = |policy: PCSTR| {
+                let mut status = CERT_CHAIN_POLICY_STATUS { cbSize: size_of::<CERT_CHAIN_POLICY_STATUS>() as u32, ..Default::default() };
+                CertVerifyCertificateChainPolicy(policy, chain, &policyPara, &mut status).as_bool() && status.dwError == 0
+            };
+            if verify(CERT_CHAIN_POLICY_BASE) {
+                trust = if verify(CERT_CHAIN_POLICY_MICROSOFT_ROOT) { CertificateTrust::MicrosoftRoot } else { CertificateTrust::Trusted };
+            }
+            CertFreeCertificateChain(chain);
+        }
+        let _ = CertFreeCertificateContext(Some(leafContext));
+    }
+    CertCloseStore(Some(store), 0).ok();
+    trust
+}
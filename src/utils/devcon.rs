@@ -1,12 +1,14 @@
 use std::error::Error;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use crate::utils::util::{writeEmbedFile, String_utils};
+use crate::utils::devconParser::{decodeOutput, parseHwids, parseStatus, DeviceState, DeviceStatus};
+use crate::utils::util::writeEmbedFile;
 use crate::TEMP_PATH;
+use serde::{Deserialize, Serialize};
 use windows_version::OsVersion;
 
 /// 硬件信息
-#[derive(Debug, Clone, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq)]
 pub struct HwID {
     /// 设备实例路径
     pub(crate) DeviceInstancePath: String,
@@ -16,6 +18,9 @@ pub struct HwID {
     pub(crate) HardwareIDs: Vec<String>,
     /// 兼容id
     pub(crate) CompatibleIDs: Vec<String>,
+    /// 运行状态（为 None 时未获取）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) Status: Option<DeviceState>,
 }
 
 impl PartialEq for HwID {
@@ -24,6 +29,18 @@ impl PartialEq for HwID {
     }
 }
 
+impl HwID {
+    /// 设备是否有问题
+    pub fn hasProblem(&self) -> bool {
+        self.Status.as_ref().is_some_and(DeviceState::hasProblem)
+    }
+
+    /// CM 问题代码（如 28 为未安装驱动）
+    pub fn problemCode(&self) -> Option<u32> {
+        self.Status.as_ref().and_then(DeviceState::problemCode)
+    }
+}
+
 /// Devcon操作类
 /// # 如何获取Devcon？
 /// [WDK 下载](https://docs.microsoft.com/zh-cn/windows-hardware/drivers/download-the-wdk)
@@ -57,123 +74,52 @@ impl Devcon {
             .arg(hwidType)
             .output()?;
 
-        let content = String::from_utf8_lossy(&output.stdout);
-
-        // 将 Name 与 Hardware IDs 分离
-        let content = content.replace("     Hardware IDs:", "\r\n    Hardware IDs:");
-        // 将 Name 与 Compatible IDs 分离，并加上空的Hardware IDs
-        let content = content.replace(
-            "     Compatible IDs:",
-            "\r\n    Hardware IDs:\r\n    Compatible IDs:",
-        );
-
-        const DELIMITER: &str = "|";
-        const SUBDELIMITER: &str = ",";
-
-        // 将输出的运行状态转换为每个项目一行以便读取
-        let contentLine = content.replace("\r\n        ", SUBDELIMITER);
-        let contentLine = contentLine.replace("\r\n    ", DELIMITER);
-        let contentLine = contentLine.replace("  ", "");
-        let contentLine = contentLine.replace("\r\n", &format!("{}\r\n", DELIMITER));
-
-        let mut HwIDList: Vec<HwID> = Vec::new();
-        // 通过换行符分割遍历
-        for item in contentLine.lines() {
-            // 获取设备实例路径
-            let DeviceInstancePath = item
-                .to_string()
-                .get_string_left(DELIMITER)
-                .unwrap_or_else(|_| "".to_string());
-
-            // 获取显示名称
-            let name = item
-                .to_string()
-                .get_string_center("Name:", DELIMITER)
-                .unwrap_or_else(|_| "".to_string());
-
-            // 获取硬件id
-            let hardwareIDs = item
-                .to_string()
-                .get_string_center("Hardware IDs:", DELIMITER)
-                .unwrap_or_else(|_| "".to_string())
-                .replace(DELIMITER, "");
-            let hardwareIDList: Vec<String> = hardwareIDs
-                .split(SUBDELIMITER)
-                .filter(|&hardwareID| !hardwareID.is_empty())
-                .map(|hardwareID| hardwareID.to_string())
-                .collect();
-
-            // 获取兼容id
-            let CompatibleIDs = item
-                .to_string()
-                .get_string_right("Compatible IDs:")
-                .unwrap_or_else(|_| "".to_string())
-                .replace(DELIMITER, "");
-            let CompatibleIDList: Vec<String> = CompatibleIDs
-                .split(SUBDELIMITER)
-                .filter(|&CompatibleID| !CompatibleID.is_empty())
-                .map(|CompatibleID| CompatibleID.to_string())
-                .collect();
-
-            HwIDList.push(HwID {
-                DeviceInstancePath,
-                Name: name,
-                HardwareIDs: hardwareIDList,
-                CompatibleIDs: CompatibleIDList,
-            });
-        }
-        Ok(HwIDList)
+        Ok(parseHwids(&decodeOutput(&output.stdout)))
     }
 
-    /// 获取有问题的硬件设备实例路径
-    pub fn getProblemDeviceInstancePath(&self) -> Result<Vec<String>, Box<dyn Error>> {
+    /// 获取设备运行状态
+    pub fn getDeviceStatus(&self) -> Result<Vec<DeviceStatus>, Box<dyn Error>> {
         // pnputil /enum-devices /problem /ids
         // 列出设备的运行状态
         let output = Command::new(&self.devconPath)
             .arg("status")
             .arg("*")
             .output()?;
-        let content = String::from_utf8_lossy(&output.stdout);
-
-        const DELIMITER: &str = "|";
+        Ok(parseStatus(&decodeOutput(&output.stdout)))
+    }
 
-        // 将输出的运行状态转换为一行以便读取
-        let contentLine = content.replace("\r\n    ", DELIMITER);
+    /// 获取有问题的硬件设备实例路径
+    pub fn getProblemDeviceInstancePath(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        Ok(self
+            .getDeviceStatus()?
+            .into_iter()
+            .filter(|status| status.hasProblem())
+            .map(|status| status.DeviceInstancePath)
+            .collect())
+    }
 
-        let mut problemIdList: Vec<String> = Vec::new();
-        // 通过换行符分割遍历
-        for item in contentLine.lines() {
-            if item.contains("problem") {
-                let id = item.split(DELIMITER).next().unwrap_or("");
-                if !id.is_empty() {
-                    problemIdList.push(String::from(id));
-                }
-            }
-        }
-        Ok(problemIdList)
+    /// 获取硬件id信息的运行状态
+    /// # 参数
+    /// 1. 真实硬件id信息
+    pub fn getStatusIdInfo(&self, realIdInfo: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>> {
+        let statusList = self.getDeviceStatus()?;
+        Ok(realIdInfo
+            .into_iter()
+            .map(|mut idInfo| {
+                idInfo.Status = statusList
+                    .iter()
+                    .find(|status| status.DeviceInstancePath.eq_ignore_ascii_case(&idInfo.DeviceInstancePath))
+                    .map(|status| status.State.clone());
+                idInfo
+            })
+            .collect())
     }
 
     /// 获取有问题的硬件id信息
     /// # 参数
     /// 1. 真实硬件id信息
     pub fn getProblemIdInfo(&self, realIdInfo: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>> {
-        // 获取有问题的硬件设备实例路径
-        let problemIdList = &self.getProblemDeviceInstancePath()?;
-
-        let mut problemIdInfoList: Vec<HwID> = Vec::new();
-
-        // 遍历有问题的硬件设备实例路径
-        for problemId in problemIdList {
-            // 遍历获取真实硬件id信息
-            // let problemIdInfoList: Vec<HwID> = realIdInfo.into_iter().filter(|idInfo| problemId.to_lowercase() == idInfo.DeviceInstancePath.to_lowercase()).collect();
-            for realIdItem in realIdInfo.iter() {
-                if problemId.to_lowercase() == realIdItem.DeviceInstancePath.to_lowercase() {
-                    problemIdInfoList.push(realIdItem.clone());
-                    break;
-                }
-            }
-        }
-        Ok(problemIdInfoList)
+        Ok(self.getStatusIdInfo(realIdInfo)?.into_iter().filter(HwID::hasProblem).collect())
     }
 
     /// 加载驱动
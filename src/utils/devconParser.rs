@@ -0,0 +1,251 @@
+use crate::utils::devcon::HwID;
+use chardet::{charset2encoding, detect};
+use encoding::label::encoding_from_whatwg_label;
+use encoding::DecoderTrap;
+use serde::{Deserialize, Serialize};
+
+/// 设备被禁用的问题代码（CM_PROB_DISABLED）
+pub const CM_PROB_DISABLED: u32 = 22;
+/// 未安装驱动的问题代码（CM_PROB_FAILED_INSTALL）
+pub const CM_PROB_FAILED_INSTALL: u32 = 28;
+
+/// 设备状态（devcon status 输出）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeviceStatus {
+    /// 设备实例路径
+    pub(crate) DeviceInstancePath: String,
+    /// 显示名称
+    pub(crate) Name: String,
+    /// 运行状态
+    pub(crate) State: DeviceState,
+}
+
+/// 设备运行状态
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DeviceState {
+    /// 驱动正在运行
+    Running,
+    /// 设备已停止
+    Stopped,
+    /// 设备已禁用
+    Disabled,
+    /// 设备有问题（CM 问题代码）
+    Problem(u32),
+    /// 驱动报告设备有问题（无问题代码）
+    PrivateProblem,
+    /// 无法识别的状态（原始文本）
+    Unknown(String),
+}
+
+impl DeviceState {
+    /// 设备是否有问题
+    pub fn hasProblem(&self) -> bool {
+        matches!(self, DeviceState::Problem(_) | DeviceState::PrivateProblem)
+    }
+
+    /// CM 问题代码
+    pub fn problemCode(&self) -> Option<u32> {
+        match self {
+            DeviceState::Problem(code) => Some(*code),
+            DeviceState::Disabled => Some(CM_PROB_DISABLED),
+            _ => None,
+        }
+    }
+}
+
+impl DeviceStatus {
+    /// 设备是否有问题
+    pub fn hasProblem(&self) -> bool {
+        self.State.hasProblem()
+    }
+
+    /// CM 问题代码
+    pub fn problemCode(&self) -> Option<u32> {
+        self.State.problemCode()
+    }
+}
+
+/// 解码 devcon 输出（非 UTF-8 时按检测到的代码页解码，如本地化版本的 GBK 输出）
+/// # 参数
+/// 1. 输出内容
+pub fn decodeOutput(output: &[u8]) -> String {
+    if let Ok(content) = std::str::from_utf8(output) {
+        return content.to_string();
+    }
+    let result = detect(output);
+    match encoding_from_whatwg_label(charset2encoding(&result.0)) {
+        Some(coder) => coder.decode(output, DecoderTrap::Replace).unwrap_or_else(|_| String::from_utf8_lossy(output).to_string()),
+        None => String::from_utf8_lossy(output).to_string(),
+    }
+}
+
+/// 输出行
+enum Line<'a> {
+    /// 设备实例路径（无缩进）
+    Device(&'a str),
+    /// 设备属性（一级缩进），部分 devcon 版本会将多个属性输出在同一行
+    Attributes(Vec<&'a str>),
+    /// 硬件id、兼容id（二级缩进）
+    Item(&'a str),
+}
+
+/// 拆分输出行
+/// # 参数
+/// 1. 输出内容
+fn splitLines(content: &str) -> Vec<Line<'_>> {
+    let mut lines: Vec<Line> = Vec::new();
+    for line in content.lines() {
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let line = match indent {
+            // 设备实例路径不含空白字符，其余为汇总信息（如 “12 matching device(s) found.”）
+            0 if text.contains(char::is_whitespace) => continue,
+            0 => Line::Device(text),
+            1..=7 => Line::Attributes(text.split("    ").map(str::trim).filter(|item| !item.is_empty()).collect()),
+            _ => Line::Item(text),
+        };
+        lines.push(line);
+    }
+    lines
+}
+
+/// 拆分属性的标签与值（支持全角冒号）
+/// # 参数
+/// 1. 属性
+fn splitAttribute(attribute: &str) -> Option<(&str, &str)> {
+    let (label, value) = attribute.split_once(':').or_else(|| attribute.split_once('：'))?;
+    Some((label.trim(), value.trim()))
+}
+
+/// 解析 devcon hwids 输出
+/// # 参数
+/// 1. 输出内容
+/// # 规则
+/// 1. 无缩进的行为设备实例路径，一级缩进为名称及 id 节标题，二级缩进为 id
+/// 2. 标题为 “Hardware IDs”、“Compatible IDs” 时按标题区分；无法识别的标题（本地化版本）按出现顺序区分，首个为硬件id
+pub fn parseHwids(content: &str) -> Vec<HwID> {
+    #[derive(PartialEq)]
+    enum Section {
+        None,
+        Hardware,
+        Compatible,
+    }
+
+    let mut hwIDList: Vec<HwID> = Vec::new();
+    let mut section = Section::None;
+    let mut hasHardware = false;
+    for line in splitLines(content) {
+        match line {
+            Line::Device(path) => {
+                hwIDList.push(HwID {
+                    DeviceInstancePath: path.to_string(),
+                    Name: String::new(),
+                    HardwareIDs: Vec::new(),
+                    CompatibleIDs: Vec::new(),
+                    Status: None,
+                });
+                section = Section::None;
+                hasHardware = false;
+            }
+            Line::Attributes(attributes) => {
+                let Some(hwID) = hwIDList.last_mut() else { continue };
+                for attribute in attributes {
+                    let Some((label, value)) = splitAttribute(attribute) else {
+                        // 无 id 的提示信息
+                        section = Section::None;
+                        continue;
+                    };
+                    if label.eq_ignore_ascii_case("Name") || !value.is_empty() {
+                        if hwID.Name.is_empty() {
+                            hwID.Name = value.to_string();
+                        }
+                        section = Section::None;
+                        continue;
+                    }
+                    section = if label.eq_ignore_ascii_case("Hardware IDs") {
+                        Section::Hardware
+                    } else if label.eq_ignore_ascii_case("Compatible IDs") || hasHardware {
+                        Section::Compatible
+                    } else {
+                        Section::Hardware
+                    };
+                    hasHardware |= section == Section::Hardware;
+                }
+            }
+            Line::Item(id) => {
+                let Some(hwID) = hwIDList.last_mut() else { continue };
+                match section {
+                    Section::Hardware => hwID.HardwareIDs.push(id.to_string()),
+                    Section::Compatible => hwID.CompatibleIDs.push(id.to_string()),
+                    Section::None => {}
+                }
+            }
+        }
+    }
+    hwIDList
+}
+
+/// 解析 devcon status 输出
+/// # 参数
+/// 1. 输出内容
+/// # 规则
+/// 1. 无缩进的行为设备实例路径，一级缩进为名称及运行状态
+/// 2. 名称为标题为 “Name” 的属性；无法识别的标题（本地化版本）时为设备的首个非问题代码属性
+/// 3. 无法识别的状态（本地化版本）以冒号后的数字作为问题代码
+pub fn parseStatus(content: &str) -> Vec<DeviceStatus> {
+    let mut statusList: Vec<DeviceStatus> = Vec::new();
+    let mut isFirst = false;
+    for line in splitLines(content) {
+        match line {
+            Line::Device(path) => {
+                statusList.push(DeviceStatus {
+                    DeviceInstancePath: path.to_string(),
+                    Name: String::new(),
+                    State: DeviceState::Unknown(String::new()),
+                });
+                isFirst = true;
+            }
+            Line::Attributes(attributes) => {
+                let Some(status) = statusList.last_mut() else { continue };
+                for attribute in attributes {
+                    match splitAttribute(attribute) {
+                        Some((label, value)) if label.eq_ignore_ascii_case("Name") => status.Name = value.to_string(),
+                        Some((_, value)) if isFirst && value.trim_end_matches('.').parse::<u32>().is_err() => status.Name = value.to_string(),
+                        _ => status.State = parseState(attribute),
+                    }
+                    isFirst = false;
+                }
+            }
+            Line::Item(_) => {}
+        }
+    }
+    statusList
+}
+
+/// 解析运行状态
+/// # 参数
+/// 1. 状态文本
+fn parseState(text: &str) -> DeviceState {
+    // 问题代码（如 “The device has the following problem: 28”）
+    let code = splitAttribute(text).and_then(|(_, value)| value.trim_end_matches('.').parse::<u32>().ok());
+    let lower = text.to_lowercase();
+    if lower.contains("problem") {
+        return code.map(DeviceState::Problem).unwrap_or(DeviceState::PrivateProblem);
+    }
+    if lower.contains("disabled") {
+        return DeviceState::Disabled;
+    }
+    if lower.contains("running") {
+        return DeviceState::Running;
+    }
+    if lower.contains("stopped") {
+        return DeviceState::Stopped;
+    }
+    match code {
+        Some(code) => DeviceState::Problem(code),
+        None => DeviceState::Unknown(text.to_string()),
+    }
+}
@@ -0,0 +1,118 @@
+use crate::utils::devcon::{Devcon, HwID};
+use crate::utils::inventory::Inventory;
+use crate::utils::targetOS::TargetOS;
+use crate::utils::{newdevAPI, setupAPI};
+use std::error::Error;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 设备后端
+/// 枚举设备、扫描硬件改动及安装驱动，加载驱动的匹配流程通过该接口访问设备
+pub trait DeviceBackend: Send + Sync {
+    /// 获取全部设备
+    fn devices(&self) -> Result<Vec<HwID>, Box<dyn Error>>;
+
+    /// 获取设备的运行状态
+    /// # 参数
+    /// 1. 设备列表
+    fn queryStatus(&self, hwIDList: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>>;
+
+    /// 扫描检测硬件改动
+    fn rescan(&self) -> bool;
+
+    /// 为硬件id安装INF驱动
+    /// # 参数
+    /// 1. INF路径
+    /// 2. 硬件id
+    fn installInf(&self, infPath: &Path, hwId: &str) -> bool;
+
+    /// 目标系统（为 None 时为当前系统）
+    fn target(&self) -> Option<TargetOS> {
+        None
+    }
+}
+
+/// 当前系统的设备（Devcon 枚举设备，SetupAPI 扫描硬件改动，newdev 安装驱动）
+pub struct LiveBackend {
+    devcon: Devcon,
+}
+
+impl LiveBackend {
+    /// 初始化
+    pub fn new() -> Result<LiveBackend, Box<dyn Error>> {
+        Ok(LiveBackend { devcon: Devcon::new()? })
+    }
+}
+
+impl DeviceBackend for LiveBackend {
+    fn devices(&self) -> Result<Vec<HwID>, Box<dyn Error>> {
+        self.devcon.getRealIdInfo(None)
+    }
+
+    fn queryStatus(&self, hwIDList: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>> {
+        self.devcon.getStatusIdInfo(hwIDList)
+    }
+
+    fn rescan(&self) -> bool {
+        unsafe { setupAPI::rescan() }
+    }
+
+    fn installInf(&self, infPath: &Path, hwId: &str) -> bool {
+        unsafe { newdevAPI::updateDriverForPlugAndPlayDevices(infPath, hwId) }
+    }
+}
+
+/// 打开设备后端
+/// # 参数
+/// 1. 硬件清单路径（为 None 时使用当前系统的设备）
+pub fn openBackend(inventoryPath: Option<&Path>) -> Result<Arc<dyn DeviceBackend>, Box<dyn Error>> {
+    Ok(match inventoryPath {
+        Some(path) => Arc::new(Inventory::load(path)?),
+        None => Arc::new(LiveBackend::new()?),
+    })
+}
+
+/// 设备筛选
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceFilter {
+    /// 所有设备（包括已安装驱动的设备）
+    All,
+    /// 有问题的设备
+    Problem,
+    /// 指定问题代码的设备
+    ProblemCodes(Vec<u32>),
+}
+
+impl DeviceFilter {
+    /// 创建设备筛选
+    /// # 参数
+    /// 1. 是否为精确匹配（匹配所有设备）
+    /// 2. 问题代码列表（为空时匹配所有有问题的设备）
+    pub fn new(isAllDevice: bool, problemCodes: Vec<u32>) -> DeviceFilter {
+        if isAllDevice {
+            DeviceFilter::All
+        } else if problemCodes.is_empty() {
+            DeviceFilter::Problem
+        } else {
+            DeviceFilter::ProblemCodes(problemCodes)
+        }
+    }
+
+    /// 是否匹配该设备
+    /// # 参数
+    /// 1. 设备（需已获取运行状态）
+    pub fn isMatch(&self, hwID: &HwID) -> bool {
+        match self {
+            DeviceFilter::All => true,
+            DeviceFilter::Problem => hwID.hasProblem(),
+            DeviceFilter::ProblemCodes(codes) => hwID.problemCode().is_some_and(|code| codes.contains(&code)),
+        }
+    }
+}
+
+/// 解析问题代码列表（如 “28,1,10”）
+/// # 参数
+/// 1. 问题代码列表
+pub fn parseProblemCodes(text: &str) -> Option<Vec<u32>> {
+    text.split(',').map(|code| code.trim().parse::<u32>().ok()).collect()
+}
@@ -0,0 +1,171 @@
+use crate::i18n::getLocaleText;
+use crate::utils::offlineRegistry::OfflineRegistry;
+use crate::utils::registryHive::{RegistryHive, RegistryKey, RegistryValue};
+use serde::Serialize;
+use std::error::Error;
+use std::path::Path;
+
+/// 扩展INF的驱动类别（Extension）
+const CLASS_EXTENSION: &str = "{e2f84ce7-8efa-411c-aa69-97454ca4cb57}";
+/// 软件组件INF的驱动类别（SoftwareComponent）
+const CLASS_SOFTWARE_COMPONENT: &str = "{5c4c3332-344d-483c-8739-259e934c9cc8}";
+/// 打印机驱动类别（Printer）
+const CLASS_PRINTER: &str = "{4d36e979-e325-11ce-bfc1-08002be10318}";
+
+/// 驱动数据库中的驱动包
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DatabasePackage {
+    /// 驱动包id（FileRepository 下的目录名称，如 rt640x64.inf_amd64_1a2b3c4d5e6f7a8b）
+    pub(crate) PackageId: String,
+    /// 发布名称（如 oem12.inf）
+    pub(crate) PublishedName: String,
+    /// 驱动提供商
+    pub(crate) Provider: String,
+    /// 驱动类别 GUID（小写，如 {4d36e972-e325-11ce-bfc1-08002be10318}，未记录时为空）
+    pub(crate) Class: String,
+    /// 驱动包支持的硬件id
+    pub(crate) HardwareIDs: Vec<String>,
+    /// 使用该驱动的设备实例路径
+    pub(crate) Devices: Vec<String>,
+}
+
+impl DatabasePackage {
+    /// 是否为第三方驱动（oem*.inf）
+    pub fn isOem(&self) -> bool {
+        let name = self.PublishedName.to_lowercase();
+        name.starts_with("oem") && name.ends_with(".inf")
+    }
+}
+
+/// 驱动数据库（Windows 10 及以上版本的 System32\config\DRIVERS）
+/// 结合 SYSTEM 配置单元中设备使用的驱动，得到驱动包与设备的绑定关系
+pub struct DriverDatabase {
+    /// 驱动包列表（仅包括已发布的驱动包）
+    pub(crate) Packages: Vec<DatabasePackage>,
+}
+
+impl DriverDatabase {
+    /// 读取离线系统的驱动数据库
+    /// # 参数
+    /// 1. 离线系统盘（如 `D:\`）
+    pub fn open(systemDrive: &Path) -> Result<DriverDatabase, Box<dyn Error>> {
+        let configPath = systemDrive.join("Windows").join("System32").join("config");
+        let drivers = RegistryHive::open(&configPath.join("DRIVERS"))?;
+        let system = RegistryHive::open(&configPath.join("SYSTEM"))?;
+        DriverDatabase::fromHives(&drivers, &system).ok_or_else(|| {
+            let arg = hash_map!("path".to_string() => configPath.join("DRIVERS").display().to_string().into());
+            getLocaleText("hive-parsing-failed", Some(&arg)).into()
+        })
+    }
+
+    /// 解析驱动数据库
+    /// # 参数
+    /// 1. DRIVERS 配置单元
+    /// 2. SYSTEM 配置单元
+    /// # 规则
+    /// 1. DriverDatabase\DriverInfFiles\<发布名称> 的 Active 值为当前使用的驱动包id
+    /// 2. DriverDatabase\DeviceIds 下的子项路径为硬件id，值名称为支持该硬件id的驱动发布名称
+    /// 3. 设备的 Driver 值指向 Control\Class 下的驱动项，其 InfPath 值为设备使用的驱动发布名称
+    /// 4. DriverDatabase\DriverPackages\<驱动包id> 的 Version 值以驱动类别 GUID 开头
+    /// 5. 扩展INF、软件组件INF不记录在设备的驱动项中，支持的硬件id为设备的硬件id或兼容id时视为被该设备使用
+    pub fn fromHives(drivers: &RegistryHive, system: &RegistryHive) -> Option<DriverDatabase> {
+        let database = drivers.root().openKey("DriverDatabase")?;
+
+        let mut Packages: Vec<DatabasePackage> = Vec::new();
+        for infKey in database.openKey("DriverInfFiles").map(|key| key.subkeys()).unwrap_or_default() {
+            let PublishedName = infKey.name();
+            let PackageId = match infKey.getString("Active").filter(|active| !active.is_empty()) {
+                Some(active) => active,
+                None => infKey.getMultiString("").into_iter().next().unwrap_or_default(),
+            };
+            let packageKey = database.openKey("DriverPackages").and_then(|packages| packages.openKey(&PackageId));
+            let Provider = packageKey.and_then(|package| package.getString("Provider")).unwrap_or_default();
+            let Class = match packageKey.and_then(|package| package.value("Version")) {
+                Some(RegistryValue::Binary(version)) => classGuid(&version).unwrap_or_default(),
+                _ => String::new(),
+            };
+            Packages.push(DatabasePackage { PackageId, PublishedName, Provider, Class, HardwareIDs: Vec::new(), Devices: Vec::new() });
+        }
+
+        // 硬件id => 驱动发布名称
+        if let Some(deviceIds) = database.openKey("DeviceIds") {
+            let mut idList: Vec<(String, String)> = Vec::new();
+            collectDeviceIds(&deviceIds, "", &mut idList);
+            for (hwId, publishedName) in idList {
+                if let Some(package) = Packages.iter_mut().find(|package| package.PublishedName.eq_ignore_ascii_case(&publishedName)) {
+                    package.HardwareIDs.push(hwId);
+                }
+            }
+        }
+
+        // 设备 => 驱动发布名称
+        let classKey = OfflineRegistry::currentControlSet(system)?.openKey(r"Control\Class");
+        for device in OfflineRegistry::enumDevices(system)? {
+            // 扩展INF、软件组件INF
+            let deviceIds: Vec<String> = device.Device.HardwareIDs.iter().chain(&device.Device.CompatibleIDs).map(|id| id.to_uppercase()).collect();
+            for package in Packages.iter_mut().filter(|package| package.Class == CLASS_EXTENSION || package.Class == CLASS_SOFTWARE_COMPONENT) {
+                if package.HardwareIDs.iter().any(|hwId| deviceIds.contains(hwId)) {
+                    package.Devices.push(device.Device.DeviceInstancePath.clone());
+                }
+            }
+
+            let Some(driver) = device.Driver else { continue };
+            let Some(infPath) = classKey.and_then(|classKey| classKey.openKey(&driver)).and_then(|driverKey| driverKey.getString("InfPath")) else { continue };
+            if let Some(package) = Packages.iter_mut().find(|package| package.PublishedName.eq_ignore_ascii_case(&infPath)) {
+                package.Devices.push(device.Device.DeviceInstancePath);
+            }
+        }
+        Some(DriverDatabase { Packages })
+    }
+
+    /// 驱动是否正在被设备使用
+    /// # 参数
+    /// 1. 驱动发布名称
+    pub fn isInUse(&self, publishedName: &str) -> bool {
+        self.Packages.iter().any(|package| package.PublishedName.eq_ignore_ascii_case(publishedName) && !package.Devices.is_empty())
+    }
+
+    /// 未被任何设备使用的第三方驱动
+    /// 打印机驱动（由打印队列使用）及不支持任何硬件id的驱动（如仅安装服务、类筛选器的驱动）无法确定是否被使用，不包括在内
+    pub fn unusedPackages(&self) -> Vec<&DatabasePackage> {
+        self.Packages
+            .iter()
+            .filter(|package| package.isOem() && package.Devices.is_empty() && package.Class != CLASS_PRINTER && !package.HardwareIDs.is_empty())
+            .collect()
+    }
+}
+
+/// 驱动类别 GUID（Version 值的前 16 字节）
+/// # 参数
+/// 1. Version 值
+fn classGuid(version: &[u8]) -> Option<String> {
+    let data = version.get(..16)?;
+    let hex = |bytes: &[u8]| bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+    Some(format!(
+        "{{{:08x}-{:04x}-{:04x}-{}-{}}}",
+        u32::from_le_bytes(data[..4].try_into().ok()?),
+        u16::from_le_bytes(data[4..6].try_into().ok()?),
+        u16::from_le_bytes(data[6..8].try_into().ok()?),
+        hex(&data[8..10]),
+        hex(&data[10..])
+    ))
+}
+
+/// 收集 DeviceIds 下的硬件id（含 `\` 的硬件id保存为多级子项）
+/// # 参数
+/// 1. 注册表项
+/// 2. 上级硬件id
+/// 3. 硬件id与驱动发布名称列表
+fn collectDeviceIds(key: &RegistryKey, prefix: &str, idList: &mut Vec<(String, String)>) {
+    // 硬件id的层级有限，避免损坏的配置单元中的循环引用
+    if prefix.matches('\\').count() >= 8 {
+        return;
+    }
+    for subkey in key.subkeys() {
+        let hwId = if prefix.is_empty() { subkey.name() } else { format!(r"{}\{}", prefix, subkey.name()) };
+        for publishedName in subkey.valueNames().into_iter().filter(|name| !name.is_empty()) {
+            idList.push((hwId.to_uppercase(), publishedName));
+        }
+        collectDeviceIds(&subkey, &hwId, idList);
+    }
+}
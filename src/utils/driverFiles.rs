@@ -0,0 +1,372 @@
+// [INF SourceDisksFiles 节](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/inf-sourcedisksfiles-section)
+// [INF SourceDisksNames 节](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/inf-sourcedisksnames-section)
+// [INF CopyFiles 指令](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/inf-copyfiles-directive)
+
+use crate::utils::archiveBackend::ArchiveEntry;
+use crate::utils::catalog::{Catalog, SignatureStatus};
+use crate::utils::infParser::InfFile;
+use crate::utils::targetOS::TargetOSVersion;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Models 节所需的驱动文件
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SectionFiles {
+    /// Models 节名称（不含修饰）
+    pub(crate) Models: String,
+    /// Models 节的修饰（TargetOSVersion），未修饰的节为空
+    pub(crate) Decoration: String,
+    /// 架构（未指定架构的节为 NTx86）
+    pub(crate) Arch: String,
+    /// 所需的文件（相对于INF所在目录，以 `/` 分隔，编录文件在前）
+    pub(crate) Files: Vec<String>,
+}
+
+/// 获取各个 Models 节所需的文件
+/// # 规则
+/// 1. 该架构的编录文件（[Version] 节中的 CatalogFile.<架构>，未指定时为 CatalogFile）
+/// 2. 由 [Manufacturer] 定位到各个 Models 节的安装节，依次查找 [安装节.架构]、[安装节.NT]、[安装节] 及其 .CoInstallers 节
+/// 3. 安装节中 CopyFiles 引用的文件（`@文件名` 或文件列表节），源文件名未指定时与目标文件名相同
+/// 4. 文件在 [SourceDisksFiles.平台]、[SourceDisksFiles] 中查找所在的磁盘及子目录，磁盘路径来自 [SourceDisksNames.平台]、[SourceDisksNames]
+/// 5. 未在 SourceDisksFiles 中列出的文件由系统提供（如 LayoutFile），不检查
+/// # 参数
+/// 1. 已解析的INF
+pub fn sectionFiles(inf: &InfFile) -> Vec<SectionFiles> {
+    let mut sectionList: Vec<SectionFiles> = Vec::new();
+    for line in inf.section("Manufacturer").map(|section| section.Lines.as_slice()).unwrap_or_default() {
+        let Some(models) = line.Values.first().filter(|models| !models.is_empty()) else { continue };
+
+        let mut decorations: Vec<&str> = vec![""];
+        decorations.extend(line.Values[1..].iter().map(String::as_str).filter(|item| !item.is_empty()));
+
+        for decoration in decorations {
+            let Some(targetOSVersion) = TargetOSVersion::parse(decoration) else { continue };
+            let sectionName = if decoration.is_empty() { models.clone() } else { format!("{}.{}", models, decoration) };
+            let Some(section) = inf.section(&sectionName) else { continue };
+            let Arch = targetOSVersion.Arch.unwrap_or_else(|| "NTx86".to_string());
+            let platform = platformName(&Arch);
+
+            let mut Files: Vec<String> = Vec::new();
+            let mut push = |path: String| {
+                if !path.is_empty() && !Files.iter().any(|item| item.eq_ignore_ascii_case(&path)) {
+                    Files.push(path);
+                }
+            };
+            if let Some(catalog) = catalogFile(inf, &Arch) {
+                push(catalog);
+            }
+
+            // %设备描述% = 安装节, 硬件id[, 兼容id...]
+            for modelLine in section.Lines.iter() {
+                let Some(install) = modelLine.Values.first().filter(|install| !install.is_empty()) else { continue };
+                let Some(ddInstall) = [format!("{}.{}", install, Arch), format!("{}.NT", install), install.clone()].into_iter().find(|name| inf.section(name).is_some()) else { continue };
+
+                for sectionName in [ddInstall.clone(), format!("{}.CoInstallers", ddInstall)] {
+                    for file in copyFiles(inf, &sectionName) {
+                        if let Some(path) = sourcePath(inf, &file, platform) {
+                            push(path);
+                        }
+                    }
+                }
+            }
+            sectionList.push(SectionFiles { Models: models.clone(), Decoration: decoration.to_string(), Arch, Files });
+        }
+    }
+    sectionList
+}
+
+/// 获取驱动所需的文件（各个 Models 节所需文件的并集，见 `sectionFiles`）
+/// # 参数
+/// 1. 已解析的INF
+pub fn requiredFiles(inf: &InfFile) -> Vec<String> {
+    unionFiles(&sectionFiles(inf))
+}
+
+/// 各个 Models 节所需文件的并集（不区分大小写去重）
+/// # 参数
+/// 1. Models 节所需的文件
+pub fn unionFiles(sectionList: &[SectionFiles]) -> Vec<String> {
+    let mut fileList: Vec<String> = Vec::new();
+    for file in sectionList.iter().flat_map(|section| section.Files.iter()) {
+        if !fileList.iter().any(|item| item.eq_ignore_ascii_case(file)) {
+            fileList.push(file.clone());
+        }
+    }
+    fileList
+}
+
+/// 检查驱动目录中缺失或为空的文件
+/// # 参数
+/// 1. 已解析的INF
+/// 2. INF所在目录
+/// # 返回
+/// 不完整的 Models 节及其缺失的文件
+pub fn missingFiles(inf: &InfFile, infDir: &Path) -> Vec<SectionFiles> {
+    incompleteSections(inf, |file| !infDir.join(file).metadata().is_ok_and(|metadata| metadata.is_file() && metadata.len() > 0))
+}
+
+/// 检查压缩包中缺失或为空的文件
+/// # 参数
+/// 1. 已解析的INF
+/// 2. INF在压缩包内所在的目录（以 `/` 分隔）
+/// 3. 压缩包内的文件（见 `archiveFiles`）
+/// # 返回
+/// 不完整的 Models 节及其缺失的文件
+pub fn missingEntries(inf: &InfFile, infDir: &str, files: &HashMap<String, (u64, u64)>) -> Vec<SectionFiles> {
+    incompleteSections(inf, |file| files.get(&archiveKey(infDir, file)).is_none_or(|&(size, _)| size == 0))
+}
+
+/// 不完整的 Models 节（Files 仅保留缺失的文件）
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 文件是否缺失
+fn incompleteSections(inf: &InfFile, isMissing: impl Fn(&str) -> bool) -> Vec<SectionFiles> {
+    sectionFiles(inf)
+        .into_iter()
+        .filter_map(|mut section| {
+            section.Files.retain(|file| isMissing(file));
+            (!section.Files.is_empty()).then_some(section)
+        })
+        .collect()
+}
+
+/// 驱动签名
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DriverSignature {
+    /// 签名状态
+    pub(crate) Status: SignatureStatus,
+    /// 签名者证书主题
+    pub(crate) Signer: String,
+    /// 签名适用的系统版本（编录文件成员的 OSAttr，如 `2:6.3,2:10.0`）
+    pub(crate) OSAttr: String,
+    /// 与编录文件不一致的文件
+    pub(crate) Mismatched: Vec<String>,
+}
+
+/// 校验驱动签名
+/// # 规则
+/// 1. 每个架构使用各自的编录文件（CatalogFile.<架构>，未指定时为 CatalogFile），仅校验该架构的 Models 节所需的文件
+/// 2. 签名状态由编录文件的签名及证书链确定（见 `Catalog::status`）
+/// 3. INF 及该架构所需的驱动文件的哈希须在编录文件中，否则视为不一致
+/// 4. 无法读取的驱动文件（缺失或未解压）不校验
+/// 5. 未指定架构时校验所有架构，签名状态取最差的一个（如任一架构不一致即为不一致），OSAttr 合并各编录文件
+/// # 参数
+/// 1. 已解析的INF
+/// 2. INF文件名
+/// 3. 架构（如 NTamd64），None 为所有架构
+/// 4. 读取驱动文件（参数为相对于INF所在目录的路径）
+pub fn verifySignature(inf: &InfFile, infName: &str, arch: Option<&str>, read: &dyn Fn(&str) -> Option<Vec<u8>>) -> DriverSignature {
+    // 各编录文件及其架构所需的文件（多个架构可共用一个编录文件）
+    let mut catalogList: Vec<(Option<String>, Vec<SectionFiles>)> = Vec::new();
+    for section in sectionFiles(inf).into_iter().filter(|section| arch.is_none_or(|arch| section.Arch.eq_ignore_ascii_case(arch))) {
+        let catalog = catalogFile(inf, &section.Arch);
+        match catalogList.iter_mut().find(|(item, _)| *item == catalog) {
+            Some((_, sectionList)) => sectionList.push(section),
+            None => catalogList.push((catalog, vec![section])),
+        }
+    }
+    if catalogList.is_empty() {
+        catalogList.push((catalogFile(inf, arch.unwrap_or("NTx86")), Vec::new()));
+    }
+
+    let mut result: Option<DriverSignature> = None;
+    let mut osAttrList: Vec<String> = Vec::new();
+    for (catalog, sectionList) in catalogList {
+        let signature = verifyCatalog(catalog, infName, &unionFiles(&sectionList), read);
+        for item in signature.OSAttr.split(',').filter(|item| !item.is_empty()) {
+            if !osAttrList.iter().any(|osAttr| osAttr == item) {
+                osAttrList.push(item.to_string());
+            }
+        }
+        result = Some(match result {
+            Some(mut current) => {
+                if statusOrder(signature.Status) > statusOrder(current.Status) {
+                    current.Status = signature.Status;
+                }
+                if current.Signer.is_empty() {
+                    current.Signer = signature.Signer;
+                }
+                for file in signature.Mismatched {
+                    if !current.Mismatched.contains(&file) {
+                        current.Mismatched.push(file);
+                    }
+                }
+                current
+            }
+            None => signature,
+        });
+    }
+    DriverSignature { OSAttr: osAttrList.join(","), ..result.unwrap_or_default() }
+}
+
+/// 以一个编录文件校验驱动文件
+/// # 参数
+/// 1. 编录文件
+/// 2. INF文件名
+/// 3. 所需的文件（含编录文件）
+/// 4. 读取驱动文件
+fn verifyCatalog(catalogName: Option<String>, infName: &str, fileList: &[String], read: &dyn Fn(&str) -> Option<Vec<u8>>) -> DriverSignature {
+    let Some(catalog) = catalogName.as_deref().and_then(read).and_then(|data| Catalog::parse(&data).ok()) else {
+        return DriverSignature { Status: SignatureStatus::Unsigned, ..DriverSignature::default() };
+    };
+    let Status = catalog.status();
+    if Status == SignatureStatus::Unsigned {
+        return DriverSignature { Status, ..DriverSignature::default() };
+    }
+
+    let catalogName = catalogName.unwrap_or_default();
+    let mut hashList = vec![infName.to_string()];
+    hashList.extend(fileList.iter().filter(|file| !file.eq_ignore_ascii_case(&catalogName)).cloned());
+    let Mismatched: Vec<String> = hashList.into_iter().filter(|file| read(file).is_some_and(|content| catalog.findFile(&content).is_empty())).collect();
+    // 合并各成员的 OSAttr（去重）
+    let mut osAttrList: Vec<String> = Vec::new();
+    for item in catalog.osAttr().iter().flat_map(|value| value.split(',')).map(str::trim).filter(|item| !item.is_empty()) {
+        if !osAttrList.iter().any(|osAttr| osAttr == item) {
+            osAttrList.push(item.to_string());
+        }
+    }
+    DriverSignature {
+        Status: if Mismatched.is_empty() { Status } else { SignatureStatus::Tampered },
+        Signer: catalog.signer().unwrap_or_default().to_string(),
+        OSAttr: osAttrList.join(","),
+        Mismatched,
+    }
+}
+
+/// 签名状态由好到差的顺序（用于合并各架构的签名）
+/// # 参数
+/// 1. 签名状态
+fn statusOrder(status: SignatureStatus) -> u8 {
+    match status {
+        SignatureStatus::Whql => 0,
+        SignatureStatus::Signed => 1,
+        SignatureStatus::Unknown => 2,
+        SignatureStatus::Unverified => 3,
+        SignatureStatus::Unsigned => 4,
+        SignatureStatus::Tampered => 5,
+    }
+}
+
+/// 压缩包内文件的查找键（小写，以 `/` 分隔）
+/// # 参数
+/// 1. INF在压缩包内所在的目录
+/// 2. 相对于INF所在目录的路径
+pub fn archiveKey(infDir: &str, file: &str) -> String {
+    normalizePath(&format!("{}/{}", infDir, file)).to_lowercase()
+}
+
+/// 压缩包内的文件（小写路径 => (文件大小, 修改时间)）
+/// # 参数
+/// 1. 压缩包内的文件列表
+pub fn archiveFiles(entryList: &[ArchiveEntry]) -> HashMap<String, (u64, u64)> {
+    entryList.iter().filter(|entry| !entry.IsDir).map(|entry| (normalizePath(&entry.Path).to_lowercase(), (entry.Size, entry.Modified.unwrap_or_default()))).collect()
+}
+
+/// [Version] 节中的编录文件（CatalogFile 或 CatalogFile.<架构>）
+/// # 参数
+/// 1. 已解析的INF
+pub fn catalogFiles(inf: &InfFile) -> Vec<String> {
+    let mut catalogList: Vec<String> = Vec::new();
+    for line in inf.section("Version").map(|section| section.Lines.as_slice()).unwrap_or_default() {
+        if line.Key.as_deref().and_then(|key| key.get(..11)).is_some_and(|key| key.eq_ignore_ascii_case("CatalogFile")) {
+            let catalog = normalizePath(line.Values.first().map(String::as_str).unwrap_or_default());
+            if !catalog.is_empty() && !catalogList.contains(&catalog) {
+                catalogList.push(catalog);
+            }
+        }
+    }
+    catalogList
+}
+
+/// 架构对应的编录文件（CatalogFile.<架构>，未指定时为 CatalogFile）
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 架构（如 NTamd64）
+fn catalogFile(inf: &InfFile, arch: &str) -> Option<String> {
+    let lines = inf.section("Version").map(|section| section.Lines.as_slice()).unwrap_or_default();
+    [format!("CatalogFile.{}", arch), "CatalogFile".to_string()]
+        .iter()
+        .find_map(|name| lines.iter().find(|line| line.Key.as_deref().is_some_and(|key| key.eq_ignore_ascii_case(name))))
+        .and_then(|line| line.Values.first())
+        .map(|catalog| normalizePath(catalog))
+        .filter(|catalog| !catalog.is_empty())
+}
+
+/// 安装节中 CopyFiles 引用的源文件名
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 安装节名称
+fn copyFiles(inf: &InfFile, sectionName: &str) -> Vec<String> {
+    let mut fileList: Vec<String> = Vec::new();
+    let Some(section) = inf.section(sectionName) else { return fileList };
+
+    for line in section.Lines.iter().filter(|line| line.Key.as_deref().is_some_and(|key| key.eq_ignore_ascii_case("CopyFiles"))) {
+        for value in line.Values.iter().filter(|value| !value.is_empty()) {
+            // CopyFiles = @文件名
+            if let Some(file) = value.strip_prefix('@') {
+                fileList.push(file.trim().to_string());
+                continue;
+            }
+            // 文件列表节：目标文件名[, 源文件名][, 临时文件名][, 标志]
+            for fileLine in inf.section(value).map(|section| section.Lines.as_slice()).unwrap_or_default() {
+                let source = fileLine.Values.get(1).filter(|source| !source.is_empty()).or(fileLine.Values.first());
+                if let Some(source) = source.filter(|source| !source.is_empty()) {
+                    fileList.push(source.clone());
+                }
+            }
+        }
+    }
+    fileList
+}
+
+/// 源文件相对于INF所在目录的路径（未在 SourceDisksFiles 中列出时为 None）
+/// # 参数
+/// 1. 已解析的INF
+/// 2. 源文件名
+/// 3. 平台（如 amd64）
+fn sourcePath(inf: &InfFile, file: &str, platform: &str) -> Option<String> {
+    // 文件名 = 磁盘id[, 子目录][, 大小]
+    let values = [format!("SourceDisksFiles.{}", platform), "SourceDisksFiles".to_string()].iter().find_map(|name| inf.section(name).and_then(|section| section.getValues(file)))?;
+    let diskId = values.first().map(String::as_str).unwrap_or_default();
+    let subDir = values.get(1).map(String::as_str).unwrap_or_default();
+
+    // 磁盘id = 磁盘描述[, 标签或CAB文件][, 未使用][, 路径][, 标志][, 标签文件]
+    let diskPath = [format!("SourceDisksNames.{}", platform), "SourceDisksNames".to_string()]
+        .iter()
+        .find_map(|name| inf.section(name).and_then(|section| section.getValues(diskId)))
+        .and_then(|values| values.get(3))
+        .map(String::as_str)
+        .unwrap_or_default();
+
+    Some(normalizePath(&format!("{}/{}/{}", diskPath, subDir, file)))
+}
+
+/// INF架构对应的 SourceDisksFiles 平台名称
+/// # 参数
+/// 1. 架构（如 NTamd64）
+fn platformName(arch: &str) -> &str {
+    match arch.to_lowercase().as_str() {
+        "ntamd64" => "amd64",
+        "ntarm64" => "arm64",
+        "ntia64" => "ia64",
+        "ntarm" => "arm",
+        _ => "x86",
+    }
+}
+
+/// 规范化相对路径（以 `/` 分隔，去除 `.` 并解析 `..`）
+/// # 参数
+/// 1. 路径
+fn normalizePath(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part.trim() {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part.trim()),
+        }
+    }
+    parts.join("/")
+}
@@ -0,0 +1,84 @@
+// [Windows 如何对驱动进行排名](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/how-windows-ranks-drivers--windows-vista-and-later-)
+// 驱动等级格式：0xSSGGTHHH，数值越小越优先
+// - SS：签名分数
+// - GG：功能分数（FeatureScore）
+// - T：标识符分数（设备id类型 与 INF id类型）
+// - HHH：标识符位置（设备id位置 与 INF id位置）
+
+use crate::command::create_index::InfInfo;
+use crate::utils::catalog::SignatureStatus;
+
+/// 默认功能分数（INF 未指定 FeatureScore 时）
+pub const DEFAULT_FEATURE_SCORE: u8 = 0xFF;
+
+/// 驱动签名等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureTier {
+    /// Microsoft 签名（WHQL、系统内置）
+    Whql,
+    /// 受信任的第三方签名（Authenticode）
+    Signed,
+    /// 未签名
+    Unsigned,
+}
+
+impl SignatureTier {
+    /// 签名分数（SS）
+    pub fn score(self) -> u32 {
+        match self {
+            SignatureTier::Whql => 0x00,
+            SignatureTier::Signed => 0x0D,
+            SignatureTier::Unsigned => 0x80,
+        }
+    }
+
+    /// 根据INF信息获取签名等级
+    /// 优先使用编录文件的校验结果，与编录文件不一致、签名未能验证的驱动视为未签名
+    /// 未校验签名时（旧版索引）按是否引用编录文件（CatalogFile）推断
+    pub fn fromInfInfo(infInfo: &InfInfo) -> SignatureTier {
+        match infInfo.Signature {
+            SignatureStatus::Whql => SignatureTier::Whql,
+            SignatureStatus::Signed => SignatureTier::Signed,
+            SignatureStatus::Unsigned | SignatureStatus::Tampered | SignatureStatus::Unverified => SignatureTier::Unsigned,
+            SignatureStatus::Unknown if infInfo.Catalog.is_empty() => SignatureTier::Unsigned,
+            SignatureStatus::Unknown => SignatureTier::Signed,
+        }
+    }
+}
+
+/// 计算驱动等级
+/// # 参数
+/// 1. 签名等级
+/// 2. 功能分数
+/// 3. 匹配的设备id是否为兼容id
+/// 4. 匹配的设备id在设备id列表中的位置
+/// 5. INF id在 Models 行中的位置（0 为硬件id，其余为兼容id）
+pub fn computeRank(signature: SignatureTier, featureScore: u8, isDeviceCompatibleID: bool, deviceIndex: usize, infPosition: usize) -> u32 {
+    let isInfCompatibleID = infPosition > 0;
+    // 标识符分数
+    // 0：设备硬件id 匹配 INF硬件id
+    // 1：设备硬件id 匹配 INF兼容id
+    // 2：设备兼容id 匹配 INF硬件id
+    // 3：设备兼容id 匹配 INF兼容id
+    let identifierScore = match (isDeviceCompatibleID, isInfCompatibleID) {
+        (false, false) => 0x0,
+        (false, true) => 0x1,
+        (true, false) => 0x2,
+        (true, true) => 0x3,
+    };
+    // 标识符位置：高 4 位为设备id位置，低 8 位为 INF兼容id位置
+    let infIndex = if isInfCompatibleID { infPosition - 1 } else { 0 };
+    let position = (deviceIndex.min(0xF) << 8 | infIndex.min(0xFF)) as u32;
+
+    signature.score() << 24 | (featureScore as u32) << 16 | identifierScore << 12 | position
+}
+
+/// 驱动等级是否由设备的兼容id匹配（标识符分数为 2 或 3）
+pub fn isCompatibleMatch(rank: u32) -> bool {
+    (rank >> 12) & 0xF >= 0x2
+}
+
+/// 以 0xSSGGTHHH 形式显示驱动等级
+pub fn formatRank(rank: u32) -> String {
+    format!("0x{:08X}", rank)
+}
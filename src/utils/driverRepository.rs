@@ -0,0 +1,130 @@
+use crate::utils::infParser::InfFile;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 驱动库中的驱动包
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DriverPackage {
+    /// 发布名称（第三方驱动为 oem*.inf，系统自带驱动为INF文件名）
+    pub(crate) PublishedName: String,
+    /// 驱动包INF文件名
+    pub(crate) Inf: String,
+    /// 是否为系统自带驱动
+    pub(crate) Inbox: bool,
+    /// 驱动类别
+    pub(crate) Class: String,
+    /// 驱动提供商
+    pub(crate) Provider: String,
+    /// 驱动版本
+    pub(crate) Version: String,
+    /// 驱动日期
+    pub(crate) Date: String,
+    /// 驱动包大小（字节）
+    pub(crate) Size: u64,
+    /// 驱动包目录（FileRepository 下的子目录）
+    pub(crate) Folder: PathBuf,
+    /// 使用该驱动的设备实例路径（为 None 时未读取驱动数据库）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) Devices: Option<Vec<String>>,
+}
+
+impl DriverPackage {
+    /// 驱动包INF路径
+    pub fn infPath(&self) -> PathBuf {
+        self.Folder.join(&self.Inf)
+    }
+
+    /// 是否为指定名称的驱动（发布名称或INF文件名，不区分大小写）
+    /// # 参数
+    /// 1. 驱动名称
+    pub fn isNamed(&self, name: &str) -> bool {
+        self.PublishedName.eq_ignore_ascii_case(name) || self.Inf.eq_ignore_ascii_case(name)
+    }
+}
+
+/// 读取系统驱动库（System32\DriverStore\FileRepository）中的驱动包
+/// 直接解析各驱动包的INF，不调用 DriverStore API，可在任意系统上读取离线系统的驱动库
+/// # 参数
+/// 1. 系统盘（如 `D:\`）
+/// # 规则
+/// 1. 第三方驱动安装时会将驱动包INF复制为 Windows\INF\oem*.inf，按文件内容将其对应到驱动包目录
+/// 2. 未对应到 oem*.inf 的驱动包为系统自带驱动
+pub fn scanDriverStore(systemDrive: &Path) -> Result<Vec<DriverPackage>, Box<dyn Error>> {
+    let systemRoot = systemDrive.join("Windows");
+    let repositoryPath = systemRoot.join("System32").join("DriverStore").join("FileRepository");
+
+    // oem*.inf 文件内容 => 发布名称
+    let mut publishedNames: HashMap<Vec<u8>, String> = HashMap::new();
+    for entry in fs::read_dir(systemRoot.join("INF")).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let lower = name.to_lowercase();
+        if lower.starts_with("oem") && lower.ends_with(".inf") {
+            if let Ok(content) = fs::read(entry.path()) {
+                publishedNames.insert(Sha256::digest(&content).to_vec(), name);
+            }
+        }
+    }
+
+    let mut packageList: Vec<DriverPackage> = Vec::new();
+    for entry in fs::read_dir(&repositoryPath)?.flatten() {
+        let folder = entry.path();
+        if !folder.is_dir() {
+            continue;
+        }
+        let Some(infPath) = packageInf(&folder) else { continue };
+        let Ok(inf) = InfFile::open(&infPath) else { continue };
+        let publishedName = fs::read(&infPath).ok().and_then(|content| publishedNames.get(&Sha256::digest(&content).to_vec()).cloned());
+
+        // 驱动版本、日期（DriverVer = 日期, 版本）
+        let driverVer = inf.section("Version").and_then(|section| section.getValues("DriverVer")).unwrap_or_default();
+        let infName = infPath.file_name().unwrap_or_default().to_string_lossy().to_string();
+        packageList.push(DriverPackage {
+            Inbox: publishedName.is_none(),
+            PublishedName: publishedName.unwrap_or_else(|| infName.clone()),
+            Inf: infName,
+            Class: inf.getValue("Version", "Class").unwrap_or_default().to_string(),
+            Provider: inf.getValue("Version", "Provider").unwrap_or_default().to_string(),
+            Date: driverVer.first().cloned().unwrap_or_default(),
+            Version: driverVer.get(1).cloned().unwrap_or_default(),
+            Size: WalkDir::new(&folder).into_iter().flatten().filter_map(|item| item.metadata().ok()).filter(|metadata| metadata.is_file()).map(|metadata| metadata.len()).sum(),
+            Folder: folder,
+            Devices: None,
+        });
+    }
+    // 第三方驱动按 oem 编号排列，系统自带驱动按名称排列
+    packageList.sort_by_key(|package| (package.Inbox, oemNumber(&package.PublishedName), package.PublishedName.to_lowercase()));
+    Ok(packageList)
+}
+
+/// 获取驱动包目录中的INF
+/// 驱动包目录名称为 “INF文件名_架构_哈希”（如 netrtwlane.inf_amd64_5c3b5e0ae8a2b9a5），名称不符时取目录中的首个INF
+/// # 参数
+/// 1. 驱动包目录
+fn packageInf(folder: &Path) -> Option<PathBuf> {
+    let folderName = folder.file_name()?.to_string_lossy().to_lowercase();
+    let mut infList: Vec<PathBuf> = fs::read_dir(folder)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("inf")))
+        .collect();
+    infList.sort();
+    infList
+        .iter()
+        .find(|path| path.file_name().is_some_and(|name| folderName.starts_with(&format!("{}_", name.to_string_lossy().to_lowercase()))))
+        .or(infList.first())
+        .cloned()
+}
+
+/// 获取 oem*.inf 的编号（如 oem12.inf 为 12）
+/// # 参数
+/// 1. 发布名称
+fn oemNumber(publishedName: &str) -> Option<u32> {
+    let name = publishedName.to_lowercase();
+    name.strip_prefix("oem")?.strip_suffix(".inf")?.parse().ok()
+}
@@ -0,0 +1,214 @@
+use crate::command::create_index::PackageInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// 缓存清单文件名
+pub const CACHE_MANIFEST_NAME: &str = "cache.json";
+/// 默认缓存大小上限（MB）
+pub const DEFAULT_CACHE_SIZE: u64 = 4096;
+
+/// 缓存的驱动目录
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CacheEntry {
+    /// 驱动包文件名
+    pub(crate) Package: String,
+    /// 驱动包 SHA-256
+    pub(crate) Hash: String,
+    /// 驱动目录（压缩包内的相对路径）
+    pub(crate) Path: String,
+    /// 解压后的大小（字节）
+    pub(crate) Size: u64,
+    /// 解压后的文件数量
+    pub(crate) FileCount: u64,
+    /// 最近使用时间（Unix 时间戳）
+    pub(crate) LastUsed: u64,
+}
+
+/// 缓存清单
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+struct CacheManifest {
+    /// 缓存的驱动目录
+    Entries: Vec<CacheEntry>,
+}
+
+/// 解压缓存
+/// 保留解压的驱动目录供之后的加载使用，以驱动包 SHA-256 及驱动目录为键
+/// # 规则
+/// 1. 每个驱动包解压到 “缓存目录/驱动包SHA-256”，目录结构与压缩包一致
+/// 2. 解压成功的驱动目录记录其大小及文件数量，再次使用时二者一致才视为有效
+/// 3. 超出大小上限时按最近使用时间删除驱动目录，本次使用的驱动目录不删除
+pub struct ExtractCache {
+    /// 缓存目录
+    root: PathBuf,
+    /// 大小上限（字节）
+    limit: u64,
+    /// 缓存的驱动目录
+    pub(crate) Entries: Vec<CacheEntry>,
+    /// 驱动包路径 => SHA-256（每个驱动包只计算一次）
+    hashes: HashMap<PathBuf, String>,
+    /// 本次使用的驱动目录（驱动包SHA-256, 驱动目录）
+    used: Vec<(String, String)>,
+}
+
+impl ExtractCache {
+    /// 打开解压缓存（不存在时创建）
+    /// # 参数
+    /// 1. 缓存目录
+    /// 2. 大小上限（字节）
+    pub fn open(root: &Path, limit: u64) -> Result<ExtractCache, Box<dyn Error>> {
+        fs::create_dir_all(root)?;
+        // 清单损坏时视为空缓存
+        let manifest: CacheManifest = fs::read_to_string(root.join(CACHE_MANIFEST_NAME))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Ok(ExtractCache { root: root.to_path_buf(), limit, Entries: manifest.Entries, hashes: HashMap::new(), used: Vec::new() })
+    }
+
+    /// 保存缓存清单
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let manifest = CacheManifest { Entries: self.Entries.clone() };
+        fs::write(self.root.join(CACHE_MANIFEST_NAME), serde_json::to_string_pretty(&manifest)?)?;
+        Ok(())
+    }
+
+    /// 获取驱动包的 SHA-256
+    /// # 参数
+    /// 1. 驱动包路径
+    /// 2. 已计算的 SHA-256（校验索引时计算，为 None 时读取驱动包计算）
+    pub fn packageHash(&mut self, packagePath: &Path, known: Option<&str>) -> Result<String, Box<dyn Error>> {
+        if let Some(hash) = self.hashes.get(packagePath) {
+            return Ok(hash.clone());
+        }
+        let hash = match known {
+            Some(hash) => hash.to_lowercase(),
+            None => PackageInfo::fromPath(packagePath)?.Hash,
+        };
+        self.hashes.insert(packagePath.to_path_buf(), hash.clone());
+        Ok(hash)
+    }
+
+    /// 驱动包的解压目录
+    /// # 参数
+    /// 1. 驱动包 SHA-256
+    pub fn packageDir(&self, hash: &str) -> PathBuf {
+        self.root.join(hash)
+    }
+
+    /// 驱动目录是否已缓存且完整（有效时更新最近使用时间）
+    /// # 参数
+    /// 1. 驱动包 SHA-256
+    /// 2. 驱动目录
+    pub fn lookup(&mut self, hash: &str, path: &str) -> bool {
+        let Some(index) = self.Entries.iter().position(|entry| entry.Hash == hash && entryKey(&entry.Path) == entryKey(path)) else { return false };
+        let entry = &self.Entries[index];
+        if dirStat(&self.packageDir(hash).join(&entry.Path)) != Some((entry.Size, entry.FileCount)) {
+            let entry = self.Entries.remove(index);
+            self.removeDir(&entry.Hash, &entry.Path);
+            return false;
+        }
+        self.Entries[index].LastUsed = now();
+        self.used.push((hash.to_string(), entryKey(path)));
+        true
+    }
+
+    /// 记录解压成功的驱动目录，超出大小上限时删除最久未使用的驱动目录
+    /// # 参数
+    /// 1. 驱动包路径
+    /// 2. 驱动包 SHA-256
+    /// 3. 驱动目录列表
+    pub fn record(&mut self, packagePath: &Path, hash: &str, pathList: &[String]) {
+        let Package = packagePath.file_name().unwrap_or_default().to_string_lossy().to_string();
+        for path in pathList {
+            self.Entries.retain(|entry| entry.Hash != hash || entryKey(&entry.Path) != entryKey(path));
+            let Some((Size, FileCount)) = dirStat(&self.packageDir(hash).join(path)) else { continue };
+            self.Entries.push(CacheEntry { Package: Package.clone(), Hash: hash.to_string(), Path: path.clone(), Size, FileCount, LastUsed: now() });
+            self.used.push((hash.to_string(), entryKey(path)));
+        }
+        self.evict();
+    }
+
+    /// 缓存的总大小（字节）
+    pub fn totalSize(&self) -> u64 {
+        self.Entries.iter().map(|entry| entry.Size).sum()
+    }
+
+    /// 超出大小上限时按最近使用时间删除驱动目录
+    /// 与本次使用的驱动目录相互包含的驱动目录不删除
+    fn evict(&mut self) {
+        while self.totalSize() > self.limit {
+            let Some(index) = self
+                .Entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| !self.used.iter().any(|(hash, path)| *hash == entry.Hash && isOverlap(path, &entryKey(&entry.Path))))
+                .min_by_key(|(_, entry)| entry.LastUsed)
+                .map(|(index, _)| index)
+            else {
+                break;
+            };
+            let entry = self.Entries.remove(index);
+            self.removeDir(&entry.Hash, &entry.Path);
+        }
+    }
+
+    /// 清空缓存
+    /// 删除缓存目录下全部以驱动包 SHA-256 命名的解压目录（包括清单中未记录的目录，如解压失败残留的目录）
+    pub fn purge(&mut self) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(&self.root)?.flatten() {
+            if entry.path().is_dir() && isPackageHash(&entry.file_name().to_string_lossy()) {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+        self.Entries.clear();
+        self.used.clear();
+        self.save()
+    }
+
+    /// 删除驱动目录（驱动包不再有缓存的驱动目录时删除其解压目录）
+    /// # 参数
+    /// 1. 驱动包 SHA-256
+    /// 2. 驱动目录
+    fn removeDir(&self, hash: &str, path: &str) {
+        let packageDir = self.packageDir(hash);
+        if self.Entries.iter().any(|entry| entry.Hash == hash) {
+            fs::remove_dir_all(packageDir.join(path)).ok();
+        } else {
+            fs::remove_dir_all(packageDir).ok();
+        }
+    }
+}
+
+/// 驱动目录的比较键（`/` 分隔，不区分大小写）
+fn entryKey(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_lowercase()
+}
+
+/// 是否为驱动包 SHA-256（解压目录名）
+fn isPackageHash(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// 驱动目录是否相互包含（根目录包含全部驱动目录）
+fn isOverlap(first: &str, second: &str) -> bool {
+    first.is_empty() || second.is_empty() || first == second || first.starts_with(&format!("{}/", second)) || second.starts_with(&format!("{}/", first))
+}
+
+/// 目录的大小及文件数量（目录不存在时为 None）
+fn dirStat(path: &Path) -> Option<(u64, u64)> {
+    if !path.is_dir() {
+        return None;
+    }
+    let fileList: Vec<u64> = WalkDir::new(path).into_iter().flatten().filter(|item| item.file_type().is_file()).filter_map(|item| item.metadata().ok()).map(|metadata| metadata.len()).collect();
+    Some((fileList.iter().sum(), fileList.len() as u64))
+}
+
+/// 当前时间（Unix 时间戳）
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
@@ -0,0 +1,107 @@
+use crate::command::create_index::InfInfo;
+use crate::utils::devcon::HwID;
+use std::collections::HashMap;
+
+/// 硬件id倒排索引
+/// 规范化硬件id => INF驱动信息中的硬件id位置，一次建立后供多次匹配使用
+#[derive(Debug, Clone, Default)]
+pub struct HardwareIdIndex {
+    /// INF驱动信息列表
+    entries: Vec<InfInfo>,
+    /// 规范化硬件id => (INF下标, DriverList下标) 列表
+    map: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl HardwareIdIndex {
+    /// 建立索引
+    /// # 参数
+    /// 1. INF驱动信息列表
+    pub fn new(entries: Vec<InfInfo>) -> HardwareIdIndex {
+        let mut map: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (entryIndex, infInfo) in entries.iter().enumerate() {
+            for (driverIndex, driverID) in infInfo.DriverList.iter().enumerate() {
+                map.entry(HardwareIdIndex::normalize(&driverID.Id)).or_default().push((entryIndex, driverIndex));
+            }
+        }
+        HardwareIdIndex { entries, map }
+    }
+
+    /// INF驱动信息列表
+    pub fn entries(&self) -> &[InfInfo] {
+        &self.entries
+    }
+
+    /// 取出INF驱动信息列表
+    pub fn into_entries(self) -> Vec<InfInfo> {
+        self.entries
+    }
+
+    /// 查找硬件id
+    /// # 参数
+    /// 1. 硬件id（不区分大小写）
+    /// # 返回
+    /// (INF下标, DriverList下标) 列表
+    pub fn lookup(&self, id: &str) -> &[(usize, usize)] {
+        self.map.get(&HardwareIdIndex::normalize(id)).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// 规范化硬件id（去除首尾空白并转为大写）
+    pub fn normalize(id: &str) -> String {
+        id.trim().to_uppercase()
+    }
+
+    /// 获取硬件id的简化形式（去除 REV_ 与 SUBSYS_ 部分），由具体到通用排列
+    /// 如 `PCI\VEN_8086&DEV_15B8&SUBSYS_00008086&REV_03` =>
+    /// `PCI\VEN_8086&DEV_15B8&SUBSYS_00008086`、`PCI\VEN_8086&DEV_15B8&REV_03`、`PCI\VEN_8086&DEV_15B8`
+    /// # 参数
+    /// 1. 硬件id
+    pub fn variants(id: &str) -> Vec<String> {
+        let id = HardwareIdIndex::normalize(id);
+        let Some((bus, rest)) = id.split_once('\\') else { return Vec::new() };
+        let parts: Vec<&str> = rest.split('&').collect();
+        let isRev = |part: &&str| part.starts_with("REV_");
+        let isSubsys = |part: &&str| part.starts_with("SUBSYS_");
+
+        let mut result: Vec<String> = Vec::new();
+        for (removeRev, removeSubsys) in [(true, false), (false, true), (true, true)] {
+            let kept: Vec<&str> = parts
+                .iter()
+                .filter(|part| !(removeRev && isRev(part) || removeSubsys && isSubsys(part)))
+                .copied()
+                .collect();
+            if kept.len() == parts.len() || kept.is_empty() {
+                continue;
+            }
+            let variant = format!("{}\\{}", bus, kept.join("&"));
+            if !result.contains(&variant) {
+                result.push(variant);
+            }
+        }
+        result
+    }
+
+    /// 获取设备的全部id
+    /// 设备未报告的 REV_/SUBSYS_ 简化形式追加在硬件id之后（作为优先级较低的硬件id）；
+    /// 已作为兼容id报告的简化形式不追加，仍按兼容id计算等级
+    /// # 参数
+    /// 1. 硬件信息
+    /// # 返回
+    /// (是否为兼容id, 位置, 规范化的设备id) 列表
+    pub fn deviceIDs(hwID: &HwID) -> Vec<(bool, usize, String)> {
+        let mut hardwareIDs: Vec<String> = hwID.HardwareIDs.iter().map(|id| HardwareIdIndex::normalize(id)).collect();
+        let compatibleIDs: Vec<String> = hwID.CompatibleIDs.iter().map(|id| HardwareIdIndex::normalize(id)).collect();
+        let variants: Vec<String> = hardwareIDs.iter().flat_map(|id| HardwareIdIndex::variants(id)).collect();
+        for variant in variants {
+            if !hardwareIDs.contains(&variant) && !compatibleIDs.contains(&variant) {
+                hardwareIDs.push(variant);
+            }
+        }
+
+        hardwareIDs
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| (false, index, id))
+            .chain(compatibleIDs.into_iter().enumerate().map(|(index, id)| (true, index, id)))
+            .collect()
+    }
+}
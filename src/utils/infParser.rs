@@ -0,0 +1,318 @@
+use chardet::{charset2encoding, detect};
+use encoding::label::encoding_from_whatwg_label;
+use encoding::DecoderTrap;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// INF 行
+/// 形如 `键 = 值1, 值2, ...`，无等号的行键为 None
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfLine {
+    /// 键
+    pub(crate) Key: Option<String>,
+    /// 逗号分隔的值（保留空值以维持位置）
+    pub(crate) Values: Vec<String>,
+}
+
+/// INF 节
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfSection {
+    /// 节名称
+    pub(crate) Name: String,
+    /// 节内的行（重复的节会合并到一起）
+    pub(crate) Lines: Vec<InfLine>,
+}
+
+impl InfSection {
+    /// 获取指定键的第一行
+    pub fn getLine(&self, key: &str) -> Option<&InfLine> {
+        self.Lines.iter().find(|line| line.Key.as_deref().is_some_and(|k| k.eq_ignore_ascii_case(key)))
+    }
+
+    /// 获取指定键的全部值
+    pub fn getValues(&self, key: &str) -> Option<&[String]> {
+        self.getLine(key).map(|line| line.Values.as_slice())
+    }
+
+    /// 获取指定键的第一个值
+    pub fn getValue(&self, key: &str) -> Option<&str> {
+        self.getValues(key).and_then(|values| values.first()).map(String::as_str)
+    }
+}
+
+/// INF 文件
+/// [INF 文件的一般语法规则](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/general-syntax-rules-for-inf-files)
+#[derive(Debug, Clone, Default)]
+pub struct InfFile {
+    /// 节列表（按首次出现顺序）
+    sections: Vec<InfSection>,
+    /// 节名称（小写） => 节下标
+    sectionIndex: HashMap<String, usize>,
+}
+
+impl InfFile {
+    /// 解析INF文本
+    /// # 参数
+    /// 1. INF 文件内容（已解码为 UTF-8）
+    pub fn parse(content: &str) -> InfFile {
+        let mut inf = InfFile::default();
+        // 当前节下标，首个节之前的内容会被忽略
+        let mut current: Option<usize> = None;
+
+        for logicalLine in joinLines(content) {
+            let trimmed = logicalLine.trim_start();
+            // 节头
+            if let Some(rest) = trimmed.strip_prefix('[') {
+                let name = rest.split(']').next().unwrap_or(rest).trim();
+                current = Some(inf.getOrInsertSection(name));
+                continue;
+            }
+            let Some(index) = current else { continue };
+            if let Some(line) = parseLine(&logicalLine) {
+                inf.sections[index].Lines.push(line);
+            }
+        }
+
+        inf.substituteStrings();
+        inf
+    }
+
+    /// 读取INF文件（自动识别编码）
+    /// # 参数
+    /// 1. INF 文件路径
+    pub fn open(infPath: &Path) -> Result<InfFile, Box<dyn Error>> {
+        InfFile::fromBytes(&fs::read(infPath)?)
+    }
+
+    /// 解析INF文件内容（自动识别编码）
+    /// # 参数
+    /// 1. INF 文件内容
+    pub fn fromBytes(fileBuf: &[u8]) -> Result<InfFile, Box<dyn Error>> {
+        let result = detect(fileBuf);
+        let coder = encoding_from_whatwg_label(charset2encoding(&result.0)).ok_or("Failed encoding")?;
+        let infContent = coder.decode(fileBuf, DecoderTrap::Ignore)?;
+        Ok(InfFile::parse(&infContent))
+    }
+
+    /// 获取全部节
+    pub fn sections(&self) -> &[InfSection] {
+        &self.sections
+    }
+
+    /// 获取指定节（名称不区分大小写）
+    pub fn section(&self, name: &str) -> Option<&InfSection> {
+        self.sectionIndex.get(&name.to_lowercase()).map(|&index| &self.sections[index])
+    }
+
+    /// 获取指定节中指定键的第一个值
+    pub fn getValue(&self, section: &str, key: &str) -> Option<&str> {
+        self.section(section).and_then(|section| section.getValue(key))
+    }
+
+    /// 获取 [Strings] 节中的字符串
+    /// 优先使用 [Strings]，其次使用本地化的 [Strings.xxxx]
+    pub fn getString(&self, token: &str) -> Option<&str> {
+        if let Some(value) = self.getValue("Strings", token) {
+            return Some(value);
+        }
+        self.sections
+            .iter()
+            .filter(|section| isStringsSection(&section.Name))
+            .find_map(|section| section.getValue(token))
+    }
+
+    /// 获取或创建节，返回节下标
+    fn getOrInsertSection(&mut self, name: &str) -> usize {
+        let key = name.to_lowercase();
+        if let Some(&index) = self.sectionIndex.get(&key) {
+            return index;
+        }
+        self.sections.push(InfSection { Name: name.to_string(), Lines: Vec::new() });
+        self.sectionIndex.insert(key, self.sections.len() - 1);
+        self.sections.len() - 1
+    }
+
+    /// 将 %token% 替换为 [Strings] 中的值（[Strings] 节自身不替换）
+    fn substituteStrings(&mut self) {
+        let mut strings: HashMap<String, String> = HashMap::new();
+        // 本地化字符串优先级低于 [Strings]，先插入以便被覆盖
+        for section in self.sections.iter().filter(|section| isStringsSection(&section.Name)) {
+            let isDefault = section.Name.eq_ignore_ascii_case("Strings");
+            for line in section.Lines.iter() {
+                if let (Some(key), Some(value)) = (&line.Key, line.Values.first()) {
+                    let key = key.to_lowercase();
+                    if isDefault || !strings.contains_key(&key) {
+                        strings.insert(key, value.clone());
+                    }
+                }
+            }
+        }
+
+        for section in self.sections.iter_mut().filter(|section| !isStringsSection(&section.Name)) {
+            for line in section.Lines.iter_mut() {
+                if let Some(key) = &line.Key {
+                    line.Key = Some(substituteTokens(key, &strings));
+                }
+                for value in line.Values.iter_mut() {
+                    *value = substituteTokens(value, &strings);
+                }
+            }
+        }
+    }
+}
+
+/// 是否为字符串节（[Strings] 或 [Strings.xxxx]）
+fn isStringsSection(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == "strings" || lower.starts_with("strings.")
+}
+
+/// 合并续行（行尾的 `\`），同时去除注释
+/// 引号内的 `;` 与 `\` 不做处理
+fn joinLines(content: &str) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    let mut pending = String::new();
+
+    for rawLine in content.lines() {
+        let line = stripComment(rawLine);
+        let trimmed = line.trim_end();
+        if let Some(body) = trimmed.strip_suffix('\\') {
+            // 续行：去掉反斜杠后与下一行拼接
+            pending.push_str(body);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(trimmed);
+        result.push(std::mem::take(&mut pending));
+    }
+    if !pending.trim().is_empty() {
+        result.push(pending);
+    }
+    result
+}
+
+/// 去除引号之外的注释
+fn stripComment(line: &str) -> &str {
+    let mut inQuote = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => inQuote = !inQuote,
+            ';' if !inQuote => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// 解析一行为 键 与 值列表
+/// 引号内的 `=`、`,` 不作为分隔符，`""` 表示一个双引号
+fn parseLine(line: &str) -> Option<InfLine> {
+    let mut key: Option<String> = None;
+    let mut values: Vec<String> = Vec::new();
+    let mut field = InfField::default();
+    let mut inQuote = false;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if inQuote {
+            if c != '"' {
+                field.value.push(c);
+            } else if chars.peek() == Some(&'"') {
+                chars.next();
+                field.value.push('"');
+            } else {
+                inQuote = false;
+                field.quoteEnd = Some(field.value.len());
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                inQuote = true;
+                field.quoteStart.get_or_insert(field.value.len());
+            }
+            '=' if key.is_none() && values.is_empty() => key = Some(field.finish()),
+            ',' => values.push(field.finish()),
+            _ => field.value.push(c),
+        }
+    }
+    // 未闭合的引号视为到行尾结束
+    if inQuote {
+        field.quoteEnd = Some(field.value.len());
+    }
+    let isQuoted = field.quoteStart.is_some();
+    let last = field.finish();
+
+    // 空行
+    if key.is_none() && values.is_empty() && last.is_empty() && !isQuoted {
+        return None;
+    }
+    values.push(last);
+    Some(InfLine { Key: key, Values: values })
+}
+
+/// 正在解析的字段
+#[derive(Default)]
+struct InfField {
+    /// 字段内容（引号已去除）
+    value: String,
+    /// 第一个引号内容的起始位置
+    quoteStart: Option<usize>,
+    /// 最后一个引号内容的结束位置
+    quoteEnd: Option<usize>,
+}
+
+impl InfField {
+    /// 结束当前字段并返回字段值，仅去除引号之外的首尾空白
+    fn finish(&mut self) -> String {
+        let field = std::mem::take(self);
+        match (field.quoteStart, field.quoteEnd) {
+            (Some(start), Some(end)) => format!(
+                "{}{}{}",
+                field.value[..start].trim_start(),
+                &field.value[start..end],
+                field.value[end..].trim_end()
+            ),
+            _ => field.value.trim().to_string(),
+        }
+    }
+}
+
+/// 替换字符串中的 %token%，未定义的 token 保持不变，`%%` 替换为 `%`
+fn substituteTokens(value: &str, strings: &HashMap<String, String>) -> String {
+    if !value.contains('%') {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(0) => {
+                result.push('%');
+                rest = &after[1..];
+            }
+            Some(end) => {
+                let token = &after[..end];
+                match strings.get(&token.to_lowercase()) {
+                    Some(text) => result.push_str(text),
+                    None => {
+                        result.push('%');
+                        result.push_str(token);
+                        result.push('%');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('%');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
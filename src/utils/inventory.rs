@@ -0,0 +1,160 @@
+use crate::i18n::getLocaleText;
+use crate::utils::devcon::{Devcon, HwID};
+use crate::utils::devconParser::DeviceState;
+use crate::utils::deviceBackend::DeviceBackend;
+use crate::utils::targetOS::TargetOS;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// 硬件清单格式版本
+pub const INVENTORY_FORMAT_VERSION: u32 = 1;
+
+/// 硬件清单
+/// 保存设备列表的快照，以便在其他计算机上规划、测试驱动匹配
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Inventory {
+    /// 清单格式版本
+    pub(crate) FormatVersion: u32,
+    /// 创建时间（RFC 3339）
+    pub(crate) CreatedAt: String,
+    /// 计算机名
+    pub(crate) ComputerName: String,
+    /// 目标系统（未记录时以当前系统为准）
+    #[serde(default)]
+    pub(crate) Target: Option<TargetOS>,
+    /// 设备列表
+    pub(crate) Devices: Vec<InventoryDevice>,
+}
+
+/// 硬件清单中的设备
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InventoryDevice {
+    /// 设备实例路径
+    pub(crate) DeviceInstancePath: String,
+    /// 显示名称
+    pub(crate) Name: String,
+    /// 硬件id
+    pub(crate) HardwareIDs: Vec<String>,
+    /// 兼容id
+    pub(crate) CompatibleIDs: Vec<String>,
+    /// 设备是否有问题（如未安装驱动）
+    pub(crate) Problem: bool,
+    /// 运行状态
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) Status: Option<DeviceState>,
+}
+
+impl Inventory {
+    /// 由设备列表创建硬件清单
+    /// # 参数
+    /// 1. 硬件信息列表（含运行状态）
+    /// 2. 目标系统
+    pub fn new(hwIDList: &[HwID], target: Option<TargetOS>) -> Inventory {
+        let Devices = hwIDList
+            .iter()
+            .map(|hwID| InventoryDevice {
+                DeviceInstancePath: hwID.DeviceInstancePath.clone(),
+                Name: hwID.Name.clone(),
+                HardwareIDs: hwID.HardwareIDs.clone(),
+                CompatibleIDs: hwID.CompatibleIDs.clone(),
+                Problem: hwID.hasProblem(),
+                Status: hwID.Status.clone(),
+            })
+            .collect();
+        Inventory {
+            FormatVersion: INVENTORY_FORMAT_VERSION,
+            CreatedAt: Local::now().to_rfc3339(),
+            ComputerName: env::var("COMPUTERNAME").unwrap_or_default(),
+            Target: target,
+            Devices,
+        }
+    }
+
+    /// 采集当前系统的设备
+    /// # 参数
+    /// 1. Devcon
+    pub fn capture(devcon: &Devcon) -> Result<Inventory, Box<dyn Error>> {
+        let hwIDList = devcon.getRealIdInfo(None)?;
+        if hwIDList.is_empty() {
+            return Err(getLocaleText("no-device", None).into());
+        }
+        let hwIDList = devcon.getStatusIdInfo(hwIDList)?;
+        Ok(Inventory::new(&hwIDList, Some(TargetOS::current())))
+    }
+
+    /// 保存硬件清单
+    /// # 参数
+    /// 1. 保存路径
+    pub fn save(&self, savePath: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(savePath, serde_json::to_string_pretty(&self)?)?;
+        Ok(())
+    }
+
+    /// 读取硬件清单
+    /// # 参数
+    /// 1. 硬件清单路径
+    pub fn load(inventoryPath: &Path) -> Result<Inventory, Box<dyn Error>> {
+        let content = fs::read_to_string(inventoryPath)?;
+        let value: serde_json::Value = serde_json::from_str(&content).map_err(|_| getLocaleText("inventory-parsing-failed", None))?;
+
+        let formatVersion = value.get("FormatVersion").and_then(serde_json::Value::as_u64).unwrap_or(0);
+        if formatVersion == 0 || formatVersion > INVENTORY_FORMAT_VERSION as u64 {
+            let arg = hash_map!("version".to_string() => formatVersion.to_string().into());
+            return Err(getLocaleText("inventory-version-unsupported", Some(&arg)).into());
+        }
+        let inventory: Inventory = serde_json::from_value(value).map_err(|_| getLocaleText("inventory-parsing-failed", None))?;
+        Ok(inventory)
+    }
+
+    /// 获取设备列表
+    /// # 参数
+    /// 1. 是否为精确匹配（为 false 时仅获取有问题的设备）
+    pub fn deviceList(&self, isAllDevice: bool) -> Vec<HwID> {
+        self.Devices
+            .iter()
+            .filter(|device| isAllDevice || device.Problem)
+            .map(|device| HwID {
+                DeviceInstancePath: device.DeviceInstancePath.clone(),
+                Name: device.Name.clone(),
+                HardwareIDs: device.HardwareIDs.clone(),
+                CompatibleIDs: device.CompatibleIDs.clone(),
+                // 未记录运行状态的清单仅能区分设备是否有问题
+                Status: device.Status.clone().or(if device.Problem { Some(DeviceState::PrivateProblem) } else { None }),
+            })
+            .collect()
+    }
+}
+
+/// 硬件清单中的设备（不能扫描硬件改动、安装驱动）
+impl DeviceBackend for Inventory {
+    fn devices(&self) -> Result<Vec<HwID>, Box<dyn Error>> {
+        Ok(self.deviceList(true))
+    }
+
+    fn queryStatus(&self, hwIDList: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>> {
+        let deviceList = self.deviceList(true);
+        Ok(hwIDList
+            .into_iter()
+            .map(|mut hwID| {
+                hwID.Status = deviceList.iter().find(|device| **device == hwID).and_then(|device| device.Status.clone());
+                hwID
+            })
+            .collect())
+    }
+
+    fn rescan(&self) -> bool {
+        true
+    }
+
+    fn installInf(&self, _infPath: &Path, _hwId: &str) -> bool {
+        false
+    }
+
+    fn target(&self) -> Option<TargetOS> {
+        self.Target.clone()
+    }
+}
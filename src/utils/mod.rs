@@ -1,7 +1,28 @@
 pub mod console;
 pub mod devcon;
+pub mod devconParser;
 pub mod newdevAPI;
 pub mod setupAPI;
 pub mod sevenZIP;
 pub mod util;
 pub mod drvstoreAPI;
+pub mod infParser;
+pub mod targetOS;
+pub mod driverRank;
+pub mod binaryIndex;
+pub mod hardwareIdIndex;
+pub mod inventory;
+pub mod deviceBackend;
+pub mod registryHive;
+pub mod offlineRegistry;
+pub mod driverRepository;
+pub mod driverDatabase;
+pub mod archiveBackend;
+pub mod nativeArchive;
+pub mod cabinet;
+pub mod sevenZipParser;
+pub mod extractCache;
+pub mod driverFiles;
+pub mod catalog;
+pub mod signaturePolicy;
+pub mod cryptAPI;
@@ -0,0 +1,308 @@
+use crate::i18n::getLocaleText;
+use crate::utils::archiveBackend::{dosTimestamp, entryOutPath, matchPattern, ArchiveBackend, ArchiveEntry, ArchiveFormat, FileVisitor, FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_DIRECTORY};
+use crate::utils::cabinet::Cabinet;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use lzma_rust2::XzReader;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+use walkdir::{DirEntry, WalkDir};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// 内置压缩包实现（ZIP、TAR、TAR.GZ、TAR.XZ、CAB）
+/// 不依赖 7-zip，可在任意平台上运行；ZIP 仅支持 ZipCrypto 加密，CAB 仅支持未压缩及 MSZIP 压缩
+pub struct NativeArchive;
+
+impl NativeArchive {
+    /// 初始化
+    pub fn new() -> NativeArchive {
+        NativeArchive
+    }
+
+    /// 是否支持该压缩包
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包格式
+    /// # 规则
+    /// 1. ZIP 中的文件须为未压缩或 Deflate 压缩，加密的文件须为 ZipCrypto 加密（不支持 AES）
+    /// 2. CAB 中的文件夹须为未压缩或 MSZIP 压缩
+    /// 3. 压缩包无法解析时视为不支持，交由 7-zip 处理
+    pub fn supports(archivePath: &Path, format: ArchiveFormat) -> bool {
+        match format {
+            ArchiveFormat::Zip => {
+                let Some(mut archive) = File::open(archivePath).ok().and_then(|file| ZipArchive::new(BufReader::new(file)).ok()) else {
+                    return false;
+                };
+                (0..archive.len()).all(|index| {
+                    archive.by_index_raw(index).is_ok_and(|file| {
+                        matches!(file.compression(), CompressionMethod::Stored | CompressionMethod::Deflated)
+                            && !(file.encrypted() && file.extra_data().is_some_and(hasAesField))
+                    })
+                })
+            }
+            ArchiveFormat::Cab => Cabinet::open(archivePath).is_ok_and(|cabinet| cabinet.isSupported()),
+            ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarXz => true,
+            _ => false,
+        }
+    }
+
+    /// 遍历压缩包内的文件
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 是否读取该文件的内容
+    /// 4. 文件内容处理函数
+    fn walk(
+        &self,
+        archivePath: &Path,
+        password: Option<&str>,
+        mut select: impl FnMut(&ArchiveEntry) -> bool,
+        mut read: impl FnMut(&ArchiveEntry, &mut dyn Read) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let parsingFailed = || {
+            let arg = hash_map!("path".to_string() => archivePath.display().to_string().into());
+            getLocaleText("archive-parsing-failed", Some(&arg))
+        };
+
+        let format = ArchiveFormat::detect(archivePath);
+        match format {
+            Some(ArchiveFormat::Zip) => {
+                let mut archive = ZipArchive::new(BufReader::new(File::open(archivePath)?))?;
+                for index in 0..archive.len() {
+                    let entry = {
+                        let file = archive.by_index_raw(index)?;
+                        ArchiveEntry {
+                            Path: file.name().replace('\\', "/").trim_end_matches('/').to_string(),
+                            Size: file.size(),
+                            IsDir: file.is_dir(),
+                            Modified: file.last_modified().and_then(|time| dosTimestamp(time.datepart(), time.timepart())),
+                            Crc: Some(file.crc32()),
+                            Attributes: if file.is_dir() { FILE_ATTRIBUTE_DIRECTORY } else { FILE_ATTRIBUTE_ARCHIVE },
+                            Encrypted: file.encrypted(),
+                            Block: None,
+                        }
+                    };
+                    if select(&entry) {
+                        let mut file = match password {
+                            Some(password) => archive.by_index_decrypt(index, password.as_bytes())?,
+                            None => archive.by_index(index)?,
+                        };
+                        read(&entry, &mut file)?;
+                    }
+                }
+            }
+            Some(ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarXz) => {
+                // 边解压边读取，不缓存整个 tar
+                let file = BufReader::new(File::open(archivePath)?);
+                let reader: Box<dyn Read> = match format {
+                    Some(ArchiveFormat::TarGz) => Box::new(GzDecoder::new(file)),
+                    Some(ArchiveFormat::TarXz) => Box::new(XzReader::new(file, true)),
+                    _ => Box::new(file),
+                };
+                let mut archive = tar::Archive::new(reader);
+                for file in archive.entries()? {
+                    let mut file = file?;
+                    // 仅处理文件、目录（忽略链接等）
+                    let entryType = file.header().entry_type();
+                    if !entryType.is_file() && !entryType.is_dir() {
+                        continue;
+                    }
+                    let path = file.path()?.to_string_lossy().replace('\\', "/");
+                    let entry = ArchiveEntry {
+                        Path: path.trim_end_matches('/').to_string(),
+                        Size: file.size(),
+                        IsDir: entryType.is_dir(),
+                        Modified: file.header().mtime().ok(),
+                        Attributes: if entryType.is_dir() { FILE_ATTRIBUTE_DIRECTORY } else { FILE_ATTRIBUTE_ARCHIVE },
+                        ..Default::default()
+                    };
+                    if select(&entry) {
+                        read(&entry, &mut file)?;
+                    }
+                }
+            }
+            Some(ArchiveFormat::Cab) => {
+                let mut cabinet = Cabinet::open(archivePath)?;
+                // 按文件夹解压，每个文件夹仅顺序解压一次
+                let mut folderList: BTreeMap<u16, Vec<(ArchiveEntry, u64)>> = BTreeMap::new();
+                for file in &cabinet.Files {
+                    let entry = ArchiveEntry {
+                        Path: file.Name.replace('\\', "/"),
+                        Size: file.Size as u64,
+                        Modified: dosTimestamp(file.Date, file.Time),
+                        Attributes: file.Attributes as u32,
+                        // 同一文件夹中的文件连续压缩，相当于固实块
+                        Block: Some(file.Folder as u64),
+                        ..Default::default()
+                    };
+                    if select(&entry) {
+                        folderList.entry(file.Folder).or_default().push((entry, file.Offset as u64));
+                    }
+                }
+                for (folder, mut entryList) in folderList {
+                    entryList.sort_by_key(|(_, offset)| *offset);
+                    let mut content = cabinet.readFolder(folder as usize).ok_or_else(parsingFailed)?;
+                    let mut position = 0;
+                    for (entry, offset) in entryList {
+                        // 跳过文件之间的数据（文件重叠时重新解压该文件夹）
+                        if offset < position {
+                            drop(content);
+                            content = cabinet.readFolder(folder as usize).ok_or_else(parsingFailed)?;
+                            position = 0;
+                        }
+                        if io::copy(&mut (&mut content).take(offset - position), &mut io::sink())? != offset - position {
+                            return Err(parsingFailed().into());
+                        }
+                        let mut data = (&mut content).take(entry.Size);
+                        read(&entry, &mut data)?;
+                        // 处理函数可能未读完文件内容；数据块不足时文件不完整
+                        io::copy(&mut data, &mut io::sink())?;
+                        if data.limit() > 0 {
+                            return Err(parsingFailed().into());
+                        }
+                        position = offset + entry.Size;
+                    }
+                }
+            }
+            _ => {
+                let arg = hash_map!("path".to_string() => archivePath.display().to_string().into());
+                return Err(getLocaleText("archive-format-unsupported", Some(&arg)).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// 解压匹配的文件，返回匹配的文件数量
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 是否匹配该文件
+    /// 4. 输出路径
+    fn extract(&self, archivePath: &Path, password: Option<&str>, isMatch: impl Fn(&str) -> bool, outPath: &Path) -> Result<usize, Box<dyn Error>> {
+        let mut count = 0;
+        self.walk(
+            archivePath,
+            password,
+            |entry| {
+                let Some(filePath) = entryOutPath(outPath, &entry.Path).filter(|_| isMatch(&entry.Path)) else { return false };
+                count += 1;
+                // 目录直接创建（创建失败时在写入其中的文件时报错），已存在的文件不覆盖
+                if entry.IsDir {
+                    fs::create_dir_all(&filePath).ok();
+                    return false;
+                }
+                !filePath.exists()
+            },
+            |entry, content| {
+                let filePath = entryOutPath(outPath, &entry.Path).unwrap_or_default();
+                if let Some(parent) = filePath.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                io::copy(content, &mut File::create(&filePath)?)?;
+                Ok(())
+            },
+        )?;
+        Ok(count)
+    }
+}
+
+impl ArchiveBackend for NativeArchive {
+    fn list(&self, archivePath: &Path, password: Option<&str>) -> Result<Vec<ArchiveEntry>, Box<dyn Error>> {
+        let mut entryList = Vec::new();
+        self.walk(
+            archivePath,
+            password,
+            |entry| {
+                entryList.push(entry.clone());
+                false
+            },
+            |_, _| Ok(()),
+        )?;
+        Ok(entryList)
+    }
+
+    fn extractPattern(&self, archivePath: &Path, password: Option<&str>, pattern: &str, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        Ok(self.extract(archivePath, password, |path| matchPattern(path, pattern), outPath)? > 0)
+    }
+
+    fn extractPatterns(&self, archivePath: &Path, password: Option<&str>, patterns: &[String], outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        Ok(self.extract(archivePath, password, |path| patterns.iter().any(|pattern| matchPattern(path, pattern)), outPath)? > 0)
+    }
+
+    fn extractEntry(&self, archivePath: &Path, password: Option<&str>, entryPath: &str, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        let entryPath = entryPath.replace('\\', "/");
+        Ok(self.extract(archivePath, password, |path| path.eq_ignore_ascii_case(&entryPath), outPath)? > 0)
+    }
+
+    fn visitFiles(&self, archivePath: &Path, password: Option<&str>, pattern: &str, visit: &mut FileVisitor) -> Result<(), Box<dyn Error>> {
+        self.walk(
+            archivePath,
+            password,
+            |entry| !entry.IsDir && matchPattern(&entry.Path, pattern),
+            |entry, content| {
+                // 文件大小来自压缩包头，不据此预分配，由读取的内容决定
+                let mut buffer = Vec::new();
+                content.read_to_end(&mut buffer)?;
+                visit(entry.clone(), buffer)
+            },
+        )
+    }
+
+    /// 按扩展名创建 ZIP（.zip）、TAR（.tar）或 TAR.GZ（.tar.gz、.tgz）压缩包
+    fn create(&self, inputPath: &Path, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        let name = outPath.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        let fileList: Vec<DirEntry> = WalkDir::new(inputPath).min_depth(1).sort_by_file_name().into_iter().flatten().filter(|item| item.file_type().is_file()).collect();
+
+        if name.ends_with(".zip") {
+            let mut writer = ZipWriter::new(File::create(outPath)?);
+            let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+            for item in &fileList {
+                writer.start_file(item.path().strip_prefix(inputPath)?.to_string_lossy().replace('\\', "/"), options)?;
+                io::copy(&mut File::open(item.path())?, &mut writer)?;
+            }
+            writer.finish()?;
+        } else if name.ends_with(".tar") {
+            tarFiles(File::create(outPath)?, inputPath, &fileList)?;
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            tarFiles(GzEncoder::new(File::create(outPath)?, Compression::best()), inputPath, &fileList)?.finish()?;
+        } else {
+            let arg = hash_map!("path".to_string() => outPath.display().to_string().into());
+            return Err(getLocaleText("archive-format-unsupported", Some(&arg)).into());
+        }
+        Ok(true)
+    }
+}
+
+/// 将文件写入 tar
+/// # 参数
+/// 1. 输出流
+/// 2. 待压缩的目录
+/// 3. 文件列表
+fn tarFiles<W: Write>(writer: W, inputPath: &Path, fileList: &[DirEntry]) -> Result<W, Box<dyn Error>> {
+    let mut builder = tar::Builder::new(writer);
+    for item in fileList {
+        builder.append_path_with_name(item.path(), item.path().strip_prefix(inputPath)?)?;
+    }
+    Ok(builder.into_inner()?)
+}
+
+/// ZIP 扩展字段中是否包含 AES 加密信息（标识 0x9901）
+/// # 参数
+/// 1. 扩展字段
+fn hasAesField(extra: &[u8]) -> bool {
+    let mut position = 0;
+    while position + 4 <= extra.len() {
+        let kind = u16::from_le_bytes([extra[position], extra[position + 1]]);
+        let size = u16::from_le_bytes([extra[position + 2], extra[position + 3]]) as usize;
+        if kind == 0x9901 {
+            return true;
+        }
+        position += 4 + size;
+    }
+    false
+}
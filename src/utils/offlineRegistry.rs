@@ -0,0 +1,148 @@
+use crate::i18n::getLocaleText;
+use crate::utils::devcon::HwID;
+use crate::utils::devconParser::{DeviceState, CM_PROB_FAILED_INSTALL};
+use crate::utils::deviceBackend::DeviceBackend;
+use crate::utils::registryHive::{RegistryHive, RegistryKey};
+use crate::utils::targetOS::TargetOS;
+use std::error::Error;
+use std::path::Path;
+
+/// 设备已禁用（CONFIGFLAG_DISABLED）
+pub const CONFIGFLAG_DISABLED: u32 = 0x0000_0001;
+/// 设备需重新安装驱动（CONFIGFLAG_REINSTALL）
+pub const CONFIGFLAG_REINSTALL: u32 = 0x0000_0020;
+/// 驱动安装失败（CONFIGFLAG_FAILEDINSTALL）
+pub const CONFIGFLAG_FAILEDINSTALL: u32 = 0x0000_0040;
+
+/// 离线系统中的设备
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfflineDevice {
+    /// 硬件信息
+    pub(crate) Device: HwID,
+    /// 配置标志（ConfigFlags）
+    pub(crate) ConfigFlags: u32,
+    /// 已安装驱动的注册表项（如 {4d36e972-e325-11ce-bfc1-08002be10318}\0001）
+    pub(crate) Driver: Option<String>,
+}
+
+impl OfflineDevice {
+    /// 运行状态
+    /// 离线系统中的设备均未运行，已安装驱动的设备视为已停止
+    pub fn state(&self) -> DeviceState {
+        if self.ConfigFlags & CONFIGFLAG_DISABLED != 0 {
+            DeviceState::Disabled
+        } else if self.Driver.is_none() || self.ConfigFlags & (CONFIGFLAG_REINSTALL | CONFIGFLAG_FAILEDINSTALL) != 0 {
+            DeviceState::Problem(CM_PROB_FAILED_INSTALL)
+        } else {
+            DeviceState::Stopped
+        }
+    }
+}
+
+/// 离线系统的设备
+/// 读取离线系统 SYSTEM 配置单元中当前控制集的 Enum 项，包括该系统曾识别过的全部设备（不能扫描硬件改动、安装驱动）
+pub struct OfflineRegistry {
+    /// 设备列表
+    pub(crate) Devices: Vec<OfflineDevice>,
+    /// 目标系统
+    pub(crate) Target: Option<TargetOS>,
+}
+
+impl OfflineRegistry {
+    /// 读取离线系统的设备
+    /// # 参数
+    /// 1. 离线系统盘（如 `D:\`）
+    pub fn open(systemDrive: &Path) -> Result<OfflineRegistry, Box<dyn Error>> {
+        let hivePath = systemDrive.join("Windows").join("System32").join("config").join("SYSTEM");
+        let hive = RegistryHive::open(&hivePath)?;
+        let Devices = OfflineRegistry::enumDevices(&hive).ok_or_else(|| {
+            let arg = hash_map!("path".to_string() => hivePath.display().to_string().into());
+            getLocaleText("hive-parsing-failed", Some(&arg))
+        })?;
+        Ok(OfflineRegistry { Devices, Target: TargetOS::offline(systemDrive).ok() })
+    }
+
+    /// 枚举配置单元中的设备
+    /// # 参数
+    /// 1. SYSTEM 配置单元
+    /// # 规则
+    /// 1. 读取当前控制集的 Enum 项
+    /// 2. 设备实例路径为 Enum 下的三级子项（枚举器\设备id\实例id）
+    pub fn enumDevices(hive: &RegistryHive) -> Option<Vec<OfflineDevice>> {
+        let enumKey = OfflineRegistry::currentControlSet(hive)?.openKey("Enum")?;
+
+        let mut deviceList: Vec<OfflineDevice> = Vec::new();
+        for enumerator in enumKey.subkeys() {
+            for device in enumerator.subkeys() {
+                for instance in device.subkeys() {
+                    let path = format!(r"{}\{}\{}", enumerator.name(), device.name(), instance.name());
+                    deviceList.push(OfflineRegistry::readDevice(&instance, path));
+                }
+            }
+        }
+        Some(deviceList)
+    }
+
+    /// 获取当前控制集（由 Select\Current 指定，未指定时为 ControlSet001）
+    /// # 参数
+    /// 1. SYSTEM 配置单元
+    pub fn currentControlSet(hive: &RegistryHive) -> Option<RegistryKey<'_>> {
+        let root = hive.root();
+        let current = root.openKey("Select").and_then(|select| select.getDword("Current")).unwrap_or(1);
+        root.openKey(&format!("ControlSet{:03}", current))
+    }
+
+    /// 读取设备实例项
+    /// # 参数
+    /// 1. 设备实例项
+    /// 2. 设备实例路径
+    fn readDevice(instance: &RegistryKey, path: String) -> OfflineDevice {
+        // 设备描述可能为间接字符串（如 “@net1ic64.inf,%e15b8desc%;Intel(R) Ethernet Connection”），取分号后的文本
+        let name = instance
+            .getString("FriendlyName")
+            .or_else(|| instance.getString("DeviceDesc"))
+            .map(|name| name.rsplit(';').next().unwrap_or_default().to_string())
+            .unwrap_or_default();
+        let mut device = OfflineDevice {
+            Device: HwID {
+                DeviceInstancePath: path,
+                Name: name,
+                HardwareIDs: instance.getMultiString("HardwareID"),
+                CompatibleIDs: instance.getMultiString("CompatibleIDs"),
+                Status: None,
+            },
+            ConfigFlags: instance.getDword("ConfigFlags").unwrap_or(0),
+            Driver: instance.getString("Driver").filter(|driver| !driver.is_empty()),
+        };
+        device.Device.Status = Some(device.state());
+        device
+    }
+}
+
+impl DeviceBackend for OfflineRegistry {
+    fn devices(&self) -> Result<Vec<HwID>, Box<dyn Error>> {
+        Ok(self.Devices.iter().map(|device| device.Device.clone()).collect())
+    }
+
+    fn queryStatus(&self, hwIDList: Vec<HwID>) -> Result<Vec<HwID>, Box<dyn Error>> {
+        Ok(hwIDList
+            .into_iter()
+            .map(|mut hwID| {
+                hwID.Status = self.Devices.iter().find(|device| device.Device == hwID).map(OfflineDevice::state);
+                hwID
+            })
+            .collect())
+    }
+
+    fn rescan(&self) -> bool {
+        true
+    }
+
+    fn installInf(&self, _infPath: &Path, _hwId: &str) -> bool {
+        false
+    }
+
+    fn target(&self) -> Option<TargetOS> {
+        self.Target.clone()
+    }
+}
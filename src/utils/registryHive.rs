@@ -0,0 +1,313 @@
+use crate::i18n::getLocaleText;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// 字符串（REG_SZ）
+pub const REG_SZ: u32 = 1;
+/// 可扩展字符串（REG_EXPAND_SZ）
+pub const REG_EXPAND_SZ: u32 = 2;
+/// 32 位整数（REG_DWORD）
+pub const REG_DWORD: u32 = 4;
+/// 多字符串（REG_MULTI_SZ）
+pub const REG_MULTI_SZ: u32 = 7;
+/// 64 位整数（REG_QWORD）
+pub const REG_QWORD: u32 = 11;
+
+/// 基本块大小（hive bin 从该偏移开始，单元偏移均相对于此）
+const BASE_BLOCK_SIZE: usize = 0x1000;
+/// 无效的单元偏移
+const INVALID_OFFSET: u32 = 0xFFFF_FFFF;
+/// 项名称为 ASCII 编码（KEY_COMP_NAME）
+const KEY_COMP_NAME: u16 = 0x0020;
+/// 值名称为 ASCII 编码（VALUE_COMP_NAME）
+const VALUE_COMP_NAME: u16 = 0x0001;
+/// 数据保存在数据偏移字段中（不超过 4 字节）
+const DATA_IS_RESIDENT: u32 = 0x8000_0000;
+/// 大数据（db）的分段大小
+const BIG_DATA_SEGMENT_SIZE: usize = 16344;
+
+/// 注册表配置单元（regf 格式）
+/// 只读解析，不应用事务日志（SYSTEM.LOG1、SYSTEM.LOG2）中未写入的改动
+pub struct RegistryHive {
+    data: Vec<u8>,
+    rootOffset: u32,
+}
+
+/// 注册表项（nk 单元）
+#[derive(Clone, Copy)]
+pub struct RegistryKey<'a> {
+    hive: &'a RegistryHive,
+    offset: u32,
+}
+
+/// 注册表值
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryValue {
+    /// REG_SZ、REG_EXPAND_SZ
+    String(String),
+    /// REG_MULTI_SZ
+    MultiString(Vec<String>),
+    /// REG_DWORD
+    Dword(u32),
+    /// REG_QWORD
+    Qword(u64),
+    /// 其他类型的原始数据
+    Binary(Vec<u8>),
+}
+
+impl RegistryHive {
+    /// 读取配置单元文件
+    /// # 参数
+    /// 1. 配置单元路径（如 `D:\Windows\System32\config\SYSTEM`）
+    pub fn open(hivePath: &Path) -> Result<RegistryHive, Box<dyn Error>> {
+        let data = fs::read(hivePath)?;
+        RegistryHive::fromBytes(data).map_err(|_| {
+            let arg = hash_map!("path".to_string() => hivePath.display().to_string().into());
+            getLocaleText("hive-parsing-failed", Some(&arg)).into()
+        })
+    }
+
+    /// 解析配置单元
+    /// # 参数
+    /// 1. 配置单元内容
+    pub fn fromBytes(data: Vec<u8>) -> Result<RegistryHive, Box<dyn Error>> {
+        if data.len() < BASE_BLOCK_SIZE || &data[0..4] != b"regf" {
+            return Err(getLocaleText("hive-parsing-failed", None).into());
+        }
+        let rootOffset = readU32(&data, 0x24).unwrap_or(INVALID_OFFSET);
+        let hive = RegistryHive { data, rootOffset };
+        if hive.root().node().is_none() {
+            return Err(getLocaleText("hive-parsing-failed", None).into());
+        }
+        Ok(hive)
+    }
+
+    /// 根项
+    pub fn root(&self) -> RegistryKey<'_> {
+        RegistryKey { hive: self, offset: self.rootOffset }
+    }
+
+    /// 获取单元数据（不含单元大小字段）
+    /// # 参数
+    /// 1. 单元偏移
+    fn cell(&self, offset: u32) -> Option<&[u8]> {
+        if offset == INVALID_OFFSET {
+            return None;
+        }
+        let start = BASE_BLOCK_SIZE.checked_add(offset as usize)?;
+        // 已分配的单元大小为负数，大小包含大小字段本身
+        let size = (readU32(&self.data, start)? as i32).unsigned_abs() as usize;
+        if size < 4 {
+            return None;
+        }
+        self.data.get(start + 4..start.checked_add(size)?)
+    }
+
+    /// 收集子项列表中的项偏移
+    /// # 参数
+    /// 1. 子项列表偏移
+    /// 2. 项偏移列表
+    /// 3. 是否为索引根（ri）的下级列表
+    fn collectSubkeys(&self, listOffset: u32, offsets: &mut Vec<u32>, isNested: bool) {
+        let Some(list) = self.cell(listOffset) else { return };
+        let count = readU16(list, 2).unwrap_or(0) as usize;
+        match list.get(0..2) {
+            // 快速叶（lf）、哈希叶（lh）：偏移 + 名称提示
+            Some(b"lf") | Some(b"lh") => offsets.extend((0..count).filter_map(|index| readU32(list, 4 + index * 8))),
+            // 索引叶（li）：仅偏移
+            Some(b"li") => offsets.extend((0..count).filter_map(|index| readU32(list, 4 + index * 4))),
+            // 索引根（ri）：下级列表偏移，不允许嵌套
+            Some(b"ri") if !isNested => {
+                for index in 0..count {
+                    if let Some(offset) = readU32(list, 4 + index * 4) {
+                        self.collectSubkeys(offset, offsets, true);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 读取值数据
+    /// # 参数
+    /// 1. 值单元（vk）
+    fn valueData(&self, vk: &[u8]) -> Option<RegistryValue> {
+        let size = readU32(vk, 4)?;
+        let dataType = readU32(vk, 12)?;
+        let data: Vec<u8> = if size & DATA_IS_RESIDENT != 0 {
+            vk.get(8..8 + ((size & !DATA_IS_RESIDENT) as usize).min(4))?.to_vec()
+        } else {
+            let size = size as usize;
+            let cell = self.cell(readU32(vk, 8)?)?;
+            if size > BIG_DATA_SEGMENT_SIZE && cell.get(0..2) == Some(b"db") {
+                self.bigData(cell, size)?
+            } else {
+                cell.get(..size)?.to_vec()
+            }
+        };
+        Some(match dataType {
+            REG_SZ | REG_EXPAND_SZ => RegistryValue::String(decodeUtf16(&data).split('\0').next().unwrap_or_default().to_string()),
+            REG_MULTI_SZ => RegistryValue::MultiString(decodeUtf16(&data).split('\0').filter(|item| !item.is_empty()).map(str::to_string).collect()),
+            REG_DWORD if data.len() >= 4 => RegistryValue::Dword(readU32(&data, 0)?),
+            REG_QWORD if data.len() >= 8 => RegistryValue::Qword(u64::from_le_bytes(data[0..8].try_into().ok()?)),
+            _ => RegistryValue::Binary(data),
+        })
+    }
+
+    /// 读取分段保存的大数据（db）
+    /// # 参数
+    /// 1. 大数据单元
+    /// 2. 数据大小
+    fn bigData(&self, db: &[u8], size: usize) -> Option<Vec<u8>> {
+        let count = readU16(db, 2)? as usize;
+        // 数据大小不能超过各分段的总大小
+        if size > count * BIG_DATA_SEGMENT_SIZE {
+            return None;
+        }
+        let list = self.cell(readU32(db, 4)?)?;
+        let mut data: Vec<u8> = Vec::with_capacity(size);
+        for index in 0..count {
+            let segment = self.cell(readU32(list, index * 4)?)?;
+            let length = segment.len().min(BIG_DATA_SEGMENT_SIZE).min(size - data.len());
+            data.extend_from_slice(&segment[..length]);
+        }
+        (data.len() == size).then_some(data)
+    }
+}
+
+impl<'a> RegistryKey<'a> {
+    /// 项单元（nk）
+    fn node(&self) -> Option<&'a [u8]> {
+        self.hive.cell(self.offset).filter(|cell| cell.len() >= 76 && &cell[0..2] == b"nk")
+    }
+
+    /// 项名称
+    pub fn name(&self) -> String {
+        let Some(node) = self.node() else { return String::new() };
+        let flags = readU16(node, 2).unwrap_or(0);
+        let length = readU16(node, 72).unwrap_or(0) as usize;
+        decodeName(node.get(76..76 + length).unwrap_or_default(), flags & KEY_COMP_NAME != 0)
+    }
+
+    /// 子项列表
+    pub fn subkeys(&self) -> Vec<RegistryKey<'a>> {
+        let Some(node) = self.node() else { return Vec::new() };
+        let mut offsets: Vec<u32> = Vec::new();
+        if readU32(node, 20).unwrap_or(0) != 0 {
+            self.hive.collectSubkeys(readU32(node, 28).unwrap_or(INVALID_OFFSET), &mut offsets, false);
+        }
+        offsets
+            .into_iter()
+            .map(|offset| RegistryKey { hive: self.hive, offset })
+            .filter(|key| key.node().is_some())
+            .collect()
+    }
+
+    /// 打开子项（不区分大小写）
+    /// # 参数
+    /// 1. 子项路径（以 `\` 分隔）
+    pub fn openKey(&self, path: &str) -> Option<RegistryKey<'a>> {
+        path.split('\\')
+            .filter(|name| !name.is_empty())
+            .try_fold(*self, |key, name| key.subkeys().into_iter().find(|subkey| subkey.name().eq_ignore_ascii_case(name)))
+    }
+
+    /// 值单元（vk）列表
+    fn valueCells(&self) -> Vec<&'a [u8]> {
+        let Some(node) = self.node() else { return Vec::new() };
+        let count = readU32(node, 36).unwrap_or(0) as usize;
+        let Some(list) = readU32(node, 40).and_then(|offset| self.hive.cell(offset)) else { return Vec::new() };
+        (0..count)
+            .filter_map(|index| readU32(list, index * 4))
+            .filter_map(|offset| self.hive.cell(offset))
+            .filter(|vk| vk.len() >= 20 && &vk[0..2] == b"vk")
+            .collect()
+    }
+
+    /// 值名称列表（默认值为空名称）
+    pub fn valueNames(&self) -> Vec<String> {
+        self.valueCells().into_iter().map(valueName).collect()
+    }
+
+    /// 读取值（不区分大小写，空名称为默认值）
+    /// # 参数
+    /// 1. 值名称
+    pub fn value(&self, name: &str) -> Option<RegistryValue> {
+        self.valueCells()
+            .into_iter()
+            .find(|vk| valueName(vk).eq_ignore_ascii_case(name))
+            .and_then(|vk| self.hive.valueData(vk))
+    }
+
+    /// 读取字符串值
+    /// # 参数
+    /// 1. 值名称
+    pub fn getString(&self, name: &str) -> Option<String> {
+        match self.value(name)? {
+            RegistryValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// 读取多字符串值
+    /// # 参数
+    /// 1. 值名称
+    pub fn getMultiString(&self, name: &str) -> Vec<String> {
+        match self.value(name) {
+            Some(RegistryValue::MultiString(value)) => value,
+            // 部分设备以 REG_SZ 保存单个id
+            Some(RegistryValue::String(value)) if !value.is_empty() => vec![value],
+            _ => Vec::new(),
+        }
+    }
+
+    /// 读取 32 位整数值
+    /// # 参数
+    /// 1. 值名称
+    pub fn getDword(&self, name: &str) -> Option<u32> {
+        match self.value(name)? {
+            RegistryValue::Dword(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// 值名称
+/// # 参数
+/// 1. 值单元（vk）
+fn valueName(vk: &[u8]) -> String {
+    let length = readU16(vk, 2).unwrap_or(0) as usize;
+    let flags = readU16(vk, 16).unwrap_or(0);
+    decodeName(vk.get(20..20 + length).unwrap_or_default(), flags & VALUE_COMP_NAME != 0)
+}
+
+/// 解码项、值名称
+/// # 参数
+/// 1. 名称数据
+/// 2. 是否为 ASCII 编码（否则为 UTF-16LE）
+fn decodeName(data: &[u8], isCompressed: bool) -> String {
+    if isCompressed {
+        data.iter().map(|byte| *byte as char).collect()
+    } else {
+        decodeUtf16(data)
+    }
+}
+
+/// 解码 UTF-16LE 数据
+/// # 参数
+/// 1. 数据
+fn decodeUtf16(data: &[u8]) -> String {
+    let units: Vec<u16> = data.chunks_exact(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// 读取 16 位小端整数
+fn readU16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(offset..offset.checked_add(2)?)?.try_into().ok()?))
+}
+
+/// 读取 32 位小端整数
+fn readU32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset.checked_add(4)?)?.try_into().ok()?))
+}
@@ -1,9 +1,22 @@
 use std::error::Error;
+use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use flate2::Crc;
+use crate::i18n::getLocaleText;
+use crate::utils::archiveBackend::{matchPattern, ArchiveBackend, ArchiveEntry, FileVisitor};
+use crate::utils::sevenZipParser::parseTechnicalListing;
 use crate::utils::util::writeEmbedFile;
 use crate::TEMP_PATH;
 
+/// 7-zip 退出代码：成功
+pub const EXIT_SUCCESS: i32 = 0;
+/// 7-zip 退出代码：警告（如部分文件被占用，结果不完整）
+pub const EXIT_WARNING: i32 = 1;
+/// 7-zip 退出代码：致命错误（如密码错误、压缩包损坏）
+pub const EXIT_FATAL_ERROR: i32 = 2;
+
 pub struct sevenZip {
     zipProgram: PathBuf,
 }
@@ -16,32 +29,6 @@ impl sevenZip {
         Ok(sevenZip { zipProgram })
     }
 
-    /// 7-zip 创建压缩包
-    pub fn createArchivePage(
-        &self,
-        inputPath: &Path,
-        outPath: &Path,
-    ) -> Result<bool, Box<dyn Error>> {
-        // 7z a -t7z "文件名.7z" "路径\*" -mx=9 -ms=128m -mmt -r
-        let output = Command::new(&self.zipProgram)
-            .arg("a")
-            // 指定7z格式
-            .arg("-t7z")
-            .arg(outPath)
-            .arg(format!("{}\\*", inputPath.to_str().unwrap()))
-            // 极限压缩
-            .arg("-mx=9")
-            // 固实压缩（8MB分块）
-            .arg("-ms=8m")
-            // 启用多线程
-            .arg("-mmt")
-            // 递归子目录
-            .arg("-r")
-            .output()?;
-        let content = String::from_utf8_lossy(&output.stdout);
-        Ok(content.contains("Everything is Ok"))
-    }
-
     /// 7-zip 释放文件（指定压缩包内文件）
     /// 从存档中提取文件（不使用目录名）
     /// 注意：此命令会将压缩档案中的所有文件输出到同一个目录中
@@ -57,6 +44,9 @@ impl sevenZip {
         extractPath: &str,
         outPath: &Path,
     ) -> Result<bool, Box<dyn Error>> {
+        if !self.hasMatch(zipFile, password, extractPath) {
+            return Ok(false);
+        }
         let output = Command::new(&self.zipProgram)
             .arg("e")
             .arg(zipFile.to_str().unwrap())
@@ -66,8 +56,36 @@ impl sevenZip {
             .arg(format!("-p{}", password.unwrap_or("")))
             .arg(format!("-o{}", outPath.to_str().unwrap()))
             .output()?;
-        let content = String::from_utf8_lossy(&output.stdout);
-        Ok(!content.contains("No files to process"))
+        Ok(output.status.code() == Some(EXIT_SUCCESS))
+    }
+
+    /// 压缩包中是否有匹配通配符的文件
+    /// 7-zip 未匹配到文件时仍返回成功，需先列出文件判断
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 通配符
+    fn hasMatch(&self, zipFile: &Path, password: Option<&str>, pattern: &str) -> bool {
+        self.list(zipFile, password).unwrap_or_default().iter().any(|entry| matchPattern(&entry.Path, pattern))
+    }
+}
+
+impl ArchiveBackend for sevenZip {
+    /// 7-zip 列出文件
+    /// 解析 `l -slt` 的技术信息输出（路径、大小、CRC、属性、加密、固实块）
+    fn list(&self, archivePath: &Path, password: Option<&str>) -> Result<Vec<ArchiveEntry>, Box<dyn Error>> {
+        let output = Command::new(&self.zipProgram)
+            .arg("l")
+            .arg("-slt")
+            .arg("-sccUTF-8")
+            .arg(format!("-p{}", password.unwrap_or("")))
+            .arg(archivePath.to_str().unwrap())
+            .output()?;
+        if output.status.code() != Some(EXIT_SUCCESS) {
+            let arg = hash_map!("path".to_string() => archivePath.display().to_string().into());
+            return Err(getLocaleText("archive-parsing-failed", Some(&arg)).into());
+        }
+        Ok(parseTechnicalListing(&String::from_utf8_lossy(&output.stdout)))
     }
 
     /// 7-zip 解压文件
@@ -78,13 +96,10 @@ impl sevenZip {
     /// 2. 压缩包密码
     /// 3. 压缩包内文件路径（解压全部文件为*）
     /// 4. 输出路径
-    pub fn extractFilesFromPath(
-        &self,
-        zipFile: &Path,
-        password: Option<&str>,
-        extractPath: &str,
-        outPath: &Path,
-    ) -> Result<bool, Box<dyn Error>> {
+    fn extractPattern(&self, zipFile: &Path, password: Option<&str>, extractPath: &str, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        if !self.hasMatch(zipFile, password, extractPath) {
+            return Ok(false);
+        }
         let output = Command::new(&self.zipProgram)
             .arg("x")
             .arg("-r")
@@ -95,22 +110,139 @@ impl sevenZip {
             .arg(format!("-p{}", password.unwrap_or("")))
             .arg(format!("-o{}", outPath.to_str().unwrap()))
             .output()?;
-        let content = String::from_utf8_lossy(&output.stdout);
-        Ok(!content.contains("No files to process") && !content.contains("Errors") && !content.contains("Can't open as archive"))
+        Ok(output.status.code() == Some(EXIT_SUCCESS))
     }
 
-    /// 判断指定文件是否为驱动包（包含驱动INF文件）
-    /// 用于判断自身程序是否为驱动包应用程序
+    /// 7-zip 批量解压文件
+    /// 将通配符写入列表文件（`@listfile`），一次调用解压全部匹配的文件，固实块只解压一遍
     /// # 参数
     /// 1. 压缩包路径
-    pub fn isDriverPackage(&self, zipFile: &Path) -> Result<bool, Box<dyn Error>> {
+    /// 2. 压缩包密码
+    /// 3. 通配符列表
+    /// 4. 输出路径
+    fn extractPatterns(&self, zipFile: &Path, password: Option<&str>, patterns: &[String], outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        let entryList = self.list(zipFile, password).unwrap_or_default();
+        let patterns: Vec<&String> = patterns.iter().filter(|pattern| entryList.iter().any(|entry| matchPattern(&entry.Path, pattern))).collect();
+        if patterns.is_empty() {
+            return Ok(false);
+        }
+        // 空通配符表示解压全部文件
+        let listContent: Vec<&str> = patterns.iter().map(|pattern| if pattern.is_empty() { "*" } else { pattern.as_str() }).collect();
+        let listFile = TEMP_PATH.join(format!("{}.{}.txt", zipFile.file_name().unwrap_or_default().to_string_lossy(), std::process::id()));
+        fs::write(&listFile, listContent.join("\r\n"))?;
         let output = Command::new(&self.zipProgram)
-            .arg("l")
-            .arg("-ba")
-            .arg("-sccUTF-8")
+            .arg("x")
+            .arg("-r")
+            // 列表文件为 UTF-8 编码
+            .arg("-scsUTF-8")
             .arg(zipFile.to_str().unwrap())
+            .arg(format!("@{}", listFile.to_str().unwrap()))
+            .arg("-y")
+            .arg("-aos")
+            .arg(format!("-p{}", password.unwrap_or("")))
+            .arg(format!("-o{}", outPath.to_str().unwrap()))
+            .output();
+        fs::remove_file(&listFile).ok();
+        Ok(output?.status.code() == Some(EXIT_SUCCESS))
+    }
+
+    /// 7-zip 解压指定文件
+    /// 提取具有完整路径的文件（不递归子目录）
+    /// # 参数
+    /// 1. 压缩包路径
+    /// 2. 压缩包密码
+    /// 3. 压缩包内文件路径
+    /// 4. 输出路径
+    fn extractEntry(&self, zipFile: &Path, password: Option<&str>, entryPath: &str, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        let entryPath = entryPath.replace('\\', "/");
+        if !self.list(zipFile, password).unwrap_or_default().iter().any(|entry| entry.Path.eq_ignore_ascii_case(&entryPath)) {
+            return Ok(false);
+        }
+        let output = Command::new(&self.zipProgram)
+            .arg("x")
+            .arg(zipFile.to_str().unwrap())
+            .arg(&entryPath)
+            .arg("-y")
+            .arg("-aos")
+            .arg(format!("-p{}", password.unwrap_or("")))
+            .arg(format!("-o{}", outPath.to_str().unwrap()))
             .output()?;
-        let content = String::from_utf8_lossy(&output.stdout);
-        Ok(content.to_lowercase().contains(".inf"))
+        Ok(output.status.code() == Some(EXIT_SUCCESS))
+    }
+
+    /// 7-zip 创建压缩包
+    fn create(&self, inputPath: &Path, outPath: &Path) -> Result<bool, Box<dyn Error>> {
+        // 7z a -t7z "文件名.7z" "路径\*" -mx=9 -ms=128m -mmt -r
+        let output = Command::new(&self.zipProgram)
+            .arg("a")
+            // 指定7z格式
+            .arg("-t7z")
+            .arg(outPath)
+            .arg(format!("{}\\*", inputPath.to_str().unwrap()))
+            // 极限压缩
+            .arg("-mx=9")
+            // 固实压缩（8MB分块）
+            .arg("-ms=8m")
+            // 启用多线程
+            .arg("-mmt")
+            // 递归子目录
+            .arg("-r")
+            .output()?;
+        Ok(output.status.code() == Some(EXIT_SUCCESS))
+    }
+
+    /// 7-zip 读取文件内容
+    /// `e -so` 将匹配的文件按压缩包中的顺序连续输出到标准输出，按列出的文件大小逐个读取，并以 CRC 校验读取结果
+    fn visitFiles(&self, archivePath: &Path, password: Option<&str>, pattern: &str, visit: &mut FileVisitor) -> Result<(), Box<dyn Error>> {
+        let entryList: Vec<ArchiveEntry> = self.list(archivePath, password)?.into_iter().filter(|entry| !entry.IsDir && matchPattern(&entry.Path, pattern)).collect();
+        if entryList.is_empty() {
+            return Ok(());
+        }
+        let mut child = Command::new(&self.zipProgram)
+            .arg("e")
+            .arg("-so")
+            .arg("-r")
+            .arg(archivePath.to_str().unwrap())
+            .arg(if pattern.is_empty() { "*" } else { pattern })
+            .arg(format!("-p{}", password.unwrap_or("")))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let parsingFailed = || -> Box<dyn Error> {
+            let arg = hash_map!("path".to_string() => archivePath.display().to_string().into());
+            getLocaleText("archive-parsing-failed", Some(&arg)).into()
+        };
+        let mut stdout = child.stdout.take().ok_or_else(parsingFailed)?;
+        let readAll = || -> Result<(), Box<dyn Error>> {
+            for entry in entryList {
+                let mut content = vec![0u8; entry.Size as usize];
+                stdout.read_exact(&mut content).map_err(|_| parsingFailed())?;
+                if let Some(crc) = entry.Crc {
+                    let mut checksum = Crc::new();
+                    checksum.update(&content);
+                    if checksum.sum() != crc {
+                        return Err(parsingFailed());
+                    }
+                }
+                visit(entry, content)?;
+            }
+            // 输出的内容应与列出的文件大小一致
+            if stdout.read(&mut [0u8; 1])? != 0 {
+                return Err(parsingFailed());
+            }
+            Ok(())
+        };
+        let result = readAll();
+        // 提前停止读取时结束 7-zip，避免其阻塞在写入标准输出
+        if result.is_err() {
+            child.kill().ok();
+        }
+        let status = child.wait()?;
+        result?;
+        if status.code() != Some(EXIT_SUCCESS) {
+            return Err(parsingFailed());
+        }
+        Ok(())
     }
 }
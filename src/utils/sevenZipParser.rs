@@ -0,0 +1,70 @@
+use crate::utils::archiveBackend::{ArchiveEntry, FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM};
+use chrono::NaiveDateTime;
+
+/// 解析 7-zip `l -slt` 输出
+/// # 参数
+/// 1. 输出内容
+/// # 规则
+/// 1. “----------” 之前为压缩包信息，之后每个以空行分隔的 “键 = 值” 段落为一个文件
+/// 2. 目录由 “Folder = +” 或属性中的 D 标识
+/// 3. 修改时间按 UTC 转换（7-zip 输出本地时间，仅用于判断文件是否变更）
+pub fn parseTechnicalListing(content: &str) -> Vec<ArchiveEntry> {
+    let mut entryList: Vec<ArchiveEntry> = Vec::new();
+    let Some((_, content)) = content.split_once("\n----------") else { return entryList };
+
+    let mut entry: Option<ArchiveEntry> = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            entryList.extend(entry.take());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" = ").or_else(|| line.strip_suffix(" =").map(|key| (key, ""))) else { continue };
+        if key == "Path" {
+            entryList.extend(entry.take());
+            entry = Some(ArchiveEntry { Path: value.replace('\\', "/"), ..Default::default() });
+            continue;
+        }
+        let Some(entry) = entry.as_mut() else { continue };
+        match key {
+            "Folder" => entry.IsDir |= value == "+",
+            "Size" => entry.Size = value.parse().unwrap_or(0),
+            "Modified" => entry.Modified = parseTime(value),
+            "Attributes" => {
+                entry.Attributes = parseAttributes(value);
+                entry.IsDir |= entry.Attributes & FILE_ATTRIBUTE_DIRECTORY != 0;
+            }
+            "CRC" => entry.Crc = u32::from_str_radix(value, 16).ok(),
+            "Encrypted" => entry.Encrypted = value == "+",
+            "Block" => entry.Block = value.parse().ok(),
+            _ => {}
+        }
+    }
+    entryList.extend(entry);
+    entryList
+}
+
+/// 解析文件属性（如 “A”、“D”、“....A”、“RHS -rw-r--r--”）
+/// # 参数
+/// 1. 属性文本
+fn parseAttributes(value: &str) -> u32 {
+    value.split_whitespace().next().unwrap_or_default().chars().fold(0, |attributes, flag| {
+        attributes
+            | match flag {
+                'R' => FILE_ATTRIBUTE_READONLY,
+                'H' => FILE_ATTRIBUTE_HIDDEN,
+                'S' => FILE_ATTRIBUTE_SYSTEM,
+                'D' => FILE_ATTRIBUTE_DIRECTORY,
+                'A' => FILE_ATTRIBUTE_ARCHIVE,
+                _ => 0,
+            }
+    })
+}
+
+/// 解析修改时间（如 “2023-06-01 12:30:45”，新版本含小数秒）
+/// # 参数
+/// 1. 时间文本
+fn parseTime(value: &str) -> Option<u64> {
+    let value = value.split('.').next()?;
+    let time = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok()?;
+    u64::try_from(time.and_utc().timestamp()).ok()
+}
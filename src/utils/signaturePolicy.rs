@@ -0,0 +1,138 @@
+use crate::command::create_index::InfInfo;
+use crate::i18n::getLocaleText;
+use crate::utils::catalog::SignatureStatus;
+use crate::utils::driverFiles::DriverSignature;
+use crate::utils::targetOS::TargetOS;
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+
+/// 驱动签名策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignaturePolicy {
+    /// 仅 WHQL 签名且适用于目标系统版本的驱动
+    Whql,
+    /// WHQL 或第三方签名的驱动
+    Signed,
+    /// 不限制签名（与编录文件不一致的驱动仍不会安装）
+    #[default]
+    Any,
+}
+
+/// 驱动不符合签名策略的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyViolation {
+    /// 未签名（缺少编录文件或编录文件没有签名）
+    Unsigned,
+    /// 驱动文件与编录文件不一致
+    Tampered,
+    /// 签名或证书链无法验证（签名者）
+    Unverified(String),
+    /// 非 WHQL 签名（签名者）
+    NotWhql(String),
+    /// WHQL 签名不适用于目标系统版本（OSAttr）
+    UnsupportedOS(String),
+}
+
+impl SignaturePolicy {
+    /// 根据名称获取签名策略（不区分大小写）
+    pub fn fromName(name: &str) -> Option<SignaturePolicy> {
+        match name.to_lowercase().as_str() {
+            "whql" => Some(SignaturePolicy::Whql),
+            "signed" => Some(SignaturePolicy::Signed),
+            "any" => Some(SignaturePolicy::Any),
+            _ => None,
+        }
+    }
+
+    /// 检查签名是否符合策略
+    /// # 参数
+    /// 1. 签名状态
+    /// 2. 签名者证书主题
+    /// 3. 签名适用的系统版本（OSAttr）
+    /// 4. 目标系统
+    /// # 规则
+    /// 1. any：不限制
+    /// 2. signed：WHQL 或第三方签名，未签名、签名无法验证、与编录文件不一致的驱动不符合
+    /// 3. whql：WHQL 签名，且 OSAttr 包含目标系统版本
+    /// 4. WHQL 与第三方签名均须验证签名及证书链，仅签名者名称符合的编录文件视为签名无法验证
+    /// 5. 签名状态未知（旧版索引）时不限制，安装前按解压后的驱动文件再次检查
+    pub fn check(self, status: SignatureStatus, signer: &str, osAttr: &str, target: &TargetOS) -> Result<(), PolicyViolation> {
+        if self == SignaturePolicy::Any {
+            return Ok(());
+        }
+        match status {
+            SignatureStatus::Unknown => Ok(()),
+            SignatureStatus::Unsigned => Err(PolicyViolation::Unsigned),
+            SignatureStatus::Tampered => Err(PolicyViolation::Tampered),
+            SignatureStatus::Unverified => Err(PolicyViolation::Unverified(signer.to_string())),
+            SignatureStatus::Signed if self == SignaturePolicy::Whql => Err(PolicyViolation::NotWhql(signer.to_string())),
+            SignatureStatus::Whql if self == SignaturePolicy::Whql && !osAttrCovers(osAttr, target) => Err(PolicyViolation::UnsupportedOS(osAttr.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// 按索引中的INF信息检查签名
+    /// # 参数
+    /// 1. INF驱动信息
+    /// 2. 目标系统
+    pub fn checkInf(self, infInfo: &InfInfo, target: &TargetOS) -> Result<(), PolicyViolation> {
+        self.check(infInfo.Signature, &infInfo.Signer, &infInfo.OSAttr, target)
+    }
+
+    /// 按驱动文件的校验结果检查签名
+    /// # 参数
+    /// 1. 驱动签名
+    /// 2. 目标系统
+    pub fn checkSignature(self, signature: &DriverSignature, target: &TargetOS) -> Result<(), PolicyViolation> {
+        self.check(signature.Status, &signature.Signer, &signature.OSAttr, target)
+    }
+}
+
+impl PolicyViolation {
+    /// 拒绝驱动的提示信息
+    /// # 参数
+    /// 1. 驱动路径
+    /// 2. 目标系统
+    pub fn message(&self, path: &str, target: &TargetOS) -> String {
+        let reason = match self {
+            PolicyViolation::Unsigned => getLocaleText("policy-unsigned", None),
+            PolicyViolation::Tampered => getLocaleText("policy-tampered", None),
+            PolicyViolation::Unverified(signer) => {
+                let arg: HashMap<String, FluentValue> = hash_map!("signer".to_string() => signer.clone().into());
+                getLocaleText("policy-unverified", Some(&arg))
+            }
+            PolicyViolation::NotWhql(signer) => {
+                let arg: HashMap<String, FluentValue> = hash_map!("signer".to_string() => signer.clone().into());
+                getLocaleText("policy-not-whql", Some(&arg))
+            }
+            PolicyViolation::UnsupportedOS(osAttr) => {
+                let arg: HashMap<String, FluentValue> = hash_map!(
+                    "version".to_string() => format!("{}.{}", target.Major, target.Minor).into(),
+                    "osAttr".to_string() => osAttr.clone().into(),
+                );
+                getLocaleText("policy-unsupported-os", Some(&arg))
+            }
+        };
+        let arg: HashMap<String, FluentValue> = hash_map!(
+            "path".to_string() => path.to_string().into(),
+            "reason".to_string() => reason.into(),
+        );
+        getLocaleText("driver-policy-rejected", Some(&arg))
+    }
+}
+
+/// OSAttr 是否包含目标系统版本
+/// OSAttr 以逗号分隔，每项为 `平台:主版本.次版本`，平台 2 为 Windows NT 系列（如 `2:6.3,2:10.0`）
+/// # 参数
+/// 1. OSAttr
+/// 2. 目标系统
+pub fn osAttrCovers(osAttr: &str, target: &TargetOS) -> bool {
+    osAttr
+        .split(',')
+        .filter_map(|item| item.trim().split_once(':'))
+        .filter(|(platform, _)| platform.trim() == "2")
+        .any(|(_, version)| {
+            let mut numbers = version.trim().split('.').map(|number| number.trim().parse::<u32>().ok());
+            numbers.next().flatten() == Some(target.Major) && numbers.next().flatten().unwrap_or(0) == target.Minor
+        })
+}
@@ -0,0 +1,218 @@
+use crate::utils::util::{getArchCode, getSystemVersion};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use windows::Win32::System::SystemInformation::{
+    GetNativeSystemInfo, GetVersionExW, IMAGE_FILE_MACHINE, OSVERSIONINFOEXW, OSVERSIONINFOW, PROCESSOR_ARCHITECTURE_AMD64, PROCESSOR_ARCHITECTURE_ARM,
+    PROCESSOR_ARCHITECTURE_ARM64, PROCESSOR_ARCHITECTURE_INTEL, SYSTEM_INFO,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+use windows_version::OsVersion;
+
+/// 目标系统
+/// 用于判断INF中 Models 节的修饰是否适用
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TargetOS {
+    /// 系统架构（如 NTamd64）
+    pub(crate) Arch: String,
+    /// 主版本号
+    pub(crate) Major: u32,
+    /// 次版本号
+    pub(crate) Minor: u32,
+    /// 产品类型（1: 工作站，2: 域控制器，3: 服务器）
+    pub(crate) ProductType: u32,
+    /// 套件掩码
+    pub(crate) SuiteMask: u32,
+    /// 内部版本号
+    pub(crate) Build: u32,
+}
+
+impl TargetOS {
+    /// 当前运行的系统
+    pub fn current() -> TargetOS {
+        // 当前系统架构（以本机架构为准，兼容 ARM64 上运行的 x86、x64、ARM64EC 程序）
+        let arch = nativeMachine()
+            .and_then(TargetOS::archFromMachine)
+            .unwrap_or(match env::consts::ARCH {
+                "x86" => "NTx86",
+                "x86_64" => "NTamd64",
+                "arm" => "NTarm",
+                "aarch64" | "arm64ec" => "NTarm64",
+                _ => "",
+            });
+
+        let version = OsVersion::current();
+
+        // 产品类型与套件掩码（不受系统版本兼容性伪装影响）
+        let mut info = OSVERSIONINFOEXW {
+            dwOSVersionInfoSize: size_of::<OSVERSIONINFOEXW>() as u32,
+            ..Default::default()
+        };
+        let (productType, suiteMask) = match unsafe { GetVersionExW(&mut info as *mut OSVERSIONINFOEXW as *mut OSVERSIONINFOW) } {
+            Ok(_) => (info.wProductType as u32, info.wSuiteMask as u32),
+            Err(_) => (1, 0),
+        };
+
+        TargetOS {
+            Arch: arch.to_string(),
+            Major: version.major,
+            Minor: version.minor,
+            ProductType: productType,
+            SuiteMask: suiteMask,
+            Build: version.build,
+        }
+    }
+
+    /// 离线系统
+    /// 架构与版本号读取自离线系统的 ntoskrnl.exe，产品类型视为工作站
+    /// # 参数
+    /// 1. 离线系统盘（如 `D:\`）
+    pub fn offline(systemPath: &Path) -> Result<TargetOS, Box<dyn Error>> {
+        let arch = TargetOS::archFromMachine(getArchCode(systemPath)?).ok_or("Unsupported architecture")?;
+        let (major, minor, build) = getSystemVersion(systemPath)?;
+        Ok(TargetOS {
+            Arch: arch.to_string(),
+            Major: major,
+            Minor: minor,
+            ProductType: 1,
+            SuiteMask: 0,
+            Build: build,
+        })
+    }
+
+    /// 将 PE 文件 Machine 字段转换为 INF 架构名称
+    /// # 参数
+    /// 1. Machine 字段（0x014c: x86，0x8664: x64，0x01c4: ARM，0xAA64: ARM64）
+    pub fn archFromMachine(machine: u16) -> Option<&'static str> {
+        match machine {
+            0x014c => Some("NTx86"),
+            0x8664 => Some("NTamd64"),
+            0x01c4 => Some("NTarm"),
+            0xAA64 => Some("NTarm64"),
+            _ => None,
+        }
+    }
+}
+
+/// 获取本机架构的 Machine 字段
+/// WOW64 与 ARM64 模拟环境下 GetNativeSystemInfo 返回的是模拟的架构，故优先使用 IsWow64Process2
+fn nativeMachine() -> Option<u16> {
+    let mut processMachine = IMAGE_FILE_MACHINE::default();
+    let mut nativeMachine = IMAGE_FILE_MACHINE::default();
+    if unsafe { IsWow64Process2(GetCurrentProcess(), &mut processMachine, Some(&mut nativeMachine)) }.is_ok() {
+        return Some(nativeMachine.0);
+    }
+
+    // 旧版系统不支持 IsWow64Process2
+    let mut info = SYSTEM_INFO::default();
+    unsafe { GetNativeSystemInfo(&mut info) };
+    match unsafe { info.Anonymous.Anonymous.wProcessorArchitecture } {
+        PROCESSOR_ARCHITECTURE_INTEL => Some(0x014c),
+        PROCESSOR_ARCHITECTURE_AMD64 => Some(0x8664),
+        PROCESSOR_ARCHITECTURE_ARM => Some(0x01c4),
+        PROCESSOR_ARCHITECTURE_ARM64 => Some(0xAA64),
+        _ => None,
+    }
+}
+
+/// INF Models 节修饰（TargetOSVersion）
+/// 语法：`NT[Architecture][.[OSMajorVersion][.[OSMinorVersion][.[ProductType][.[SuiteMask][.[BuildNumber]]]]]]`
+/// [相关文档](https://learn.microsoft.com/zh-cn/windows-hardware/drivers/install/inf-manufacturer-section)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TargetOSVersion {
+    /// 架构（未指定时仅适用于 x86）
+    pub(crate) Arch: Option<String>,
+    /// 最低主版本号
+    pub(crate) Major: Option<u32>,
+    /// 最低次版本号
+    pub(crate) Minor: Option<u32>,
+    /// 产品类型
+    pub(crate) ProductType: Option<u32>,
+    /// 套件掩码
+    pub(crate) SuiteMask: Option<u32>,
+    /// 最低内部版本号
+    pub(crate) Build: Option<u32>,
+}
+
+impl TargetOSVersion {
+    /// 解析修饰
+    /// 空字符串表示未修饰的 Models 节
+    /// # 参数
+    /// 1. 修饰（如 `NTamd64.10.0...16299`）
+    pub fn parse(decoration: &str) -> Option<TargetOSVersion> {
+        if decoration.is_empty() {
+            return Some(TargetOSVersion::default());
+        }
+        if decoration.len() < 2 || !decoration[..2].eq_ignore_ascii_case("NT") {
+            return None;
+        }
+
+        let mut fields = decoration[2..].split('.');
+        let arch = fields.next().unwrap_or_default();
+        let mut numbers: [Option<u32>; 5] = [None; 5];
+        for number in numbers.iter_mut() {
+            let Some(field) = fields.next() else { break };
+            if field.is_empty() {
+                continue;
+            }
+            *number = Some(parseNumber(field)?);
+        }
+
+        Some(TargetOSVersion {
+            Arch: (!arch.is_empty()).then(|| format!("NT{}", arch)),
+            Major: numbers[0],
+            Minor: numbers[1],
+            ProductType: numbers[2],
+            SuiteMask: numbers[3],
+            Build: numbers[4],
+        })
+    }
+
+    /// 是否适用于目标系统
+    pub fn isApplicable(&self, target: &TargetOS) -> bool {
+        // 架构：未指定架构的节仅适用于 x86
+        let arch = self.Arch.as_deref().unwrap_or("NTx86");
+        if !arch.eq_ignore_ascii_case(&target.Arch) {
+            return false;
+        }
+        // 版本：适用于指定版本及更高版本
+        let version = (self.Major.unwrap_or(0), self.Minor.unwrap_or(0));
+        if (target.Major, target.Minor) < version {
+            return false;
+        }
+        if self.ProductType.is_some_and(|productType| productType != target.ProductType) {
+            return false;
+        }
+        if self.SuiteMask.is_some_and(|suiteMask| target.SuiteMask & suiteMask != suiteMask) {
+            return false;
+        }
+        if self.Build.is_some_and(|build| target.Build < build) {
+            return false;
+        }
+        true
+    }
+
+    /// 比较两个修饰的具体程度（越大越具体）
+    /// 版本越高越具体，版本相同时指定的字段越多越具体
+    pub fn compareSpecificity(&self, other: &TargetOSVersion) -> Ordering {
+        let key = |item: &TargetOSVersion| {
+            (
+                (item.Major.unwrap_or(0), item.Minor.unwrap_or(0), item.Build.unwrap_or(0)),
+                item.ProductType.is_some(),
+                item.SuiteMask.is_some(),
+                item.Arch.is_some(),
+            )
+        };
+        key(self).cmp(&key(other))
+    }
+}
+
+/// 解析十进制或十六进制（0x前缀）数字
+fn parseNumber(value: &str) -> Option<u32> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
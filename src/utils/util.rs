@@ -1,3 +1,4 @@
+use crate::utils::archiveBackend::ArchiveFormat;
 use crate::Asset;
 use glob::MatchOptions;
 use goblin::pe::PE;
@@ -132,16 +133,9 @@ pub fn move_dir(src: &Path, dst: &Path) -> io::Result<()> {
     Ok(())
 }
 
-/// 是否为压缩包文件
+/// 是否为压缩包文件（按文件头识别，不依赖扩展名）
 pub fn isArchive(archivePath: &Path) -> bool {
-    let extension = archivePath.extension().unwrap().to_str().unwrap_or("");
-    let supportExtension = ["7z", "zip", "rar", "cab", "tar", "wim"];
-    for item in supportExtension.iter() {
-        if extension.to_lowercase() == *item.to_lowercase() {
-            return true;
-        }
-    }
-    false
+    ArchiveFormat::detect(archivePath).is_some()
 }
 
 /// 比较版本号大小
@@ -177,6 +171,22 @@ pub fn compareVersion(version1: &str, version2: &str) -> Result<Ordering, Box<dy
     Ok(Ordering::Equal)
 }
 
+/// 解析驱动日期（DriverVer 中的 月/日/年）
+///
+/// 参数
+/// - `date`: 驱动日期
+///
+/// 返回
+/// - `Some((年, 月, 日))`
+/// - `None`：格式错误
+pub fn parseDriverDate(date: &str) -> Option<(u32, u32, u32)> {
+    let fields: Vec<&str> = date.trim().split(['/', '-']).collect();
+    if fields.len() != 3 {
+        return None;
+    }
+    Some((fields[2].parse().ok()?, fields[0].parse().ok()?, fields[1].parse().ok()?))
+}
+
 /// 生成临时文件名
 ///
 /// 参数
@@ -270,6 +280,38 @@ pub fn getArchCode(systemPath: &Path) -> Result<u16, Box<dyn Error>> {
     Ok(machine)
 }
 
+/// 获取系统版本号
+///
+/// 参数
+/// - `systemPath`: 系统目录
+///
+/// 返回
+/// - `Ok((u32, u32, u32))`: ntoskrnl.exe 的文件版本（主版本号, 次版本号, 内部版本号）
+/// - `Err(...)`：读取或解析失败
+pub fn getSystemVersion(systemPath: &Path) -> Result<(u32, u32, u32), Box<dyn Error>> {
+    let krnl_path = systemPath.join("Windows").join("System32").join("ntoskrnl.exe");
+    let bytes = read(&krnl_path)?;
+
+    // 查找版本资源 VS_VERSION_INFO，其后为 VS_FIXEDFILEINFO（以签名 0xFEEF04BD 开头）
+    let key: Vec<u8> = "VS_VERSION_INFO".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let keyOffset = bytes
+        .windows(key.len())
+        .position(|item| item == key.as_slice())
+        .ok_or("VS_VERSION_INFO not found")?;
+    let signature = 0xFEEF04BDu32.to_le_bytes();
+    let offset = bytes[keyOffset..]
+        .windows(4)
+        .take(64)
+        .position(|item| item == signature)
+        .map(|pos| keyOffset + pos)
+        .ok_or("VS_FIXEDFILEINFO not found")?;
+    let readU32 = |pos: usize| -> Option<u32> { bytes.get(pos..pos + 4).map(|item| u32::from_le_bytes(item.try_into().unwrap())) };
+    // dwFileVersionMS、dwFileVersionLS
+    let versionMS = readU32(offset + 8).ok_or("Invalid VS_FIXEDFILEINFO")?;
+    let versionLS = readU32(offset + 12).ok_or("Invalid VS_FIXEDFILEINFO")?;
+    Ok((versionMS >> 16, versionMS & 0xFFFF, versionLS >> 16))
+}
+
 /// 查找离线系统盘
 ///
 /// 返回
@@ -0,0 +1,248 @@
+#!/usr/bin/env python3
+# 生成编录文件测试所需的驱动文件（需要 openssl）
+#
+# sample.inf     驱动INF
+# sample.sys     最小的 PE32+ 驱动映像
+# sample.cat     以测试证书链签名的编录文件（测试根证书 -> 测试 PCA -> 签名证书，签名证书具有 WHQL 用途）
+# forged.cat     内容与 sample.cat 相同，签名证书主题与用途相同，但由另一个同名的根证书颁发
+# root.modulus   测试根证书的 RSA 模数（仅测试时视为 Microsoft 根证书）
+#
+# 用法：python3 generate.py（在本目录中生成上述文件，密钥不保留）
+
+import hashlib
+import os
+import struct
+import subprocess
+import tempfile
+import uuid
+
+OUT = os.path.dirname(os.path.abspath(__file__))
+
+OID_CATALOG_LIST = "1.3.6.1.4.1.311.12.1.1"
+OID_CATALOG_LIST_MEMBER = "1.3.6.1.4.1.311.12.1.2"
+OID_NAME_VALUE = "1.3.6.1.4.1.311.12.2.1"
+OID_INDIRECT_DATA = "1.3.6.1.4.1.311.2.1.4"
+OID_PE_IMAGE_DATA = "1.3.6.1.4.1.311.2.1.15"
+OID_CAB_DATA = "1.3.6.1.4.1.311.2.1.25"
+OID_CTL = "1.3.6.1.4.1.311.10.1"
+OID_SHA1 = "1.3.14.3.2.26"
+OID_SHA256 = "2.16.840.1.101.3.4.2.1"
+
+OS_ATTR = "2:6.2,2:6.3,2:10.0"
+HARDWARE_ID = r"PCI\VEN_FFFF&DEV_0001"
+
+INF = r"""[Version]
+Signature   = "$Windows NT$"
+Class       = System
+ClassGUID   = {4d36e97d-e325-11ce-bfc1-08002be10318}
+Provider    = %Provider%
+DriverVer   = 05/25/2016, 9.8.6.0
+CatalogFile = sample.cat
+
+[SourceDisksNames]
+1 = %DiskName%
+
+[SourceDisksFiles]
+sample.sys = 1
+
+[DestinationDirs]
+DefaultDestDir = 12
+
+[Manufacturer]
+%Manufacturer% = Sample, NTamd64.6.2
+
+[Sample.NTamd64.6.2]
+%DeviceDesc% = Sample.Install, PCI\VEN_FFFF&DEV_0001&SUBSYS_0001FFFF, PCI\VEN_FFFF&DEV_0001
+
+[Sample.Install.NTamd64]
+CopyFiles = @sample.sys
+
+[Sample.Install.NTamd64.Services]
+AddService = sample, 0x00000002, Sample.Service
+
+[Sample.Service]
+ServiceType   = 1
+StartType     = 3
+ErrorControl  = 1
+ServiceBinary = %12%\sample.sys
+
+[Strings]
+Provider     = "DriverIndexer"
+Manufacturer = "DriverIndexer"
+DeviceDesc   = "DriverIndexer Sample Device"
+DiskName     = "DriverIndexer Sample Disk"
+""".replace("\n", "\r\n")
+
+
+# DER 编码
+def der(tag, content):
+    length = len(content)
+    if length < 0x80:
+        header = bytes([length])
+    else:
+        size = length.to_bytes((length.bit_length() + 7) // 8, "big")
+        header = bytes([0x80 | len(size)]) + size
+    return bytes([tag]) + header + content
+
+
+def seq(*items):
+    return der(0x30, b"".join(items))
+
+
+def set_of(*items):
+    return der(0x31, b"".join(items))
+
+
+def oid(text):
+    parts = [int(part) for part in text.split(".")]
+    body = bytes([parts[0] * 40 + parts[1]])
+    for part in parts[2:]:
+        chunk = [part & 0x7F]
+        part >>= 7
+        while part:
+            chunk.insert(0, 0x80 | (part & 0x7F))
+            part >>= 7
+        body += bytes(chunk)
+    return der(0x06, body)
+
+
+def integer(value):
+    return der(0x02, value.to_bytes(max(1, (value.bit_length() + 8) // 8), "big"))
+
+
+def octets(data):
+    return der(0x04, data)
+
+
+def bmp(text):
+    return der(0x1E, text.encode("utf-16-be"))
+
+
+def null():
+    return der(0x05, b"")
+
+
+# 名称-值属性 { BMPString 名称, INTEGER 标志, OCTET STRING 值（UTF-16LE，以空字符结尾） }
+def name_value(name, value):
+    return seq(bmp(name), integer(0x10010001), octets((value + "\0").encode("utf-16-le")))
+
+
+# 最小的 PE32+ 映像：文件头 0x200 字节，一个 .text 节
+def pe_image():
+    data = bytearray(0x1400)
+    data[0:2] = b"MZ"
+    struct.pack_into("<I", data, 0x3C, 0x80)
+    data[0x80:0x84] = b"PE\0\0"
+    # 文件头：AMD64，1 个节，可选头 240 字节，可执行映像
+    struct.pack_into("<HHIIIHH", data, 0x84, 0x8664, 1, 0, 0, 0, 240, 0x0022)
+    optional = 0x98
+    struct.pack_into("<HBBIIIII", data, optional, 0x20B, 14, 0, 0x1200, 0, 0, 0x1000, 0x1000)
+    struct.pack_into("<QII", data, optional + 24, 0x140000000, 0x1000, 0x200)
+    struct.pack_into("<HHHHHHI", data, optional + 40, 6, 1, 0, 0, 6, 1, 0)
+    # 映像大小、文件头大小、校验和、子系统（原生）、DLL 特征
+    struct.pack_into("<IIIHH", data, optional + 56, 0x3000, 0x200, 0, 1, 0x0160)
+    struct.pack_into("<QQQQII", data, optional + 72, 0x40000, 0x1000, 0x100000, 0x1000, 0, 16)
+    section = optional + 240
+    data[section:section + 8] = b".text\0\0\0"
+    struct.pack_into("<IIIIIIHHI", data, section + 8, 0x1200, 0x1000, 0x1200, 0x200, 0, 0, 0, 0, 0x68000020)
+    # 节内容：ret 指令及可重复生成的填充
+    data[0x200] = 0xC3
+    for offset in range(0x210, 0x1400):
+        data[offset] = (offset * 7) & 0xFF
+    return bytes(data)
+
+
+# Authenticode 映像哈希：排除校验和与证书表目录项
+def authenticode(data, algorithm):
+    pe = struct.unpack_from("<I", data, 0x3C)[0]
+    optional = pe + 24
+    checksum = optional + 64
+    cert_entry = optional + 112 + 4 * 8
+    return hashlib.new(algorithm, data[:checksum] + data[checksum + 4:cert_entry] + data[cert_entry + 8:]).digest()
+
+
+# 编录成员 { 标签（哈希值的十六进制字符串，UTF-16LE）, 属性集 { 名称-值, SPC_INDIRECT_DATA } }
+def member(file_name, digest, algorithm, is_pe):
+    if is_pe:
+        data = seq(oid(OID_PE_IMAGE_DATA), seq(der(0x03, b"\0"), der(0xA0, der(0xA2, der(0x80, "<<<Obsolete>>>".encode("utf-16-be"))))))
+    else:
+        data = seq(oid(OID_CAB_DATA), der(0xA2, der(0x80, "<<<Obsolete>>>".encode("utf-16-be"))))
+    indirect = seq(data, seq(seq(oid(algorithm), null()), octets(digest)))
+    attributes = set_of(
+        seq(oid(OID_NAME_VALUE), set_of(name_value("File", file_name))),
+        seq(oid(OID_NAME_VALUE), set_of(name_value("OSAttr", OS_ATTR))),
+        seq(oid(OID_INDIRECT_DATA), set_of(indirect)),
+    )
+    return digest.hex().upper(), seq(octets(digest.hex().upper().encode("utf-16-le")), attributes)
+
+
+# 证书信任列表 { 用途, 标识, 更新时间, 成员算法, 成员, [0] 扩展 }
+def ctl(files):
+    members = []
+    for file_name, content, is_pe in files:
+        for algorithm, oid_text in (("sha1", OID_SHA1), ("sha256", OID_SHA256)):
+            digest = authenticode(content, algorithm) if is_pe else hashlib.new(algorithm, content).digest()
+            members.append(member(file_name, digest, oid_text, is_pe))
+    members.sort()
+    extensions = [
+        seq(oid(OID_NAME_VALUE), octets(name_value("OS", "_v100_X64,_v63_X64,_v62_X64"))),
+        seq(oid(OID_NAME_VALUE), octets(name_value("HWID1", HARDWARE_ID.lower()))),
+    ]
+    return seq(
+        seq(oid(OID_CATALOG_LIST)),
+        octets(uuid.uuid5(uuid.NAMESPACE_URL, "DriverIndexer/sample.cat").bytes),
+        der(0x17, b"160525000000Z"),
+        seq(oid(OID_CATALOG_LIST_MEMBER), null()),
+        seq(*[item for _, item in members]),
+        der(0xA0, seq(*extensions)),
+    )
+
+
+def openssl(*args, cwd):
+    subprocess.run(["openssl", *args], cwd=cwd, check=True, stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+
+
+# 证书链：根证书 -> PCA -> 签名证书（WHQL 用途）
+def chain(work, prefix):
+    with open(os.path.join(work, "ca.ext"), "w") as file:
+        file.write("basicConstraints=critical,CA:TRUE\nkeyUsage=critical,keyCertSign,cRLSign\nsubjectKeyIdentifier=hash\nauthorityKeyIdentifier=keyid\n")
+    with open(os.path.join(work, "signer.ext"), "w") as file:
+        file.write("basicConstraints=critical,CA:FALSE\nkeyUsage=critical,digitalSignature\nextendedKeyUsage=codeSigning,1.3.6.1.4.1.311.10.3.5\nsubjectKeyIdentifier=hash\nauthorityKeyIdentifier=keyid\n")
+    openssl("req", "-x509", "-newkey", "rsa:2048", "-nodes", "-sha256", "-days", "7300", "-subj", "/C=CN/O=DriverIndexer/CN=DriverIndexer Test Root",
+            "-keyout", prefix + "root.key", "-out", prefix + "root.pem", cwd=work)
+    for name, subject, issuer, extension in (
+        ("pca", "/C=CN/O=DriverIndexer/CN=DriverIndexer Test PCA", "root", "ca.ext"),
+        ("signer", "/C=CN/O=DriverIndexer/OU=Driver Signing/CN=DriverIndexer Test Publisher", "pca", "signer.ext"),
+    ):
+        openssl("req", "-new", "-newkey", "rsa:2048", "-nodes", "-subj", subject, "-keyout", prefix + name + ".key", "-out", prefix + name + ".csr", cwd=work)
+        openssl("x509", "-req", "-in", prefix + name + ".csr", "-CA", prefix + issuer + ".pem", "-CAkey", prefix + issuer + ".key", "-set_serial", str(uuid.uuid4().int >> 64),
+                "-sha256", "-days", "3650", "-extfile", extension, "-out", prefix + name + ".pem", cwd=work)
+
+
+# 以 CMS 签名证书信任列表（签名内容类型为 szOID_CTL）
+def sign(work, prefix, output):
+    openssl("cms", "-sign", "-binary", "-nodetach", "-in", "ctl.der", "-econtent_type", OID_CTL, "-md", "sha256", "-nosmimecap",
+            "-signer", prefix + "signer.pem", "-inkey", prefix + "signer.key", "-certfile", prefix + "pca.pem", "-outform", "DER", "-out", output, cwd=work)
+
+
+def main():
+    inf = INF.encode("ascii")
+    sys = pe_image()
+    with tempfile.TemporaryDirectory() as work:
+        with open(os.path.join(work, "ctl.der"), "wb") as file:
+            file.write(ctl([("sample.inf", inf, False), ("sample.sys", sys, True)]))
+        chain(work, "")
+        chain(work, "forged-")
+        sign(work, "", os.path.join(OUT, "sample.cat"))
+        sign(work, "forged-", os.path.join(OUT, "forged.cat"))
+        modulus = subprocess.run(["openssl", "x509", "-in", "root.pem", "-noout", "-modulus"], cwd=work, check=True, capture_output=True, text=True).stdout
+        with open(os.path.join(OUT, "root.modulus"), "wb") as file:
+            file.write(bytes.fromhex(modulus.strip().split("=", 1)[1]))
+    with open(os.path.join(OUT, "sample.inf"), "wb") as file:
+        file.write(inf)
+    with open(os.path.join(OUT, "sample.sys"), "wb") as file:
+        file.write(sys)
+
+
+if __name__ == "__main__":
+    main()